@@ -0,0 +1,10 @@
+//! Generates the gRPC service and its client from the protocol buffers under
+//! `proto/`. The compiler is vendored so that building does not depend on an
+//! installed `protoc`.
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path()?);
+    tonic_build::compile_protos("proto/user.proto")?;
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+## Titles of problem documents, by the code of the problem.
+
+blob_not_found = File not found
+delivery_not_dead = The delivery has not failed
+delivery_not_found = No delivery found
+events_unavailable = Account events are unavailable
+follower_not_found = No follower found
+forbidden = No permission for this action
+idempotency_key_in_progress = A request with the same idempotency key is in progress
+idempotency_key_reused = The idempotency key was used for a different request
+internal_error = An internal server error occurred
+invalid_body = The request body is invalid
+invalid_credentials = Authentication failed
+invalid_cursor = The cursor is invalid
+invalid_filter = A filter is invalid
+invalid_idempotency_key = The idempotency key is invalid
+invalid_image = The image is invalid
+invalid_image_dimensions = The image dimensions are not allowed
+invalid_limit = The limit is out of range
+invalid_query = The search query is invalid
+invalid_token = Authentication failed
+invalid_upload = The upload is invalid
+invitation_accepted = The invitation has already been accepted
+invitation_expired = The invitation has expired
+invitation_not_found = No invitation found
+member_not_found = No member found
+missing_file = No file was uploaded
+no_active_organization = No organization is active
+not_found = The resource was not found
+not_member = Not a member of the organization
+patch_test_failed = A test operation of the patch failed
+payload_too_large = The request body is too large
+precondition_failed = The user has changed since it was last read
+precondition_required = An If-Match header is required
+rate_limited = Too many requests
+self_follow = Users cannot follow themselves
+self_restriction = Users cannot block or mute themselves
+unauthenticated = Authentication failed
+unknown_parameter = The parameter is not supported
+unknown_sort = The sort order is not supported
+unsupported_image_type = The image type is not supported
+unsupported_media_type = The media type of the request body is not supported
+user_id_changed_recently = The user_id was changed too recently
+user_id_confusable = The user_id is too similar to an existing one
+user_id_taken = The user_id is already used
+user_moved = The user has changed their user_id
+user_not_found = No user found
+validation_failed = The submission is invalid
+webhook_not_found = No webhook subscription found
+
+## Details of problem documents.
+
+detail-invalid-field = Submission for field { $field } is invalid: { $reason }
+detail-missing-credentials = required user_id and password
+detail-signin-mismatch = The username and/or password submitted to not match any user in the system
+detail-signin-user-not-found = User for submitted credentials does not exist
+detail-user-id-confusable = a similar user_id is already used
+detail-user-id-taken = already same user_id is used
+detail-user-not-found = No data was found for the user
+
+## Reasons why the value of a field is invalid.
+
+validation-blank = must not be blank
+validation-boolean = must be true or false
+validation-email = must be a valid email address
+validation-event-type = must be one of { $types }
+validation-integer = must be an integer
+validation-language-tag = must be a valid BCP 47 language tag: { $error }
+validation-length-range = must be between { $min } and { $max } characters
+validation-not-patchable = is not a field which can be patched
+validation-number = must be a number
+validation-reserved = is reserved
+validation-role = must be admin or member
+validation-same-user-id = must be different from the current user_id
+validation-string-or-null = must be a string or null
+validation-time-zone = must be a valid IANA time zone name
+validation-timestamp = must be an RFC 3339 timestamp
+validation-too-long = must be less or equal to { $max }
+validation-too-short = must be greater than or equal to { $min }
+validation-url = must be a valid url: { $error }
+validation-url-scheme = must be an http or https url
+validation-user-id-characters = may only contain { $characters } letters and digits or the symbols '{ $symbols }'
+validation-uuid = must be a uuid
@@ -0,0 +1,83 @@
+## Titles of problem documents, by the code of the problem.
+
+blob_not_found = ファイルが見つかりません
+delivery_not_dead = 配信は失敗していません
+delivery_not_found = 配信が見つかりません
+events_unavailable = アカウントのイベントを利用できません
+follower_not_found = フォロワーが見つかりません
+forbidden = この操作を行う権限がありません
+idempotency_key_in_progress = 同じ冪等キーのリクエストを処理中です
+idempotency_key_reused = 冪等キーが別のリクエストで使用されています
+internal_error = サーバー内部でエラーが発生しました
+invalid_body = リクエスト本文が不正です
+invalid_credentials = 認証に失敗しました
+invalid_cursor = カーソルが不正です
+invalid_filter = フィルターが不正です
+invalid_idempotency_key = 冪等キーが不正です
+invalid_image = 画像が不正です
+invalid_image_dimensions = 画像のサイズが許可されていません
+invalid_limit = 件数の指定が範囲外です
+invalid_query = 検索クエリが不正です
+invalid_token = 認証に失敗しました
+invalid_upload = アップロードが不正です
+invitation_accepted = この招待はすでに承諾されています
+invitation_expired = この招待は有効期限が切れています
+invitation_not_found = 招待が見つかりません
+member_not_found = メンバーが見つかりません
+missing_file = ファイルがアップロードされていません
+no_active_organization = 選択中の組織がありません
+not_found = リソースが見つかりません
+not_member = 組織のメンバーではありません
+patch_test_failed = パッチの test 操作が失敗しました
+payload_too_large = リクエスト本文が大きすぎます
+precondition_failed = 前回の取得以降にユーザーが変更されています
+precondition_required = If-Match ヘッダーが必要です
+rate_limited = リクエストが多すぎます
+self_follow = 自分自身をフォローすることはできません
+self_restriction = 自分自身をブロックまたはミュートすることはできません
+unauthenticated = 認証に失敗しました
+unknown_parameter = サポートされていないパラメーターです
+unknown_sort = サポートされていない並び順です
+unsupported_image_type = サポートされていない画像形式です
+unsupported_media_type = サポートされていないメディアタイプです
+user_id_changed_recently = user_id は最近変更されたばかりです
+user_id_confusable = user_id が既存のものと似すぎています
+user_id_taken = その user_id はすでに使われています
+user_moved = ユーザーの user_id が変更されました
+user_not_found = ユーザーが見つかりません
+validation_failed = 入力内容が不正です
+webhook_not_found = Webhook の購読が見つかりません
+
+## Details of problem documents.
+
+detail-invalid-field = { $field } の値が不正です: { $reason }
+detail-missing-credentials = user_id と password は必須です
+detail-signin-mismatch = 送信されたユーザー名またはパスワードに一致するユーザーはいません
+detail-signin-user-not-found = 送信された認証情報のユーザーは存在しません
+detail-user-id-confusable = 似た user_id がすでに使われています
+detail-user-id-taken = 同じ user_id がすでに使われています
+detail-user-not-found = ユーザーのデータが見つかりません
+
+## Reasons why the value of a field is invalid.
+
+validation-blank = 空白にはできません
+validation-boolean = true または false である必要があります
+validation-email = 有効なメールアドレスである必要があります
+validation-event-type = { $types } のいずれかである必要があります
+validation-integer = 整数である必要があります
+validation-language-tag = BCP 47 の言語タグである必要があります: { $error }
+validation-length-range = { $min } 文字以上 { $max } 文字以下である必要があります
+validation-not-patchable = パッチで変更できるフィールドではありません
+validation-number = 数値である必要があります
+validation-reserved = 予約されています
+validation-role = admin または member である必要があります
+validation-same-user-id = 現在の user_id と異なる必要があります
+validation-string-or-null = 文字列または null である必要があります
+validation-time-zone = IANA のタイムゾーン名である必要があります
+validation-timestamp = RFC 3339 形式の日時である必要があります
+validation-too-long = { $max } 文字以下である必要があります
+validation-too-short = { $min } 文字以上である必要があります
+validation-url = 有効な URL である必要があります: { $error }
+validation-url-scheme = http または https の URL である必要があります
+validation-user-id-characters = { $characters } の英数字と記号「{ $symbols }」のみ使用できます
+validation-uuid = UUID である必要があります
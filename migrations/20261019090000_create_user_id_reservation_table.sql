@@ -0,0 +1,9 @@
+ALTER TABLE user_ ADD COLUMN user_id_changed_at TIMESTAMP;
+
+CREATE TABLE user_id_reservation (
+    user_id VARCHAR (20) NOT NULL,
+    PRIMARY KEY (user_id),
+    owner_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    reserved_until TIMESTAMP NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
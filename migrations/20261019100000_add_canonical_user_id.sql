@@ -0,0 +1,19 @@
+-- The canonical (case folded) form and the confusable skeleton of each user_id
+-- are computed by the application, since only it implements the case folding
+-- and the UTS #39 skeletons. The columns of existing rows are left empty here and
+-- backfilled when the application starts, see `database::backfill`. Empty values
+-- do not collide in the unique indexes, so legacy data never fails this migration.
+ALTER TABLE user_
+    ADD COLUMN canonical_user_id VARCHAR (80),
+    ADD COLUMN user_id_skeleton TEXT;
+
+CREATE UNIQUE INDEX user_canonical_user_id_idx ON user_ (canonical_user_id);
+CREATE UNIQUE INDEX user_user_id_skeleton_idx ON user_ (user_id_skeleton);
+
+ALTER TABLE user_id_reservation
+    ADD COLUMN canonical_user_id VARCHAR (80),
+    ADD COLUMN user_id_skeleton TEXT;
+
+CREATE UNIQUE INDEX user_id_reservation_canonical_user_id_idx
+    ON user_id_reservation (canonical_user_id);
+CREATE INDEX user_id_reservation_skeleton_idx ON user_id_reservation (user_id_skeleton);
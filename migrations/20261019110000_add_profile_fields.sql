@@ -0,0 +1,7 @@
+ALTER TABLE user_
+    ADD COLUMN display_name VARCHAR (50),
+    ADD COLUMN bio VARCHAR (300),
+    ADD COLUMN avatar_url TEXT,
+    ADD COLUMN locale VARCHAR (35),
+    ADD COLUMN timezone VARCHAR (64),
+    ADD COLUMN website TEXT;
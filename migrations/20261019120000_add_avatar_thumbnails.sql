@@ -0,0 +1,3 @@
+ALTER TABLE user_
+    ADD COLUMN avatar_key TEXT,
+    ADD COLUMN avatar_thumbnails JSONB;
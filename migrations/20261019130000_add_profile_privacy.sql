@@ -0,0 +1,7 @@
+-- Users may restrict who can see each field of their profile and hide their
+-- account from lookups entirely. Fields which are missing from
+-- profile_visibility are public.
+ALTER TABLE user_
+    ADD COLUMN role VARCHAR (16) NOT NULL DEFAULT 'user',
+    ADD COLUMN profile_visibility JSONB NOT NULL DEFAULT '{}',
+    ADD COLUMN hidden BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,16 @@
+-- A follow starts out as pending when the followed user requires approval and
+-- becomes accepted once they approve it.
+CREATE TABLE follow (
+    follower_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    followee_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    PRIMARY KEY (follower_id, followee_id),
+    status VARCHAR (16) NOT NULL,
+    created_at TIMESTAMP NOT NULL,
+    CHECK (follower_id <> followee_id)
+);
+
+CREATE INDEX follow_followee_idx ON follow (followee_id, status, created_at, follower_id);
+CREATE INDEX follow_follower_idx ON follow (follower_id, status, created_at, followee_id);
+
+ALTER TABLE user_
+    ADD COLUMN approve_followers BOOLEAN NOT NULL DEFAULT FALSE;
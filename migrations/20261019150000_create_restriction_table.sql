@@ -0,0 +1,11 @@
+-- Blocks and mutes which a user has placed on other users.
+CREATE TABLE restriction (
+    owner_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    target_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    kind VARCHAR (16) NOT NULL,
+    PRIMARY KEY (owner_id, target_id, kind),
+    created_at TIMESTAMP NOT NULL,
+    CHECK (owner_id <> target_id)
+);
+
+CREATE INDEX restriction_target_idx ON restriction (target_id, kind);
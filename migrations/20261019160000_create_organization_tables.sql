@@ -0,0 +1,32 @@
+CREATE TABLE organization (
+    id uuid NOT NULL,
+    PRIMARY KEY (id),
+    name VARCHAR (100) NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE TABLE membership (
+    organization_id uuid NOT NULL REFERENCES organization (id) ON DELETE CASCADE,
+    user_id uuid NOT NULL REFERENCES user_ (id) ON DELETE CASCADE,
+    PRIMARY KEY (organization_id, user_id),
+    role VARCHAR (16) NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX membership_user_idx ON membership (user_id);
+
+-- Only a hash of the invitation token is stored. Invitations addressed to an
+-- email can be accepted once, while link invitations can be accepted by anyone
+-- holding the link until they expire.
+CREATE TABLE invitation (
+    id uuid NOT NULL,
+    PRIMARY KEY (id),
+    organization_id uuid NOT NULL REFERENCES organization (id) ON DELETE CASCADE,
+    token_hash TEXT NOT NULL UNIQUE,
+    email VARCHAR (254),
+    role VARCHAR (16) NOT NULL,
+    invited_by uuid REFERENCES user_ (id) ON DELETE SET NULL,
+    expires_at TIMESTAMP NOT NULL,
+    accepted_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL
+);
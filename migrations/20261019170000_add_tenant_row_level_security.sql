@@ -0,0 +1,54 @@
+-- Organizations are the tenants of the application. Every table which holds
+-- data belonging to a single tenant carries a tenant_id and is protected by row
+-- level security.
+--
+-- Policies apply to the track_tenant role, which scoped transactions switch to
+-- with SET LOCAL ROLE after setting app.tenant_id and app.user_id. The role
+-- which owns the tables is not subject to the policies, so queries made outside
+-- of a scoped transaction act on behalf of the system.
+DO $$
+BEGIN
+    CREATE ROLE track_tenant NOLOGIN;
+EXCEPTION
+    WHEN duplicate_object OR unique_violation THEN NULL;
+END
+$$;
+
+GRANT track_tenant TO CURRENT_USER;
+GRANT USAGE ON SCHEMA public TO track_tenant;
+GRANT SELECT, INSERT, UPDATE, DELETE ON ALL TABLES IN SCHEMA public TO track_tenant;
+ALTER DEFAULT PRIVILEGES IN SCHEMA public
+    GRANT SELECT, INSERT, UPDATE, DELETE ON TABLES TO track_tenant;
+
+ALTER TABLE membership RENAME COLUMN organization_id TO tenant_id;
+ALTER TABLE invitation RENAME COLUMN organization_id TO tenant_id;
+
+CREATE FUNCTION app_current_tenant() RETURNS uuid AS $$
+    SELECT NULLIF(current_setting('app.tenant_id', true), '')::uuid
+$$ LANGUAGE SQL STABLE;
+
+CREATE FUNCTION app_current_user() RETURNS uuid AS $$
+    SELECT NULLIF(current_setting('app.user_id', true), '')::uuid
+$$ LANGUAGE SQL STABLE;
+
+ALTER TABLE organization ENABLE ROW LEVEL SECURITY;
+ALTER TABLE membership ENABLE ROW LEVEL SECURITY;
+ALTER TABLE invitation ENABLE ROW LEVEL SECURITY;
+
+-- Users can see every organization they belong to, but only change the active one.
+CREATE POLICY tenant_isolation ON organization
+    USING (
+        id = app_current_tenant()
+        OR id IN (SELECT tenant_id FROM membership WHERE user_id = app_current_user())
+    )
+    WITH CHECK (id = app_current_tenant());
+
+-- Users can see their own memberships of every organization, but only the
+-- memberships of others in the active one.
+CREATE POLICY tenant_isolation ON membership
+    USING (tenant_id = app_current_tenant() OR user_id = app_current_user())
+    WITH CHECK (tenant_id = app_current_tenant());
+
+CREATE POLICY tenant_isolation ON invitation
+    USING (tenant_id = app_current_tenant())
+    WITH CHECK (tenant_id = app_current_tenant());
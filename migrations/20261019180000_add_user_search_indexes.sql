@@ -0,0 +1,6 @@
+-- Users are searched by user_id and nickname, with trigram similarity for fuzzy
+-- matches and LIKE for prefixes. Both are served by trigram indexes.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX user_canonical_user_id_trgm_idx ON user_ USING GIN (canonical_user_id gin_trgm_ops);
+CREATE INDEX user_nickname_trgm_idx ON user_ USING GIN (lower(nickname) gin_trgm_ops);
@@ -0,0 +1,14 @@
+-- Every change to a user bumps its version, which clients send back in If-Match
+-- to make sure they are not overwriting changes they have not seen. The trigger
+-- keeps the version correct no matter which query changes the row.
+ALTER TABLE user_ ADD COLUMN version BIGINT NOT NULL DEFAULT 1;
+
+CREATE FUNCTION bump_user_version() RETURNS trigger AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER user_version BEFORE UPDATE ON user_
+    FOR EACH ROW EXECUTE FUNCTION bump_user_version();
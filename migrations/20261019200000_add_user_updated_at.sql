@@ -0,0 +1,11 @@
+-- The time of the last change to a user, sent as Last-Modified. It is kept by
+-- the same trigger as the version so the two never disagree.
+ALTER TABLE user_ ADD COLUMN updated_at TIMESTAMP NOT NULL DEFAULT (now() AT TIME ZONE 'utc');
+
+CREATE OR REPLACE FUNCTION bump_user_version() RETURNS trigger AS $$
+BEGIN
+    NEW.version := OLD.version + 1;
+    NEW.updated_at := now() AT TIME ZONE 'utc';
+    RETURN NEW;
+END
+$$ LANGUAGE plpgsql;
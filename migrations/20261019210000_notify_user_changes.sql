@@ -0,0 +1,12 @@
+-- Announce every change to a user, so that each instance of the application can
+-- evict the user from its cache. Notifications are only delivered once the
+-- transaction which made the change commits.
+CREATE FUNCTION notify_user_changed() RETURNS trigger AS $$
+BEGIN
+    PERFORM pg_notify('user_changed', OLD.id::text);
+    RETURN NULL;
+END
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER user_changed AFTER UPDATE OR DELETE ON user_
+    FOR EACH ROW EXECUTE FUNCTION notify_user_changed();
@@ -0,0 +1,32 @@
+-- Downstream systems subscribe to events of accounts with a url and a secret with
+-- which deliveries are signed.
+CREATE TABLE webhook_subscription (
+    id uuid NOT NULL,
+    PRIMARY KEY (id),
+    url TEXT NOT NULL,
+    secret TEXT NOT NULL,
+    event_types TEXT[] NOT NULL,
+    created_at TIMESTAMP NOT NULL
+);
+
+-- The outbox of deliveries, one for each subscription to an event. Deliveries are
+-- written in the same transaction as the change they announce, and sent by the
+-- dispatcher once they are due. Deliveries which keep failing become dead until an
+-- admin replays them.
+CREATE TABLE webhook_delivery (
+    id uuid NOT NULL,
+    PRIMARY KEY (id),
+    subscription_id uuid NOT NULL REFERENCES webhook_subscription (id) ON DELETE CASCADE,
+    event_type VARCHAR (32) NOT NULL,
+    payload JSONB NOT NULL,
+    status VARCHAR (16) NOT NULL,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMP NOT NULL,
+    last_error TEXT,
+    created_at TIMESTAMP NOT NULL,
+    delivered_at TIMESTAMP
+);
+
+CREATE INDEX webhook_delivery_due_idx ON webhook_delivery (next_attempt_at)
+    WHERE status = 'pending';
+CREATE INDEX webhook_delivery_status_idx ON webhook_delivery (status, created_at);
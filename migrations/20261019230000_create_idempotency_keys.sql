@@ -0,0 +1,16 @@
+-- Keys sent in the Idempotency-Key header of POST requests, scoped to the
+-- credentials of the request. The response is stored once the first request with
+-- a key has been handled, and replayed to its retries until the key expires.
+CREATE TABLE idempotency_key (
+    principal VARCHAR (64) NOT NULL,
+    key VARCHAR (255) NOT NULL,
+    PRIMARY KEY (principal, key),
+    fingerprint VARCHAR (64) NOT NULL,
+    status SMALLINT,
+    headers JSONB,
+    body BYTEA,
+    created_at TIMESTAMP NOT NULL,
+    expires_at TIMESTAMP NOT NULL
+);
+
+CREATE INDEX idempotency_key_expires_at_idx ON idempotency_key (expires_at);
@@ -0,0 +1,12 @@
+-- Every user and reservation needs both forms of its user_id, since unique
+-- indexes accept any number of NULLs and a row without them would escape the
+-- case insensitive uniqueness of handles. The constraints are added NOT VALID, so
+-- they hold for every row written from now on, and are validated by
+-- `database::backfill` once the forms of existing rows were filled in.
+ALTER TABLE user_
+    ADD CONSTRAINT user_handle_forms_present
+    CHECK (canonical_user_id IS NOT NULL AND user_id_skeleton IS NOT NULL) NOT VALID;
+
+ALTER TABLE user_id_reservation
+    ADD CONSTRAINT user_id_reservation_handle_forms_present
+    CHECK (canonical_user_id IS NOT NULL AND user_id_skeleton IS NOT NULL) NOT VALID;
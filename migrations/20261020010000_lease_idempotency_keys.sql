@@ -0,0 +1,7 @@
+-- The time until which the request which claimed a key is handling it. A request
+-- which crashed without storing a response or releasing its key leaves the key
+-- in progress, so a retry may take the key over once this has passed. It is
+-- cleared when the response is stored.
+ALTER TABLE idempotency_key ADD COLUMN locked_until TIMESTAMP;
+
+UPDATE idempotency_key SET locked_until = created_at WHERE status IS NULL;
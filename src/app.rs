@@ -1,23 +1,36 @@
 use crate::{
-    configuration::{application::ApplicationSettings, auth::AuthSettings, Settings},
+    blob::{self, BlobStore},
+    configuration::{application::ApplicationSettings, Settings},
     database::Database,
-    domain::user::actions::SignupError,
-    error::ErrorResponse,
-    routes::{private::private_services, public::public_services},
+    domain::{
+        user::{
+            cache::{self, UserCache},
+            events::{self, EventHub},
+            search::SearchRateLimiter,
+        },
+        webhook::dispatcher,
+    },
+    graphql, grpc,
+    middleware::{idempotency::Idempotency, negotiation as negotiated, problem},
+    negotiation,
+    pagination::CursorSigner,
+    routes::{api_services, legacy, public::operational_services},
 };
 use actix_web::{
     dev::Server,
-    error::InternalError,
     web::{self, JsonConfig},
-    App, HttpResponse, HttpServer,
+    App, HttpServer,
 };
-use std::{fmt::Debug, net::TcpListener};
+use futures_util::future::BoxFuture;
+use std::{fmt::Debug, net::TcpListener, sync::Arc};
 
 /// A wrapper for the actix instance. It hides the details of the actix instance
 /// and only exposes functionality that we need elsewhere.
 pub struct Application {
     settings: ApplicationSettings,
     server: Server,
+    grpc_port: Option<u16>,
+    grpc: Option<BoxFuture<'static, Result<(), tonic::transport::Error>>>,
 }
 
 impl Debug for Application {
@@ -25,6 +38,7 @@ impl Debug for Application {
         f.debug_struct("Application")
             .field("settings", &self.settings)
             .field("server", &"actix_web::dev::Server")
+            .field("grpc_port", &self.grpc_port)
             .finish()
     }
 }
@@ -36,7 +50,7 @@ impl Application {
     pub async fn build(configuration: Settings, db: Database) -> anyhow::Result<Self> {
         tracing::debug!("Building application");
 
-        let mut settings = configuration.application;
+        let mut settings = configuration.application.clone();
         tracing::debug!("settings: {settings:?}");
 
         let address = format!("{}:{}", &settings.host, &settings.port);
@@ -46,9 +60,37 @@ impl Application {
         let port = listener.local_addr()?.port();
         settings.port = port;
 
-        let server = Self::build_actix_instance(listener, db, configuration.auth).await?;
+        let blob_store = blob::init(&configuration.blob)?;
+        // Shared by both servers, so that changes made through one are seen by the
+        // other.
+        let user_cache = Arc::new(UserCache::new(&configuration.cache));
 
-        Ok(Self { settings, server })
+        let (grpc_port, grpc) = match configuration.grpc.enabled {
+            true => {
+                let listener =
+                    TcpListener::bind((settings.host.as_str(), configuration.grpc.port))?;
+                let port = listener.local_addr()?.port();
+                tracing::debug!("grpc port: {port}");
+                let users = grpc::Users::new(
+                    db.clone(),
+                    user_cache.clone(),
+                    configuration.auth.clone(),
+                    configuration.user.clone(),
+                );
+                (Some(port), Some(grpc::serve(listener, users)?))
+            }
+            false => (None, None),
+        };
+
+        let server =
+            Self::build_actix_instance(listener, db, blob_store, user_cache, configuration).await?;
+
+        Ok(Self {
+            settings,
+            server,
+            grpc_port,
+            grpc,
+        })
     }
 
     /// Contains the logic for assembling and running the actix-web instance. This
@@ -57,18 +99,70 @@ impl Application {
     async fn build_actix_instance(
         listener: TcpListener,
         db: Database,
-        auth_settings: AuthSettings,
+        blob_store: Arc<dyn BlobStore>,
+        user_cache: Arc<UserCache>,
+        configuration: Settings,
     ) -> anyhow::Result<Server> {
+        let Settings {
+            auth: auth_settings,
+            user: user_settings,
+            organization: organization_settings,
+            cache: cache_settings,
+            api: api_settings,
+            webhook: webhook_settings,
+            idempotency: idempotency_settings,
+            ..
+        } = configuration;
         let db = web::Data::new(db);
+        let blob_store = web::Data::from(blob_store);
+        let graphql_schema = web::Data::new(graphql::schema(
+            CursorSigner::new(auth_settings.cursorsecret.clone()),
+            user_settings.clone(),
+        ));
         let auth_settings = web::Data::new(auth_settings);
+        let user_cache = web::Data::from(user_cache);
+        if cache_settings.listen {
+            let (cache, db) = (user_cache.clone(), db.clone());
+            tokio::spawn(async move { cache::listen(&cache, &db).await });
+        }
+        let event_hub = web::Data::new(EventHub::default());
+        {
+            let (hub, db) = (event_hub.clone(), db.clone());
+            tokio::spawn(async move { events::listen(&hub, &db).await });
+        }
+        if webhook_settings.dispatch {
+            let db = db.clone();
+            tokio::spawn(async move { dispatcher::dispatch(&db, &webhook_settings).await });
+        }
+        let search_limiter = web::Data::new(SearchRateLimiter::new(
+            user_settings.search_requests_per_minute,
+        ));
+        let user_settings = web::Data::new(user_settings);
+        let organization_settings = web::Data::new(organization_settings);
+        let api_settings = web::Data::new(api_settings);
+        let idempotency_settings = web::Data::new(idempotency_settings);
         let json_cfg = Self::init_json_config();
 
         let server = HttpServer::new(move || {
             App::new()
-                .configure(private_services)
-                .configure(public_services)
+                .wrap_fn(problem::instance)
+                .wrap_fn(negotiated::encode)
+                .wrap(Idempotency)
+                .configure(api_services)
+                .configure(operational_services)
+                .configure(graphql::services)
+                .default_service(web::to(legacy::redirect))
                 .app_data(db.clone())
+                .app_data(blob_store.clone())
                 .app_data(auth_settings.clone())
+                .app_data(user_settings.clone())
+                .app_data(search_limiter.clone())
+                .app_data(user_cache.clone())
+                .app_data(event_hub.clone())
+                .app_data(organization_settings.clone())
+                .app_data(api_settings.clone())
+                .app_data(idempotency_settings.clone())
+                .app_data(graphql_schema.clone())
                 .app_data(json_cfg.clone())
         })
         .listen(listener)?
@@ -82,31 +176,32 @@ impl Application {
         self.settings.port
     }
 
-    /// Start the application and run on an infinite loop.
-    pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
-        self.server.await
+    /// Expose the port of the gRPC server, if it is enabled.
+    pub fn grpc_port(&self) -> Option<u16> {
+        self.grpc_port
     }
 
-    /// Initialize custom configuration for custom error messages
-    fn init_json_config() -> JsonConfig {
-        web::JsonConfig::default().error_handler(|err, req| {
-            let error = match err {
-                actix_web::error::JsonPayloadError::Deserialize(e) => {
-                    if req.path().ends_with("/signup") {
-                        InternalError::from_response(
-                            e,
-                            HttpResponse::BadRequest()
-                                .json(ErrorResponse::from(&SignupError::InvalidPayload)),
-                        )
-                    } else {
-                        let message = e.to_string();
-                        InternalError::from_response(e, HttpResponse::BadRequest().json(message))
-                    }
+    /// Start the application and run on an infinite loop. The gRPC server stops
+    /// along with the HTTP server.
+    pub async fn run_until_stopped(self) -> Result<(), std::io::Error> {
+        let grpc = self.grpc.map(|grpc| {
+            tokio::spawn(async move {
+                if let Err(e) = grpc.await {
+                    tracing::error!("The gRPC server failed: {e}");
                 }
-                _ => return err.into(),
-            };
+            })
+        });
 
-            error.into()
-        })
+        let result = self.server.await;
+        if let Some(grpc) = grpc {
+            grpc.abort();
+        }
+        result
+    }
+
+    /// Initialize custom configuration so that invalid bodies are answered with
+    /// problem documents.
+    fn init_json_config() -> JsonConfig {
+        web::JsonConfig::default().error_handler(negotiation::rejected)
     }
 }
@@ -3,17 +3,20 @@ use argon2::{
     Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
 };
 use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, EncodingKey, Header};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
 use secrecy::{ExposeSecret, Secret};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TokenClaims {
     pub sub: String,
     pub iat: usize,
     pub exp: usize,
+    /// The organization which the user is currently acting in, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub org: Option<Uuid>,
 }
 
 pub fn verify_password(
@@ -32,7 +35,11 @@ pub fn verify_password(
     Ok(())
 }
 
-pub fn issue_jwt(user_id: &Uuid, jwt_secret: &Secret<String>) -> Result<String, JwtError> {
+pub fn issue_jwt(
+    user_id: &Uuid,
+    organization_id: Option<&Uuid>,
+    jwt_secret: &Secret<String>,
+) -> Result<String, JwtError> {
     let now = Utc::now();
     let iat = now.timestamp() as usize;
     let exp = (now + Duration::minutes(60)).timestamp() as usize; // TODO: make this configurable
@@ -40,6 +47,7 @@ pub fn issue_jwt(user_id: &Uuid, jwt_secret: &Secret<String>) -> Result<String,
         sub: user_id.to_string(),
         exp,
         iat,
+        org: organization_id.copied(),
     };
 
     tracing::debug!("Encoding JWT...");
@@ -53,6 +61,19 @@ pub fn issue_jwt(user_id: &Uuid, jwt_secret: &Secret<String>) -> Result<String,
     Ok(token)
 }
 
+/// Decode a JWT issued by [issue_jwt], checking its signature and expiry.
+pub fn verify_jwt(
+    token: &str,
+    jwt_secret: &Secret<String>,
+) -> Result<TokenClaims, jsonwebtoken::errors::Error> {
+    decode::<TokenClaims>(
+        token,
+        &DecodingKey::from_secret(jwt_secret.expose_secret().as_ref()),
+        &Validation::default(),
+    )
+    .map(|data| data.claims)
+}
+
 pub fn hash_password(password: &Secret<String>) -> Result<String, password_hash::Error> {
     let salt = SaltString::generate(&mut OsRng);
     Ok(Argon2::default()
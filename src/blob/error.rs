@@ -0,0 +1,18 @@
+use std::io;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum BlobError {
+    #[error("The key '{0}' is not a valid blob key")]
+    InvalidKey(String),
+    #[error("Encountered file system IO error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Failed to reach the object store: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("The object store responded with status {status}: {body}")]
+    UnexpectedResponse { status: u16, body: String },
+    #[error("The object store endpoint '{0}' is not a valid url")]
+    InvalidEndpoint(String),
+    #[error("The s3 backend was selected but no s3 settings were provided")]
+    MissingS3Config,
+}
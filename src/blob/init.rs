@@ -0,0 +1,19 @@
+use super::{BlobError, BlobStore, LocalBlobStore, S3BlobStore};
+use crate::configuration::blob::{BlobBackend, BlobSettings};
+use std::sync::Arc;
+
+/// Initializes the blob store selected by the configuration.
+#[tracing::instrument(name = "init_blob_store")]
+pub fn init(settings: &BlobSettings) -> Result<Arc<dyn BlobStore>, BlobError> {
+    tracing::info!("Initializing {} blob store", settings.backend);
+
+    let store: Arc<dyn BlobStore> = match settings.backend {
+        BlobBackend::Local => Arc::new(LocalBlobStore::new(&settings.path, &settings.url)),
+        BlobBackend::S3 => {
+            let s3 = settings.s3.as_ref().ok_or(BlobError::MissingS3Config)?;
+            Arc::new(S3BlobStore::new(s3, &settings.url))
+        }
+    };
+
+    Ok(store)
+}
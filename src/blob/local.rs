@@ -0,0 +1,80 @@
+use super::{join_url, validate_key, Blob, BlobError, BlobStore};
+use async_trait::async_trait;
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+/// Stores files in a directory on the local filesystem. The media type of a file
+/// is derived from the extension of its key.
+#[derive(Debug, Clone)]
+pub struct LocalBlobStore {
+    root: PathBuf,
+    base_url: String,
+}
+
+impl LocalBlobStore {
+    pub fn new(root: impl AsRef<Path>, base_url: &str) -> Self {
+        Self {
+            root: root.as_ref().to_owned(),
+            base_url: base_url.to_owned(),
+        }
+    }
+
+    fn path(&self, key: &str) -> Result<PathBuf, BlobError> {
+        validate_key(key)?;
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalBlobStore {
+    #[tracing::instrument(skip(data))]
+    async fn put(&self, key: &str, _content_type: &str, data: Vec<u8>) -> Result<(), BlobError> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        tokio::fs::write(path, data).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument]
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobError> {
+        let path = self.path(key)?;
+
+        match tokio::fs::read(&path).await {
+            Ok(data) => Ok(Some(Blob {
+                content_type: content_type_for(&path).into(),
+                data,
+            })),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[tracing::instrument]
+    async fn delete(&self, key: &str) -> Result<(), BlobError> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Err(e) if e.kind() != ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    fn url(&self, key: &str) -> String {
+        join_url(&self.base_url, key)
+    }
+}
+
+fn content_type_for(path: &Path) -> &'static str {
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        _ => "application/octet-stream",
+    }
+}
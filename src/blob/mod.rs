@@ -0,0 +1,61 @@
+//! An abstraction over the storage of binary files, such as avatar images. Files are
+//! addressed by a key which looks like a relative path, for example
+//! `avatars/<uuid>/128.png`. The backend is selected by configuration, see
+//! [crate::configuration::blob::BlobSettings].
+
+mod error;
+mod init;
+mod local;
+mod s3;
+
+use async_trait::async_trait;
+use std::fmt::Debug;
+
+pub use error::BlobError;
+pub use init::init;
+pub use local::LocalBlobStore;
+pub use s3::S3BlobStore;
+
+/// A stored file along with its media type.
+#[derive(Debug)]
+pub struct Blob {
+    pub content_type: String,
+    pub data: Vec<u8>,
+}
+
+/// A place to put files. Implementations must be safe to share between workers.
+#[async_trait]
+pub trait BlobStore: Debug + Send + Sync {
+    /// Store a file under the given key, replacing anything which was there.
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), BlobError>;
+
+    /// Retrieve a file. Returns `None` if nothing is stored under the key.
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobError>;
+
+    /// Remove a file. Removing a key which does not exist is not an error.
+    async fn delete(&self, key: &str) -> Result<(), BlobError>;
+
+    /// The url at which the file stored under the key can be downloaded.
+    fn url(&self, key: &str) -> String;
+}
+
+/// Confirm that a key is a plain relative path so that it cannot be used to
+/// escape the storage location.
+fn validate_key(key: &str) -> Result<(), BlobError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('/')
+        && key
+            .split('/')
+            .all(|segment| !segment.is_empty() && segment != "." && segment != "..");
+
+    if valid {
+        Ok(())
+    } else {
+        Err(BlobError::InvalidKey(key.to_owned()))
+    }
+}
+
+/// Join a base url and a key.
+fn join_url(base: &str, key: &str) -> String {
+    format!("{}/{}", base.trim_end_matches('/'), key)
+}
@@ -0,0 +1,217 @@
+use super::{join_url, validate_key, Blob, BlobError, BlobStore};
+use crate::configuration::blob::S3Settings;
+use async_trait::async_trait;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::{header, Method, StatusCode};
+use secrecy::{ExposeSecret, Secret};
+use sha2::{Digest, Sha256};
+
+const SERVICE: &str = "s3";
+const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Stores files in an S3 compatible object store. Buckets are addressed path
+/// style (`<endpoint>/<bucket>/<key>`) so that self hosted stores such as MinIO
+/// work without any DNS configuration. Requests are signed with AWS Signature
+/// Version 4.
+#[derive(Debug, Clone)]
+pub struct S3BlobStore {
+    client: reqwest::Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: Secret<String>,
+    secret_key: Secret<String>,
+    base_url: String,
+}
+
+impl S3BlobStore {
+    pub fn new(settings: &S3Settings, base_url: &str) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            endpoint: settings.endpoint.trim_end_matches('/').to_owned(),
+            bucket: settings.bucket.to_owned(),
+            region: settings.region.to_owned(),
+            access_key: settings.accesskey.clone(),
+            secret_key: settings.secretkey.clone(),
+            base_url: base_url.to_owned(),
+        }
+    }
+
+    /// Build a signed request for the object stored under the key.
+    fn request(
+        &self,
+        method: Method,
+        key: &str,
+        content_type: Option<&str>,
+        body: Vec<u8>,
+    ) -> Result<reqwest::RequestBuilder, BlobError> {
+        validate_key(key)?;
+
+        let path = format!("/{}/{}", self.bucket, key);
+        let canonical_uri = uri_encode(&path);
+        let url = reqwest::Url::parse(&format!("{}{}", self.endpoint, canonical_uri))
+            .map_err(|_| BlobError::InvalidEndpoint(self.endpoint.to_owned()))?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_owned(),
+            (None, _) => Err(BlobError::InvalidEndpoint(self.endpoint.to_owned()))?,
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+
+        let mut headers = vec![
+            ("host", host),
+            ("x-amz-content-sha256", payload_hash.clone()),
+            ("x-amz-date", amz_date.clone()),
+        ];
+        if let Some(content_type) = content_type {
+            headers.push(("content-type", content_type.to_owned()));
+        }
+        headers.sort_by(|a, b| a.0.cmp(b.0));
+
+        let canonical_headers: String = headers
+            .iter()
+            .map(|(name, value)| format!("{name}:{}\n", value.trim()))
+            .collect();
+        let signed_headers = headers
+            .iter()
+            .map(|(name, _)| *name)
+            .collect::<Vec<_>>()
+            .join(";");
+
+        let canonical_request = format!(
+            "{}\n{}\n\n{}\n{}\n{}",
+            method.as_str(),
+            canonical_uri,
+            canonical_headers,
+            signed_headers,
+            payload_hash
+        );
+
+        let scope = format!("{date}/{}/{SERVICE}/aws4_request", self.region);
+        let string_to_sign = format!(
+            "{ALGORITHM}\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let secret = format!("AWS4{}", self.secret_key.expose_secret());
+        let signing_key = [date.as_str(), self.region.as_str(), SERVICE, "aws4_request"]
+            .iter()
+            .fold(secret.into_bytes(), |key, part| {
+                hmac_sha256(&key, part.as_bytes())
+            });
+        let signature = hex(&hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "{ALGORITHM} Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.access_key.expose_secret()
+        );
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .header(header::AUTHORIZATION, authorization)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("x-amz-date", amz_date);
+
+        if let Some(content_type) = content_type {
+            request = request.header(header::CONTENT_TYPE, content_type);
+        }
+
+        Ok(request.body(body))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3BlobStore {
+    #[tracing::instrument(skip(data))]
+    async fn put(&self, key: &str, content_type: &str, data: Vec<u8>) -> Result<(), BlobError> {
+        let response = self
+            .request(Method::PUT, key, Some(content_type), data)?
+            .send()
+            .await?;
+
+        ensure_success(response).await?;
+
+        Ok(())
+    }
+
+    #[tracing::instrument]
+    async fn get(&self, key: &str) -> Result<Option<Blob>, BlobError> {
+        let response = self
+            .request(Method::GET, key, None, Vec::new())?
+            .send()
+            .await?;
+
+        if response.status() == StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+
+        let response = ensure_success(response).await?;
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_owned();
+        let data = response.bytes().await?.to_vec();
+
+        Ok(Some(Blob { content_type, data }))
+    }
+
+    #[tracing::instrument]
+    async fn delete(&self, key: &str) -> Result<(), BlobError> {
+        let response = self
+            .request(Method::DELETE, key, None, Vec::new())?
+            .send()
+            .await?;
+
+        if response.status() != StatusCode::NOT_FOUND {
+            ensure_success(response).await?;
+        }
+
+        Ok(())
+    }
+
+    fn url(&self, key: &str) -> String {
+        join_url(&self.base_url, key)
+    }
+}
+
+async fn ensure_success(response: reqwest::Response) -> Result<reqwest::Response, BlobError> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    Err(BlobError::UnexpectedResponse {
+        status: response.status().as_u16(),
+        body: response.text().await.unwrap_or_default(),
+    })
+}
+
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Percent encode a path as required by the canonical request, leaving the
+/// unreserved characters and the path separator as they are.
+fn uri_encode(path: &str) -> String {
+    path.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Deserialize;
+
+/// Settings for the versions of the API which are served side by side.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ApiSettings {
+    /// The deprecation of version 1, which is superseded by version 2.
+    pub v1: Deprecation,
+}
+
+/// When a version of the API was deprecated and when it is expected to stop being
+/// served. Both are announced to clients in the headers of its responses.
+#[derive(Debug, Deserialize, Clone)]
+pub struct Deprecation {
+    pub deprecated_at: DateTime<Utc>,
+    pub sunset_at: DateTime<Utc>,
+}
+
+impl Default for ApiSettings {
+    fn default() -> Self {
+        Self {
+            v1: Deprecation {
+                deprecated_at: Utc.with_ymd_and_hms(2026, 10, 19, 0, 0, 0).unwrap(),
+                sunset_at: Utc.with_ymd_and_hms(2027, 4, 19, 0, 0, 0).unwrap(),
+            },
+        }
+    }
+}
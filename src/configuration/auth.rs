@@ -5,6 +5,13 @@ use serde::Deserialize;
 #[derive(Debug, Deserialize, Clone)]
 pub struct AuthSettings {
     pub jwtsecret: Secret<String>,
+    /// Signs pagination cursors. It is kept apart from the JWT secret so that
+    /// neither can be used to forge what the other signs.
+    pub cursorsecret: Secret<String>,
+    /// Keys the digests under which idempotency keys and their requests are
+    /// stored, so that the stored digests reveal nothing about credentials or
+    /// bodies.
+    pub idempotencysecret: Secret<String>,
     pub jwt_expires_in: String,
     pub jwt_max_age: i32,
 }
@@ -13,6 +20,8 @@ impl Default for AuthSettings {
     fn default() -> Self {
         Self {
             jwtsecret: Secret::new("super_secret".into()), // This is never used
+            cursorsecret: Secret::new("super_secret".into()), // This is never used
+            idempotencysecret: Secret::new("super_secret".into()), // This is never used
             jwt_expires_in: "60m".into(),
             jwt_max_age: 60,
         }
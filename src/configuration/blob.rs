@@ -0,0 +1,69 @@
+use secrecy::Secret;
+use serde::Deserialize;
+use std::fmt::Display;
+
+static LOCAL: &str = "local";
+static S3: &str = "s3";
+
+/// Settings for the storage of binary files such as avatar images
+#[derive(Debug, Deserialize, Clone)]
+pub struct BlobSettings {
+    /// Which storage backend to use.
+    pub backend: BlobBackend,
+    /// The directory in which files are stored when using the local backend.
+    pub path: String,
+    /// The base url from which stored files are publicly available. It defaults
+    /// to the application's own `/blobs` route, which serves files from any backend.
+    pub url: String,
+    /// Connection details for the s3 backend. Required when `backend` is `s3`.
+    pub s3: Option<S3Settings>,
+}
+
+impl Default for BlobSettings {
+    fn default() -> Self {
+        Self {
+            backend: Default::default(),
+            path: "blobs".into(),
+            url: "/blobs".into(),
+            s3: None,
+        }
+    }
+}
+
+/// Connection details for an S3 compatible object store
+#[derive(Debug, Deserialize, Clone)]
+pub struct S3Settings {
+    /// The base url of the object store, for example `http://localhost:9000`.
+    /// Buckets are addressed path style, so this works for MinIO and similar.
+    pub endpoint: String,
+    pub bucket: String,
+    pub region: String,
+    pub accesskey: Secret<String>,
+    pub secretkey: Secret<String>,
+}
+
+/// The available storage backends
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum BlobBackend {
+    /// Store files on the local filesystem
+    #[default]
+    Local,
+    /// Store files in an S3 compatible object store
+    S3,
+}
+
+impl From<BlobBackend> for config::ValueKind {
+    fn from(value: BlobBackend) -> Self {
+        config::ValueKind::String(value.to_string())
+    }
+}
+
+impl Display for BlobBackend {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BlobBackend::Local => write!(f, "{LOCAL}"),
+            BlobBackend::S3 => write!(f, "{S3}"),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use serde::Deserialize;
+
+/// Settings for the in-process caches.
+#[derive(Debug, Deserialize, Clone)]
+pub struct CacheSettings {
+    /// How many users are kept in the cache of user lookups. Zero disables the
+    /// cache.
+    pub user_capacity: usize,
+    /// How long, in seconds, a cached user is served before it is read from the
+    /// database again.
+    pub user_ttl_seconds: u64,
+    /// Whether to evict users when the database announces changes to them.
+    /// Without this, each instance only evicts users it changed itself, so this
+    /// should be enabled whenever more than one instance is running.
+    pub listen: bool,
+}
+
+impl Default for CacheSettings {
+    fn default() -> Self {
+        Self {
+            user_capacity: 0,
+            user_ttl_seconds: 60,
+            listen: false,
+        }
+    }
+}
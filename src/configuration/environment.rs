@@ -9,11 +9,12 @@ static PROD: &str = "prod";
 /// Enum representing the possible environments in which
 /// we may be launching the application. The app
 /// will default to production if this is not configured.
-#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Environment {
     Dev,
     Test,
+    #[default]
     Prod,
 }
 
@@ -49,9 +50,3 @@ impl TryFrom<String> for Environment {
         }
     }
 }
-
-impl Default for Environment {
-    fn default() -> Self {
-        Self::Prod
-    }
-}
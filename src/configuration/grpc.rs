@@ -0,0 +1,19 @@
+use serde::Deserialize;
+
+/// Settings for the gRPC server, which runs next to the HTTP server on the same
+/// host. It is off unless `enabled` is set, so that deployments only expose the
+/// port when they ask for it.
+#[derive(Debug, Deserialize, Clone)]
+pub struct GrpcSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for GrpcSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: 50051,
+        }
+    }
+}
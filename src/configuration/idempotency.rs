@@ -0,0 +1,20 @@
+use serde::Deserialize;
+
+/// Settings for POST requests which carry an `Idempotency-Key` header
+#[derive(Debug, Deserialize, Clone)]
+pub struct IdempotencySettings {
+    /// How long, in seconds, the response to a key is replayed to its retries.
+    pub ttl_seconds: i64,
+    /// How long, in seconds, a request holds its key while it is handled. A retry
+    /// may take the key over afterwards, in case the request never finished.
+    pub lease_seconds: i64,
+}
+
+impl Default for IdempotencySettings {
+    fn default() -> Self {
+        Self {
+            ttl_seconds: 60 * 60 * 24,
+            lease_seconds: 60,
+        }
+    }
+}
@@ -6,26 +6,44 @@
 //!
 use self::application::ApplicationSettings;
 use crate::configuration::{
-    auth::AuthSettings, database::DatabaseSettings, environment::Environment,
-    error::ConfigurationError,
+    api::ApiSettings, auth::AuthSettings, blob::BlobSettings, cache::CacheSettings,
+    database::DatabaseSettings, environment::Environment, error::ConfigurationError,
+    grpc::GrpcSettings, idempotency::IdempotencySettings, organization::OrganizationSettings,
+    user::UserSettings, webhook::WebhookSettings,
 };
 use config::{Config, FileFormat};
 use dotenv::dotenv;
 use secrecy::ExposeSecret;
 use serde::Deserialize;
 
+pub mod api;
 pub mod application;
 pub mod auth;
+pub mod blob;
+pub mod cache;
 pub mod database;
 mod environment;
 mod error;
+pub mod grpc;
+pub mod idempotency;
+pub mod organization;
 pub mod scheme;
+pub mod user;
+pub mod webhook;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Settings {
     pub application: ApplicationSettings,
     pub database: DatabaseSettings,
     pub auth: AuthSettings,
+    pub user: UserSettings,
+    pub blob: BlobSettings,
+    pub organization: OrganizationSettings,
+    pub cache: CacheSettings,
+    pub api: ApiSettings,
+    pub grpc: GrpcSettings,
+    pub webhook: WebhookSettings,
+    pub idempotency: IdempotencySettings,
 }
 
 const APP_ENV_KEY: &str = "ENVIRONMENT";
@@ -97,7 +115,87 @@ pub fn init() -> Result<Settings, ConfigurationError> {
         .set_default(
             "auth.jwt_expires_in",
             AuthSettings::default().jwt_expires_in,
-        )? // Note: we don't allow a default for the secret for security reasons
+        )? // Note: we don't allow a default for the secrets for security reasons
+        .set_default(
+            "user.user_id_reservation_seconds",
+            UserSettings::default().user_id_reservation_seconds,
+        )?
+        .set_default(
+            "user.user_id_change_interval_seconds",
+            UserSettings::default().user_id_change_interval_seconds,
+        )?
+        .set_default(
+            "user.user_id_allowed_characters",
+            UserSettings::default().user_id_allowed_characters,
+        )?
+        .set_default(
+            "user.user_id_allowed_symbols",
+            UserSettings::default().user_id_allowed_symbols,
+        )?
+        .set_default(
+            "user.reserved_user_ids",
+            UserSettings::default().reserved_user_ids,
+        )?
+        .set_default(
+            "user.search_requests_per_minute",
+            UserSettings::default().search_requests_per_minute,
+        )?
+        .set_default(
+            "user.require_if_match",
+            UserSettings::default().require_if_match,
+        )?
+        .set_default("blob.backend", BlobSettings::default().backend)?
+        .set_default("blob.path", BlobSettings::default().path)?
+        .set_default("blob.url", BlobSettings::default().url)?
+        .set_default(
+            "organization.invitation_ttl_seconds",
+            OrganizationSettings::default().invitation_ttl_seconds,
+        )?
+        .set_default(
+            "cache.user_capacity",
+            CacheSettings::default().user_capacity as u64,
+        )?
+        .set_default(
+            "cache.user_ttl_seconds",
+            CacheSettings::default().user_ttl_seconds,
+        )?
+        .set_default("cache.listen", CacheSettings::default().listen)?
+        .set_default(
+            "api.v1.deprecated_at",
+            ApiSettings::default().v1.deprecated_at.to_rfc3339(),
+        )?
+        .set_default(
+            "api.v1.sunset_at",
+            ApiSettings::default().v1.sunset_at.to_rfc3339(),
+        )?
+        .set_default("grpc.enabled", GrpcSettings::default().enabled)?
+        .set_default("grpc.port", GrpcSettings::default().port)?
+        .set_default("webhook.dispatch", WebhookSettings::default().dispatch)?
+        .set_default(
+            "webhook.poll_interval_ms",
+            WebhookSettings::default().poll_interval_ms,
+        )?
+        .set_default("webhook.batch_size", WebhookSettings::default().batch_size)?
+        .set_default(
+            "webhook.max_attempts",
+            WebhookSettings::default().max_attempts,
+        )?
+        .set_default(
+            "webhook.retry_backoff_seconds",
+            WebhookSettings::default().retry_backoff_seconds,
+        )?
+        .set_default(
+            "webhook.timeout_seconds",
+            WebhookSettings::default().timeout_seconds,
+        )?
+        .set_default(
+            "idempotency.ttl_seconds",
+            IdempotencySettings::default().ttl_seconds,
+        )?
+        .set_default(
+            "idempotency.lease_seconds",
+            IdempotencySettings::default().lease_seconds,
+        )?
         .add_source(
             config::File::from(configuration_directory.join(BASE_CONFIG_FILENAME))
                 .required(false)
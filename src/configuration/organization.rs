@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+/// Settings which govern how organizations are managed
+#[derive(Debug, Deserialize, Clone)]
+pub struct OrganizationSettings {
+    /// How long, in seconds, an invitation to an organization can be accepted.
+    pub invitation_ttl_seconds: i64,
+}
+
+impl Default for OrganizationSettings {
+    fn default() -> Self {
+        Self {
+            invitation_ttl_seconds: 60 * 60 * 24 * 7,
+        }
+    }
+}
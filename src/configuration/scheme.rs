@@ -4,10 +4,11 @@ use std::fmt::Display;
 static HTTP: &str = "http";
 static HTTPS: &str = "https";
 
-#[derive(Debug, Deserialize, Clone, Copy)]
+#[derive(Debug, Deserialize, Clone, Copy, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum Scheme {
     Http,
+    #[default]
     Https,
 }
 
@@ -26,12 +27,6 @@ impl AsRef<str> for Scheme {
     }
 }
 
-impl Default for Scheme {
-    fn default() -> Self {
-        Self::Https
-    }
-}
-
 impl Display for Scheme {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -0,0 +1,82 @@
+use serde::Deserialize;
+use std::fmt::Display;
+
+static ASCII: &str = "ascii";
+static UNICODE: &str = "unicode";
+
+/// Settings which govern how users may manage their accounts
+#[derive(Debug, Deserialize, Clone)]
+pub struct UserSettings {
+    /// How long, in seconds, a user's previous `user_id` stays reserved after
+    /// they change it. During this period nobody else can claim it and lookups
+    /// by the old value are redirected to the new one.
+    pub user_id_reservation_seconds: i64,
+    /// The minimum number of seconds a user must wait between changes to their `user_id`.
+    pub user_id_change_interval_seconds: i64,
+    /// The class of letters and digits which may appear in a `user_id`.
+    pub user_id_allowed_characters: AllowedCharacters,
+    /// Symbols which may appear in a `user_id` in addition to letters and digits.
+    pub user_id_allowed_symbols: String,
+    /// Values which may not be used as a `user_id`, nor anything which looks like them.
+    /// A `user_id` has at least 8 characters, so only values of 8 or more characters
+    /// can be reserved, and shorter ones never match.
+    pub reserved_user_ids: Vec<String>,
+    /// How many searches a user may make per minute.
+    pub search_requests_per_minute: u32,
+    /// Whether updates must carry an `If-Match` header, or an expected version
+    /// over gRPC and GraphQL. Without one, concurrent updates of the same user silently
+    /// overwrite each other.
+    pub require_if_match: bool,
+}
+
+impl Default for UserSettings {
+    fn default() -> Self {
+        Self {
+            user_id_reservation_seconds: 60 * 60 * 24 * 30,
+            user_id_change_interval_seconds: 60 * 60 * 24 * 7,
+            user_id_allowed_characters: Default::default(),
+            user_id_allowed_symbols: "_.-".into(),
+            reserved_user_ids: ["administrator", "moderator", "health_check", "openapi.json"]
+                .into_iter()
+                .map(String::from)
+                .collect(),
+            search_requests_per_minute: 30,
+            require_if_match: false,
+        }
+    }
+}
+
+/// The class of letters and digits which may appear in a `user_id`.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AllowedCharacters {
+    /// Only ASCII letters and digits
+    #[default]
+    Ascii,
+    /// Any Unicode letter or digit
+    Unicode,
+}
+
+impl AllowedCharacters {
+    pub fn allows(&self, value: char) -> bool {
+        match self {
+            AllowedCharacters::Ascii => value.is_ascii_alphanumeric(),
+            AllowedCharacters::Unicode => value.is_alphanumeric(),
+        }
+    }
+}
+
+impl From<AllowedCharacters> for config::ValueKind {
+    fn from(value: AllowedCharacters) -> Self {
+        config::ValueKind::String(value.to_string())
+    }
+}
+
+impl Display for AllowedCharacters {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AllowedCharacters::Ascii => write!(f, "{ASCII}"),
+            AllowedCharacters::Unicode => write!(f, "{UNICODE}"),
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+
+/// Settings for delivering webhooks.
+#[derive(Debug, Deserialize, Clone)]
+pub struct WebhookSettings {
+    /// Whether this instance delivers the webhooks in the outbox. Instances share
+    /// the outbox, so any number of them may deliver.
+    pub dispatch: bool,
+    /// How long, in milliseconds, to wait before looking for due deliveries again
+    /// once there were none.
+    pub poll_interval_ms: u64,
+    /// How many deliveries are sent at a time.
+    pub batch_size: i64,
+    /// How often a delivery is attempted before it is dead.
+    pub max_attempts: i32,
+    /// How long, in seconds, to wait before the first retry. The wait doubles
+    /// with every further attempt.
+    pub retry_backoff_seconds: u64,
+    /// How long, in seconds, to wait for a receiver to answer.
+    pub timeout_seconds: u64,
+}
+
+impl Default for WebhookSettings {
+    fn default() -> Self {
+        Self {
+            dispatch: true,
+            poll_interval_ms: 1000,
+            batch_size: 20,
+            max_attempts: 8,
+            retry_backoff_seconds: 30,
+            timeout_seconds: 10,
+        }
+    }
+}
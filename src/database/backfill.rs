@@ -0,0 +1,165 @@
+//! Fills in the canonical form and the confusable skeleton of handles which were
+//! stored before the application computed them. The forms come from
+//! [handle](crate::domain::user::handle), which the database cannot reproduce, so
+//! this runs when the application starts rather than in a migration. Only rows
+//! without the forms are read, so once they are filled in this costs a lookup of
+//! the constraints and nothing more.
+//!
+//! Legacy handles may collide once they are case folded or reduced to their
+//! skeleton, which the unique indexes on the forms detect. Colliding users cannot
+//! be resolved without one of them choosing a new `user_id`, so they stop the
+//! application with a list of the collisions, and the user who signed up first
+//! keeps the handle. Colliding reservations are only temporary, so they are
+//! dropped. Once every row has its forms, the constraints which require them are
+//! validated.
+
+use super::error::DatabaseInitError;
+use crate::domain::user::handle;
+use sqlx::{Acquire, PgPool, Postgres, Transaction};
+
+/// The constraints which require the forms, by the table they are on.
+const CONSTRAINTS: [(&str, &str); 2] = [
+    ("user_", "user_handle_forms_present"),
+    (
+        "user_id_reservation",
+        "user_id_reservation_handle_forms_present",
+    ),
+];
+
+#[derive(sqlx::FromRow)]
+struct Handle {
+    user_id: String,
+}
+
+/// Backfill the handles of users and reservations.
+#[tracing::instrument(skip(db))]
+pub async fn backfill_handles(db: &PgPool) -> Result<(), DatabaseInitError> {
+    let mut tx = db.begin().await?;
+
+    let users = sqlx::query_as::<_, Handle>(
+        r#"
+        SELECT user_id FROM user_
+        WHERE canonical_user_id IS NULL OR user_id_skeleton IS NULL
+        ORDER BY created_at, id
+        FOR UPDATE
+    "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    let mut collisions = Vec::new();
+    for user in &users {
+        let updated = update(
+            &mut tx,
+            "UPDATE user_ SET canonical_user_id = $2, user_id_skeleton = $3 WHERE user_id = $1",
+            user,
+        )
+        .await?;
+        if let Err(constraint) = updated {
+            collisions.push(format!(
+                "'{}' collides with another user_id on {constraint}",
+                user.user_id
+            ));
+        }
+    }
+
+    if !collisions.is_empty() {
+        return Err(DatabaseInitError::HandleCollision(collisions.join("; ")));
+    }
+    if !users.is_empty() {
+        tracing::info!("Backfilled the handles of {} users", users.len());
+    }
+
+    let reservations = sqlx::query_as::<_, Handle>(
+        r#"
+        SELECT user_id FROM user_id_reservation
+        WHERE canonical_user_id IS NULL OR user_id_skeleton IS NULL
+        ORDER BY created_at
+        FOR UPDATE
+    "#,
+    )
+    .fetch_all(&mut *tx)
+    .await?;
+
+    for reservation in &reservations {
+        let taken: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM user_ WHERE canonical_user_id = $1)")
+                .bind(handle::canonicalize(&reservation.user_id))
+                .fetch_one(&mut *tx)
+                .await?;
+        let updated = match taken {
+            true => Err("user_canonical_user_id_idx".to_owned()),
+            false => {
+                update(
+                    &mut tx,
+                    r#"
+                    UPDATE user_id_reservation SET canonical_user_id = $2, user_id_skeleton = $3
+                    WHERE user_id = $1
+                "#,
+                    reservation,
+                )
+                .await?
+            }
+        };
+
+        if let Err(constraint) = updated {
+            tracing::warn!(
+                "Dropping the reservation of '{}', which collides on {constraint}",
+                reservation.user_id
+            );
+            sqlx::query("DELETE FROM user_id_reservation WHERE user_id = $1")
+                .bind(&reservation.user_id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+
+    for (table, constraint) in CONSTRAINTS {
+        let validated: bool =
+            sqlx::query_scalar("SELECT convalidated FROM pg_constraint WHERE conname = $1")
+                .bind(constraint)
+                .fetch_one(&mut *tx)
+                .await?;
+        if !validated {
+            tracing::info!("Validating {constraint}");
+            sqlx::query(&format!(
+                "ALTER TABLE {table} VALIDATE CONSTRAINT {constraint}"
+            ))
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Run `query` with the `user_id`, canonical form and skeleton of `row`. A unique
+/// violation only rolls back this update, and gives the name of the index which
+/// was violated.
+async fn update(
+    tx: &mut Transaction<'static, Postgres>,
+    query: &str,
+    row: &Handle,
+) -> Result<Result<(), String>, sqlx::Error> {
+    let mut savepoint = tx.begin().await?;
+    let updated = sqlx::query(query)
+        .bind(&row.user_id)
+        .bind(handle::canonicalize(&row.user_id))
+        .bind(handle::skeleton(&row.user_id))
+        .execute(&mut *savepoint)
+        .await;
+
+    match updated {
+        Ok(_) => {
+            savepoint.commit().await?;
+            Ok(Ok(()))
+        }
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            savepoint.rollback().await?;
+            Ok(Err(e.constraint().unwrap_or("a unique index").to_owned()))
+        }
+        Err(e) => Err(e),
+    }
+}
@@ -15,4 +15,6 @@ pub enum DatabaseInitError {
     MigrationError(#[from] MigrateError),
     #[error("Failed to connect to the database")]
     ConnectionFailure(sqlx::Error),
+    #[error("Existing user_ids collide and one of each must be renamed: {0}")]
+    HandleCollision(String),
 }
@@ -1,6 +1,8 @@
+use super::backfill::backfill_handles;
 use super::error::DatabaseInitError;
 use super::Database;
 use crate::configuration::database::DatabaseSettings;
+use crate::configuration::user::UserSettings;
 use crate::domain::user::actions::signup;
 use crate::domain::user::dto::Signup;
 use secrecy::Secret;
@@ -77,10 +79,12 @@ pub async fn init(settings: &DatabaseSettings) -> Result<Database, DatabaseInitE
         .run(&db)
         .await?;
     tracing::info!("Migrations success");
+    backfill_handles(&db).await?;
     let db: Database = Database::from(db);
 
     match signup(
         &db,
+        &UserSettings::default(),
         Signup {
             user_id: Some("TaroYamada".into()),
             password: Some(Secret::new("PaSSwd4TY".into())),
@@ -1,8 +1,11 @@
 //! A module for abstracting over the database connection.
 
+mod backfill;
 mod client;
 mod error;
 mod init;
+mod tenant;
 
 pub use client::Database;
 pub use init::init;
+pub use tenant::{ScopedTransaction, TenantScope};
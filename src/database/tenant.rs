@@ -0,0 +1,92 @@
+//! Transactions in which row level security is enforced for a tenant. Requests
+//! which act in an organization run all their queries in one, see
+//! [RequestTransaction](crate::middleware::tenant::RequestTransaction). The
+//! tables of the user domain belong to no tenant and carry no policies, so the
+//! user routes query the pool directly.
+
+use super::Database;
+use sqlx::{PgConnection, Postgres, Transaction};
+use std::ops::{Deref, DerefMut};
+use uuid::Uuid;
+
+/// The role which scoped transactions switch to. Row level security policies are
+/// enforced for it.
+const TENANT_ROLE: &str = "track_tenant";
+
+/// Who a request acts on behalf of: the user and, if one is active, the tenant
+/// they are acting in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TenantScope {
+    pub tenant_id: Option<Uuid>,
+    pub user_id: Uuid,
+}
+
+impl TenantScope {
+    /// The same user acting in another tenant.
+    pub fn with_tenant(&self, tenant_id: Uuid) -> Self {
+        Self {
+            tenant_id: Some(tenant_id),
+            user_id: self.user_id,
+        }
+    }
+}
+
+/// A transaction in which row level security is enforced for the scope it was
+/// started with. Changes are rolled back unless it is committed.
+#[derive(Debug)]
+pub struct ScopedTransaction(Transaction<'static, Postgres>);
+
+impl ScopedTransaction {
+    /// Act in the given tenant for the rest of the transaction, such as one which
+    /// is being created within it.
+    pub async fn enter(&mut self, tenant_id: Uuid) -> Result<(), sqlx::Error> {
+        sqlx::query("SELECT set_config('app.tenant_id', $1, true)")
+            .bind(tenant_id.to_string())
+            .execute(&mut *self.0)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn commit(self) -> Result<(), sqlx::Error> {
+        self.0.commit().await
+    }
+}
+
+impl Deref for ScopedTransaction {
+    type Target = PgConnection;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ScopedTransaction {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl Database {
+    /// Begin a transaction whose queries only see the rows of the given scope.
+    /// The settings are local to the transaction, so the connection is clean
+    /// again once it returns to the pool.
+    #[tracing::instrument]
+    pub async fn scoped(&self, scope: &TenantScope) -> Result<ScopedTransaction, sqlx::Error> {
+        let mut tx = self.begin().await?;
+
+        sqlx::query(
+            "SELECT set_config('app.tenant_id', $1, true), set_config('app.user_id', $2, true)",
+        )
+        .bind(scope.tenant_id.map(|id| id.to_string()).unwrap_or_default())
+        .bind(scope.user_id.to_string())
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query(&format!("SET LOCAL ROLE {TENANT_ROLE}"))
+            .execute(&mut *tx)
+            .await?;
+
+        Ok(ScopedTransaction(tx))
+    }
+}
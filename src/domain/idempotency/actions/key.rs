@@ -0,0 +1,153 @@
+use crate::{
+    database::Database,
+    domain::idempotency::{IdempotencyKey, StoredResponse},
+};
+use chrono::{Duration, NaiveDateTime, Utc};
+use sqlx::types::Json;
+use thiserror::Error;
+
+/// What a request should do with its key.
+#[derive(Debug)]
+pub enum Claim {
+    /// The key is new, or was abandoned by a request which held it, so the request
+    /// is handled and its response stored. The request holds the key until the
+    /// lease ends, which also tells its claim apart from a later one.
+    Acquired(Lease),
+    /// The key was used by an identical request, whose response is replayed.
+    Completed(StoredResponse),
+}
+
+/// The time until which a request holds the key it acquired.
+pub type Lease = NaiveDateTime;
+
+/// Action for claiming `key` for a request with `fingerprint`. Expired keys are
+/// removed first, so that an expired key can be used again. A key which is still
+/// in progress once its lease of `lease_seconds` ended is taken over, since the
+/// request which held it will not store a response anymore. The key is only
+/// acquired by one request, even when retries race.
+#[tracing::instrument]
+pub async fn claim(
+    db: &Database,
+    principal: &str,
+    key: &str,
+    fingerprint: &str,
+    ttl_seconds: i64,
+    lease_seconds: i64,
+) -> Result<Claim, IdempotencyError> {
+    let now = Utc::now().naive_utc();
+    sqlx::query("DELETE FROM idempotency_key WHERE expires_at <= $1")
+        .bind(now)
+        .execute(db.inner())
+        .await?;
+
+    let acquired = sqlx::query_scalar::<_, Lease>(
+        r#"
+        INSERT INTO idempotency_key
+            (principal, key, fingerprint, locked_until, created_at, expires_at)
+        VALUES ($1, $2, $3, $4, $5, $6)
+        ON CONFLICT (principal, key) DO UPDATE
+        SET locked_until = EXCLUDED.locked_until
+        WHERE idempotency_key.status IS NULL
+            AND idempotency_key.locked_until <= $5
+            AND idempotency_key.fingerprint = EXCLUDED.fingerprint
+        RETURNING locked_until;
+    "#,
+    )
+    .bind(principal)
+    .bind(key)
+    .bind(fingerprint)
+    .bind(now + Duration::seconds(lease_seconds))
+    .bind(now)
+    .bind(now + Duration::seconds(ttl_seconds))
+    .fetch_optional(db.inner())
+    .await?;
+
+    if let Some(lease) = acquired {
+        tracing::debug!("Acquired the key");
+        return Ok(Claim::Acquired(lease));
+    }
+
+    let existing = sqlx::query_as::<_, IdempotencyKey>(
+        "SELECT * FROM idempotency_key WHERE principal = $1 AND key = $2",
+    )
+    .bind(principal)
+    .bind(key)
+    .fetch_optional(db.inner())
+    .await?
+    // The key was released in the meantime, so the request has to be retried.
+    .ok_or_else(|| IdempotencyError::InProgress(key.to_owned()))?;
+
+    if existing.fingerprint != fingerprint {
+        return Err(IdempotencyError::KeyReused(key.to_owned()));
+    }
+
+    existing
+        .response()
+        .map(Claim::Completed)
+        .ok_or_else(|| IdempotencyError::InProgress(key.to_owned()))
+}
+
+/// Action for storing the response to the request which acquired `key` with
+/// `lease`. Nothing is stored when another request took the key over.
+#[tracing::instrument(skip(response))]
+pub async fn complete(
+    db: &Database,
+    principal: &str,
+    key: &str,
+    lease: &Lease,
+    response: &StoredResponse,
+) -> Result<(), IdempotencyError> {
+    sqlx::query(
+        r#"
+        UPDATE idempotency_key
+        SET status = $4, headers = $5, body = $6, locked_until = NULL
+        WHERE principal = $1 AND key = $2 AND locked_until = $3;
+    "#,
+    )
+    .bind(principal)
+    .bind(key)
+    .bind(lease)
+    .bind(response.status as i16)
+    .bind(Json(&response.headers))
+    .bind(&response.body)
+    .execute(db.inner())
+    .await?;
+
+    Ok(())
+}
+
+/// Action for giving up `key`, acquired with `lease`, without a response, so that
+/// the request can be retried, such as after a server error. A key which another
+/// request took over is left to it.
+#[tracing::instrument]
+pub async fn release(
+    db: &Database,
+    principal: &str,
+    key: &str,
+    lease: &Lease,
+) -> Result<(), IdempotencyError> {
+    sqlx::query(
+        "DELETE FROM idempotency_key WHERE principal = $1 AND key = $2 AND locked_until = $3",
+    )
+    .bind(principal)
+    .bind(key)
+    .bind(lease)
+    .execute(db.inner())
+    .await?;
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error("An error occurred with the database when using an idempotency key: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("The Idempotency-Key header must be 1 to {0} visible ASCII characters")]
+    InvalidKey(usize),
+    #[error("The request body is larger than the limit of {0} bytes for idempotent requests")]
+    PayloadTooLarge(usize),
+    #[error("The idempotency key '{0}' was already used for a different request")]
+    KeyReused(String),
+    #[error("A request with the idempotency key '{0}' is still being handled")]
+    InProgress(String),
+}
@@ -0,0 +1,8 @@
+mod key;
+
+pub use key::claim;
+pub use key::complete;
+pub use key::release;
+pub use key::Claim;
+pub use key::IdempotencyError;
+pub use key::Lease;
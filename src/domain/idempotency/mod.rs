@@ -0,0 +1,47 @@
+//! Keys which make POST requests safe to retry. The first request with a key
+//! [claims](actions::claim) it, and the response to it is stored once it has been
+//! handled so that it can be replayed to retries. The keys are used by the
+//! [idempotency middleware](crate::middleware::idempotency).
+
+use chrono::NaiveDateTime;
+use sqlx::types::Json;
+
+pub mod actions;
+
+/// A key as it is stored in the database. The response is missing while the
+/// first request with the key is still being handled.
+#[derive(Debug, sqlx::FromRow)]
+pub struct IdempotencyKey {
+    /// A digest of the credentials of the request, so that clients cannot see the
+    /// responses to each other's keys.
+    pub principal: String,
+    pub key: String,
+    /// A digest of the method, path, content type and body of the request.
+    pub fingerprint: String,
+    pub status: Option<i16>,
+    pub headers: Option<Json<Vec<(String, String)>>>,
+    pub body: Option<Vec<u8>>,
+    /// Until when the request which claimed the key holds it, while it is handled.
+    pub locked_until: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+    pub expires_at: NaiveDateTime,
+}
+
+/// The response to the first request with a key.
+#[derive(Debug, Clone)]
+pub struct StoredResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl IdempotencyKey {
+    /// The stored response, once the first request has been handled.
+    pub fn response(self) -> Option<StoredResponse> {
+        Some(StoredResponse {
+            status: self.status? as u16,
+            headers: self.headers.map(|headers| headers.0).unwrap_or_default(),
+            body: self.body.unwrap_or_default(),
+        })
+    }
+}
@@ -7,4 +7,7 @@
 //! The database model is typically for internal use. It should usually be
 //! converted to a DTO be returning as a response.
 
+pub mod idempotency;
+pub mod organization;
 pub mod user;
+pub mod webhook;
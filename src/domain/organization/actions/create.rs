@@ -0,0 +1,108 @@
+use crate::{
+    auth::JwtError,
+    database::{ScopedTransaction, TenantScope},
+    domain::organization::{
+        dto::{CreateOrganization, OrganizationResponse},
+        MemberRole, Organization,
+    },
+    i18n::Message,
+};
+use chrono::Utc;
+use thiserror::Error;
+use uuid::Uuid;
+
+const MAX_NAME_LENGTH: usize = 100;
+
+/// Action for creating an organization. The user who creates it becomes its owner.
+#[tracing::instrument]
+pub async fn create(
+    tx: &mut ScopedTransaction,
+    scope: &TenantScope,
+    dto: &CreateOrganization,
+) -> Result<OrganizationResponse, OrganizationError> {
+    let name = dto.name.trim();
+    if name.is_empty() || name.chars().count() > MAX_NAME_LENGTH {
+        return Err(OrganizationError::Validation {
+            field: "name".into(),
+            reason: Message::new("validation-length-range")
+                .arg("min", 1usize)
+                .arg("max", MAX_NAME_LENGTH),
+        });
+    }
+
+    let id = Uuid::new_v4();
+    let now = Utc::now().naive_utc();
+    tx.enter(id).await?;
+
+    tracing::debug!("Inserting organization");
+    let organization = sqlx::query_as::<_, Organization>(
+        r#"
+        INSERT INTO organization (id, name, created_at)
+            VALUES ($1, $2, $3)
+            RETURNING *;
+    "#,
+    )
+    .bind(id)
+    .bind(name)
+    .bind(now)
+    .fetch_one(&mut **tx)
+    .await?;
+
+    tracing::debug!("Adding the owner");
+    sqlx::query(
+        r#"
+        INSERT INTO membership (tenant_id, user_id, role, created_at)
+            VALUES ($1, $2, $3, $4);
+    "#,
+    )
+    .bind(organization.id)
+    .bind(scope.user_id)
+    .bind(MemberRole::Owner)
+    .bind(now)
+    .execute(&mut **tx)
+    .await?;
+    tracing::debug!("Success: {:?}", organization);
+
+    Ok(OrganizationResponse::new(organization, MemberRole::Owner))
+}
+
+/// Action for listing the organizations which the user is a member of.
+#[tracing::instrument]
+pub async fn list(
+    tx: &mut ScopedTransaction,
+    scope: &TenantScope,
+) -> Result<Vec<OrganizationResponse>, OrganizationError> {
+    let organizations = sqlx::query_as::<_, OrganizationResponse>(
+        r#"
+        SELECT organization.id, organization.name, membership.role FROM membership
+            INNER JOIN organization ON organization.id = membership.tenant_id
+            WHERE membership.user_id = $1
+            ORDER BY organization.name, organization.id;
+    "#,
+    )
+    .bind(scope.user_id)
+    .fetch_all(&mut **tx)
+    .await?;
+
+    Ok(organizations)
+}
+
+#[derive(Debug, Error)]
+pub enum OrganizationError {
+    #[error("An error occurred with the database when managing an organization: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("The user is not a member of the organization")]
+    NotMember,
+    #[error("No organization is active; switch to an organization first")]
+    NoActiveOrganization,
+    #[error("This requires the {0:?} role or higher")]
+    Forbidden(MemberRole),
+    #[error("A member with the id '{0}' was not found")]
+    MemberNotFound(String),
+    #[error("Value for field '{field}' is invalid: '{reason}'")]
+    Validation { field: String, reason: Message },
+    #[error("Failed to issue a token for the organization: {0}")]
+    JwtError(#[from] JwtError),
+    #[error("The transaction of the request was still in use when the request ended")]
+    TransactionInUse,
+}
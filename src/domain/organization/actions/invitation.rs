@@ -0,0 +1,178 @@
+use crate::{
+    configuration::organization::OrganizationSettings,
+    database::{Database, ScopedTransaction},
+    domain::organization::{
+        dto::{CreateInvitation, InvitationResponse, OrganizationResponse},
+        Invitation, MemberRole, Membership, Organization,
+    },
+    i18n::Message,
+};
+use argon2::password_hash::rand_core::{OsRng, RngCore};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{Duration, Utc};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use uuid::Uuid;
+
+const MAX_EMAIL_LENGTH: usize = 254;
+
+/// Action for inviting someone to the organization of the given membership.
+/// Admins may invite admins and members; nobody can be invited as an owner.
+///
+/// TODO: deliver email invitations once we have a mail integration. Until then
+/// the inviter is responsible for passing the returned link on.
+#[tracing::instrument]
+pub async fn create_invitation(
+    tx: &mut ScopedTransaction,
+    settings: &OrganizationSettings,
+    membership: &Membership,
+    dto: &CreateInvitation,
+) -> Result<InvitationResponse, InvitationError> {
+    if !membership.has_role(MemberRole::Admin) {
+        return Err(InvitationError::Forbidden);
+    }
+
+    if dto.role == MemberRole::Owner {
+        return Err(InvitationError::Validation {
+            field: "role".into(),
+            reason: Message::new("validation-role"),
+        });
+    }
+
+    let email = dto.email.as_deref().map(validate_email).transpose()?;
+
+    let token = generate_token();
+    let now = Utc::now().naive_utc();
+
+    tracing::debug!("Inserting invitation");
+    let invitation = sqlx::query_as::<_, Invitation>(
+        r#"
+        INSERT INTO invitation
+            (id, tenant_id, token_hash, email, role, invited_by, expires_at, created_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING *;
+    "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(membership.organization_id)
+    .bind(hash_token(&token))
+    .bind(email)
+    .bind(dto.role)
+    .bind(membership.user_id)
+    .bind(now + Duration::seconds(settings.invitation_ttl_seconds))
+    .bind(now)
+    .fetch_one(&mut **tx)
+    .await?;
+    tracing::debug!("Success: {:?}", invitation.id);
+
+    Ok(InvitationResponse::new(invitation, token))
+}
+
+/// Action for accepting an invitation. Users who are already members keep
+/// their current role.
+///
+/// The user is not a member of the organization yet, so this runs outside of a
+/// tenant scope; possession of the token is what authorizes it. Accounts have
+/// no email address, so the email of an invitation is never compared with the
+/// accepting user: it only records who the link was sent to and makes the
+/// invitation single use. Whoever presents the token first joins.
+#[tracing::instrument(skip(token))]
+pub async fn accept_invitation(
+    db: &Database,
+    user_id: &Uuid,
+    token: &str,
+) -> Result<OrganizationResponse, InvitationError> {
+    let now = Utc::now().naive_utc();
+    let mut tx = db.begin().await?;
+
+    let invitation = sqlx::query_as::<_, Invitation>(
+        "SELECT * FROM invitation WHERE token_hash = $1 FOR UPDATE",
+    )
+    .bind(hash_token(token))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(InvitationError::NotFound)?;
+
+    if invitation.expires_at <= now {
+        return Err(InvitationError::Expired);
+    }
+
+    if invitation.email.is_some() {
+        if invitation.accepted_at.is_some() {
+            return Err(InvitationError::AlreadyAccepted);
+        }
+
+        sqlx::query("UPDATE invitation SET accepted_at = $1 WHERE id = $2")
+            .bind(now)
+            .bind(invitation.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tracing::debug!("Adding member");
+    let role = sqlx::query_scalar::<_, MemberRole>(
+        r#"
+        INSERT INTO membership (tenant_id, user_id, role, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (tenant_id, user_id) DO UPDATE SET role = membership.role
+            RETURNING role;
+    "#,
+    )
+    .bind(invitation.organization_id)
+    .bind(user_id)
+    .bind(invitation.role)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    let organization =
+        sqlx::query_as::<_, Organization>("SELECT * FROM organization WHERE id = $1")
+            .bind(invitation.organization_id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+    tx.commit().await?;
+
+    Ok(OrganizationResponse::new(organization, role))
+}
+
+fn validate_email(email: &str) -> Result<String, InvitationError> {
+    let email = email.trim();
+    let valid = email.len() <= MAX_EMAIL_LENGTH
+        && matches!(email.split_once('@'), Some((local, domain)) if !local.is_empty() && domain.contains('.') && !domain.contains('@'));
+
+    if !valid {
+        return Err(InvitationError::Validation {
+            field: "email".into(),
+            reason: Message::new("validation-email"),
+        });
+    }
+
+    Ok(email.to_owned())
+}
+
+fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn hash_token(token: &str) -> String {
+    format!("{:x}", Sha256::digest(token.as_bytes()))
+}
+
+#[derive(Debug, Error)]
+pub enum InvitationError {
+    #[error("An error occurred with the database when managing an invitation: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("The invitation was not found")]
+    NotFound,
+    #[error("The invitation has expired")]
+    Expired,
+    #[error("The invitation has already been accepted")]
+    AlreadyAccepted,
+    #[error("Only admins can invite new members")]
+    Forbidden,
+    #[error("Value for field '{field}' is invalid: '{reason}'")]
+    Validation { field: String, reason: Message },
+}
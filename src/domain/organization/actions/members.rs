@@ -0,0 +1,112 @@
+use super::OrganizationError;
+use crate::{
+    database::{ScopedTransaction, TenantScope},
+    domain::{
+        organization::{
+            dto::{MemberListing, MemberResponse, MemberRow},
+            MemberRole, Membership,
+        },
+        user::handle,
+    },
+    pagination::{ListQuery, Page},
+};
+use sqlx::QueryBuilder;
+
+/// Look up the membership of the user of the scope in its tenant. Users can see
+/// their own memberships of every tenant, so the transaction may be scoped to
+/// another tenant than the one looked up.
+#[tracing::instrument]
+pub async fn get_membership(
+    tx: &mut ScopedTransaction,
+    scope: &TenantScope,
+) -> Result<Option<Membership>, sqlx::Error> {
+    let Some(tenant_id) = scope.tenant_id else {
+        return Ok(None);
+    };
+
+    let membership = sqlx::query_as::<_, Membership>(
+        "SELECT * FROM membership WHERE tenant_id = $1 AND user_id = $2",
+    )
+    .bind(tenant_id)
+    .bind(scope.user_id)
+    .fetch_optional(&mut **tx)
+    .await?;
+
+    Ok(membership)
+}
+
+/// Action for listing the members of the organization of the given membership.
+#[tracing::instrument]
+pub async fn list_members(
+    tx: &mut ScopedTransaction,
+    membership: &Membership,
+    list: &ListQuery<MemberListing>,
+) -> Result<Page<MemberResponse>, OrganizationError> {
+    let mut query = QueryBuilder::new(
+        r#"
+        SELECT user_.id, user_.canonical_user_id, user_.user_id,
+            membership.role, membership.created_at AS joined_at
+            FROM membership
+            INNER JOIN user_ ON user_.id = membership.user_id
+            WHERE membership.tenant_id = "#,
+    );
+    query.push_bind(membership.organization_id);
+    list.push_to(&mut query);
+
+    let rows = query
+        .build_query_as::<MemberRow>()
+        .fetch_all(&mut **tx)
+        .await?;
+
+    Ok(list.page(rows).map(|row| row.member))
+}
+
+/// Action for removing a member from the organization of the given membership.
+/// Members may always leave, except for the owner, and admins may remove members
+/// with a lower role than their own.
+#[tracing::instrument]
+pub async fn remove_member(
+    tx: &mut ScopedTransaction,
+    membership: &Membership,
+    user_id: &str,
+) -> Result<(), OrganizationError> {
+    let target = sqlx::query_as::<_, Membership>(
+        r#"
+        SELECT membership.* FROM membership
+            INNER JOIN user_ ON user_.id = membership.user_id
+            WHERE membership.tenant_id = $1 AND user_.canonical_user_id = $2;
+    "#,
+    )
+    .bind(membership.organization_id)
+    .bind(handle::canonicalize(user_id))
+    .fetch_optional(&mut **tx)
+    .await?
+    .ok_or(OrganizationError::MemberNotFound(user_id.to_owned()))?;
+
+    let leaving = target.user_id == membership.user_id;
+    if target.role == MemberRole::Owner {
+        return Err(OrganizationError::Forbidden(MemberRole::Owner));
+    }
+    if !leaving && (!membership.has_role(MemberRole::Admin) || membership.role <= target.role) {
+        return Err(OrganizationError::Forbidden(
+            MemberRole::Admin.max(next_role(target.role)),
+        ));
+    }
+
+    sqlx::query("DELETE FROM membership WHERE tenant_id = $1 AND user_id = $2")
+        .bind(target.organization_id)
+        .bind(target.user_id)
+        .execute(&mut **tx)
+        .await?;
+    tracing::debug!("Member removed");
+
+    Ok(())
+}
+
+/// The role directly above the given one.
+fn next_role(role: MemberRole) -> MemberRole {
+    match role {
+        MemberRole::Member => MemberRole::Admin,
+        MemberRole::Admin | MemberRole::Owner => MemberRole::Owner,
+    }
+}
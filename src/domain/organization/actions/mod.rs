@@ -0,0 +1,15 @@
+mod create;
+mod invitation;
+mod members;
+mod switch;
+
+pub use create::create;
+pub use create::list;
+pub use create::OrganizationError;
+pub use invitation::accept_invitation;
+pub use invitation::create_invitation;
+pub use invitation::InvitationError;
+pub use members::get_membership;
+pub use members::list_members;
+pub use members::remove_member;
+pub use switch::switch;
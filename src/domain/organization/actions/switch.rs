@@ -0,0 +1,27 @@
+use super::{get_membership, OrganizationError};
+use crate::{
+    auth::issue_jwt,
+    database::{ScopedTransaction, TenantScope},
+};
+use secrecy::Secret;
+use uuid::Uuid;
+
+/// Action for switching the organization which the user is acting in. It returns
+/// a new JWT carrying the organization, or no organization when switching back
+/// to the user's personal account.
+#[tracing::instrument]
+pub async fn switch(
+    tx: &mut ScopedTransaction,
+    scope: &TenantScope,
+    organization_id: Option<&Uuid>,
+    jwt_secret: &Secret<String>,
+) -> Result<String, OrganizationError> {
+    if let Some(organization_id) = organization_id {
+        get_membership(tx, &scope.with_tenant(*organization_id))
+            .await?
+            .ok_or(OrganizationError::NotMember)?;
+        tracing::debug!("User is a member");
+    }
+
+    Ok(issue_jwt(&scope.user_id, organization_id, jwt_secret)?)
+}
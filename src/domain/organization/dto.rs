@@ -0,0 +1,135 @@
+use super::{Invitation, MemberRole, Organization};
+use crate::pagination::{Field, FieldKind, Listing, Value};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// User submitted data for creating an organization
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateOrganization {
+    pub name: String,
+}
+
+/// Response format when an organization is requested, along with the role of
+/// the requesting user within it
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct OrganizationResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub role: MemberRole,
+}
+
+impl OrganizationResponse {
+    pub fn new(organization: Organization, role: MemberRole) -> Self {
+        Self {
+            id: organization.id,
+            name: organization.name,
+            role,
+        }
+    }
+}
+
+/// User submitted data for switching the organization they are acting in. A
+/// `null` organization switches back to the user's personal account.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct SwitchOrganization {
+    pub organization_id: Option<Uuid>,
+}
+
+/// Response format when the members of an organization are requested
+#[derive(Debug, Serialize, sqlx::FromRow, ToSchema)]
+pub struct MemberResponse {
+    pub user_id: String,
+    pub role: MemberRole,
+    pub joined_at: NaiveDateTime,
+}
+
+/// A member along with the columns they are sorted by.
+#[derive(Debug, sqlx::FromRow)]
+pub struct MemberRow {
+    pub id: Uuid,
+    pub canonical_user_id: String,
+    #[sqlx(flatten)]
+    pub member: MemberResponse,
+}
+
+/// The members of an organization may be sorted by when they joined or by their
+/// `user_id`, and filtered by role.
+#[derive(Debug)]
+pub struct MemberListing;
+
+impl Listing for MemberListing {
+    type Row = MemberRow;
+
+    const SORTS: &'static [Field] = &[
+        Field {
+            name: "joined_at",
+            column: "membership.created_at",
+            kind: FieldKind::Timestamp,
+        },
+        Field {
+            name: "user_id",
+            column: "user_.canonical_user_id",
+            kind: FieldKind::Text,
+        },
+    ];
+    const FILTERS: &'static [Field] = &[Field {
+        name: "role",
+        column: "membership.role",
+        kind: FieldKind::Text,
+    }];
+    const DEFAULT_SORT: &'static str = "joined_at";
+    const TIE_BREAKER: &'static str = "user_.id";
+
+    fn sort_value(row: &MemberRow, field: &Field) -> Value {
+        match field.name {
+            "user_id" => Value::Text(row.canonical_user_id.clone()),
+            _ => Value::Timestamp(row.member.joined_at),
+        }
+    }
+
+    fn tie_breaker(row: &MemberRow) -> Uuid {
+        row.id
+    }
+}
+
+/// User submitted data for inviting someone to an organization. Invitations
+/// without an email are link invitations which anyone with the link can accept.
+/// Invitations with an email can be accepted once, by whoever holds the token.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateInvitation {
+    pub email: Option<String>,
+    #[serde(default = "default_invitation_role")]
+    pub role: MemberRole,
+}
+
+fn default_invitation_role() -> MemberRole {
+    MemberRole::Member
+}
+
+/// Response format for a new invitation. The token is only ever shown here.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct InvitationResponse {
+    pub id: Uuid,
+    pub token: String,
+    /// Path which accepts the invitation when requested with `POST`
+    pub link: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    pub role: MemberRole,
+    pub expires_at: NaiveDateTime,
+}
+
+impl InvitationResponse {
+    pub fn new(invitation: Invitation, token: String) -> Self {
+        Self {
+            id: invitation.id,
+            link: format!("/invitations/{token}/accept"),
+            token,
+            email: invitation.email,
+            role: invitation.role,
+            expires_at: invitation.expires_at,
+        }
+    }
+}
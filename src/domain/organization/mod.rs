@@ -0,0 +1,71 @@
+use crate::database::TenantScope;
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod actions;
+pub mod dto;
+
+/// Represents an organization as stored in the database.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct Organization {
+    pub id: Uuid,
+    pub name: String,
+    pub created_at: NaiveDateTime,
+}
+
+/// The role of a member within an organization. The variants are ordered from
+/// the least to the most privileged.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum MemberRole {
+    Member,
+    /// Can invite and remove members.
+    Admin,
+    /// Created the organization. There is always exactly one owner.
+    Owner,
+}
+
+/// Represents a user's membership of an organization as stored in the database.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct Membership {
+    #[sqlx(rename = "tenant_id")]
+    pub organization_id: Uuid,
+    pub user_id: Uuid,
+    pub role: MemberRole,
+    pub created_at: NaiveDateTime,
+}
+
+impl Membership {
+    /// Whether the member has at least the given role.
+    pub fn has_role(&self, role: MemberRole) -> bool {
+        self.role >= role
+    }
+
+    /// The scope in which the member acts within the organization.
+    pub fn scope(&self) -> TenantScope {
+        TenantScope {
+            tenant_id: Some(self.organization_id),
+            user_id: self.user_id,
+        }
+    }
+}
+
+/// Represents an invitation to an organization as stored in the database.
+#[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
+pub struct Invitation {
+    pub id: Uuid,
+    #[sqlx(rename = "tenant_id")]
+    pub organization_id: Uuid,
+    pub token_hash: String,
+    pub email: Option<String>,
+    pub role: MemberRole,
+    pub invited_by: Option<Uuid>,
+    pub expires_at: NaiveDateTime,
+    pub accepted_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
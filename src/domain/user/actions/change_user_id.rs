@@ -0,0 +1,181 @@
+use crate::{
+    configuration::user::UserSettings,
+    database::Database,
+    domain::user::{
+        actions::SignupError,
+        cache::UserCache,
+        dto::{ChangeUserId, GetUserResponse},
+        events::{self, AccountEvent},
+        handle, BasicId, User,
+    },
+    i18n::Message,
+};
+use chrono::{Duration, Utc};
+use thiserror::Error;
+
+use super::signup::UserId;
+
+/// Action for changing the `user_id` of a user. The previous `user_id` is reserved
+/// for the owner for the duration configured in [UserSettings] so that nobody can
+/// claim it in the meantime.
+#[tracing::instrument]
+pub async fn change_user_id(
+    db: &Database,
+    cache: &UserCache,
+    settings: &UserSettings,
+    current_user_id: &BasicId,
+    change: &ChangeUserId,
+) -> Result<GetUserResponse, ChangeUserIdError> {
+    tracing::debug!("Validating new user_id...");
+    let new_user_id: UserId = change.user_id.to_owned().try_into()?;
+    new_user_id.check_policy(settings)?;
+    tracing::debug!("New user_id is valid");
+
+    if new_user_id.as_ref() == current_user_id.as_str() {
+        Err(ChangeUserIdError::Validation {
+            field: "user_id".into(),
+            reason: Message::new("validation-same-user-id"),
+        })?;
+    }
+
+    let now = Utc::now().naive_utc();
+    let mut tx = db.begin().await?;
+
+    tracing::debug!("Requesting user from db");
+    let user =
+        sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = $1 FOR UPDATE")
+            .bind(handle::canonicalize(current_user_id.as_str()))
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(ChangeUserIdError::NotFound(current_user_id.into()))?;
+    tracing::debug!("User found");
+
+    if let Some(changed_at) = user.user_id_changed_at {
+        let available_at = changed_at + Duration::seconds(settings.user_id_change_interval_seconds);
+
+        if available_at > now {
+            Err(ChangeUserIdError::TooSoon {
+                retry_after: (available_at - now).num_seconds(),
+            })?;
+        }
+    }
+
+    tracing::debug!("Checking if new user_id is available...");
+    let taken = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (SELECT 1 FROM user_ WHERE user_id_skeleton = $1 AND id <> $2)
+            OR EXISTS (
+                SELECT 1 FROM user_id_reservation
+                WHERE user_id_skeleton = $1 AND owner_id <> $2 AND reserved_until > $3
+            );
+    "#,
+    )
+    .bind(new_user_id.skeleton())
+    .bind(user.id)
+    .bind(now)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    if taken {
+        Err(ChangeUserIdError::AlreadyExists(
+            new_user_id.as_ref().to_owned(),
+        ))?;
+    }
+    tracing::debug!("New user_id is available");
+
+    tracing::debug!("Releasing any stale reservation for the new user_id");
+    sqlx::query("DELETE FROM user_id_reservation WHERE user_id_skeleton = $1")
+        .bind(new_user_id.skeleton())
+        .execute(&mut *tx)
+        .await?;
+
+    if !handle::matches(&user.user_id, new_user_id.as_ref()) {
+        tracing::debug!("Reserving previous user_id");
+        sqlx::query(
+            r#"
+            INSERT INTO user_id_reservation (
+                user_id, owner_id, reserved_until, created_at, canonical_user_id, user_id_skeleton
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (canonical_user_id) DO UPDATE
+                SET user_id = EXCLUDED.user_id,
+                    owner_id = EXCLUDED.owner_id,
+                    reserved_until = EXCLUDED.reserved_until,
+                    created_at = EXCLUDED.created_at,
+                    user_id_skeleton = EXCLUDED.user_id_skeleton;
+        "#,
+        )
+        .bind(&user.user_id)
+        .bind(user.id)
+        .bind(now + Duration::seconds(settings.user_id_reservation_seconds))
+        .bind(now)
+        .bind(handle::canonicalize(&user.user_id))
+        .bind(handle::skeleton(&user.user_id))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tracing::debug!("Updating user_id");
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        UPDATE user_
+            SET
+                user_id = $1,
+                user_id_changed_at = $2,
+                canonical_user_id = $3,
+                user_id_skeleton = $4
+            WHERE id = $5
+            RETURNING *;
+    "#,
+    )
+    .bind(new_user_id.as_ref())
+    .bind(now)
+    .bind(new_user_id.canonical())
+    .bind(new_user_id.skeleton())
+    .bind(user.id)
+    .fetch_one(&mut *tx)
+    .await?;
+    let event = AccountEvent::ProfileUpdated {
+        version: user.version,
+    };
+    events::publish(&mut *tx, user.id, event).await?;
+
+    tx.commit().await?;
+    cache.invalidate(&user.id);
+    tracing::debug!("Success: {:?}", user);
+
+    Ok(user.into())
+}
+
+#[derive(Debug, Error)]
+pub enum ChangeUserIdError {
+    #[error("An error occurred with the database when changing a user_id: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(String),
+    #[error(
+        "A user with the id '{requester}' does not have permission to update user '{requested}'"
+    )]
+    Forbidden {
+        requester: String,
+        requested: String,
+    },
+    #[error("Value for field '{field}' is invalid: '{reason}'")]
+    Validation { field: String, reason: Message },
+    #[error("The user_id '{0}' is already in use or reserved")]
+    AlreadyExists(String),
+    #[error("The user_id was changed too recently; retry after {retry_after} seconds")]
+    TooSoon { retry_after: i64 },
+}
+
+impl From<SignupError> for ChangeUserIdError {
+    fn from(value: SignupError) -> Self {
+        match value {
+            SignupError::Validation { field, reason } => Self::Validation { field, reason },
+            _ => Self::Validation {
+                field: "user_id".into(),
+                reason: value.to_string().into(),
+            },
+        }
+    }
+}
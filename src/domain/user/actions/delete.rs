@@ -2,29 +2,41 @@ use std::fmt::Display;
 
 use crate::{
     database::Database,
-    domain::user::{BasicId, User},
+    domain::{
+        user::{cache::UserCache, dto::GetUserResponse, handle, BasicId, User},
+        webhook::{self, WebhookEvent},
+    },
 };
 use thiserror::Error;
 use uuid::Uuid;
 
 /// Action for deleting the user.
 #[tracing::instrument]
-pub async fn delete(db: &Database, user_id: &BasicId) -> Result<User, DeleteError> {
+pub async fn delete(
+    db: &Database,
+    cache: &UserCache,
+    user_id: &BasicId,
+) -> Result<User, DeleteError> {
     tracing::debug!("Requesting user from db");
+    let mut tx = db.begin().await?;
     let user = sqlx::query_as::<_, User>(
         r#"
-        DELETE FROM user_ WHERE user_id = $1
+        DELETE FROM user_ WHERE canonical_user_id = $1
         RETURNING *;
     "#,
     )
-    .bind(user_id.as_str())
-    .fetch_optional(db.inner())
+    .bind(handle::canonicalize(user_id.as_str()))
+    .fetch_optional(&mut *tx)
     .await?
     .ok_or(DeleteError::NotFound(UserIdType::Str(String::from(
         user_id,
     ))))?;
 
     tracing::debug!("User found");
+    let deleted = GetUserResponse::from(user.clone());
+    webhook::actions::enqueue(&mut *tx, WebhookEvent::UserDeleted, &deleted).await?;
+    tx.commit().await?;
+    cache.invalidate(&user.id);
 
     Ok(user)
 }
@@ -0,0 +1,74 @@
+use crate::{
+    database::Database,
+    domain::user::{
+        actions::get_one::find_visible, dto::FollowResponse, follow::FollowStatus, privacy::Viewer,
+    },
+};
+use chrono::Utc;
+use thiserror::Error;
+
+/// Action for following another user. If the user requires approval of their
+/// followers the follow stays pending until they approve it. Following a user
+/// who is already followed leaves the existing follow as it is.
+#[tracing::instrument]
+pub async fn follow(
+    db: &Database,
+    viewer: &Viewer,
+    user_id: &str,
+) -> Result<FollowResponse, FollowError> {
+    let Viewer::User {
+        id: follower_id, ..
+    } = viewer
+    else {
+        return Err(FollowError::Unauthenticated);
+    };
+
+    tracing::debug!("Requesting user from db");
+    let followee = find_visible(db, user_id, viewer)
+        .await?
+        .ok_or(FollowError::NotFound(user_id.to_owned()))?;
+    tracing::debug!("User found");
+
+    if followee.id == *follower_id {
+        return Err(FollowError::SelfFollow);
+    }
+
+    let status = if followee.approve_followers {
+        FollowStatus::Pending
+    } else {
+        FollowStatus::Accepted
+    };
+
+    let status = sqlx::query_scalar::<_, FollowStatus>(
+        r#"
+        INSERT INTO follow (follower_id, followee_id, status, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (follower_id, followee_id) DO UPDATE SET status = follow.status
+            RETURNING status;
+    "#,
+    )
+    .bind(follower_id)
+    .bind(followee.id)
+    .bind(status)
+    .bind(Utc::now().naive_utc())
+    .fetch_one(db.inner())
+    .await?;
+    tracing::debug!("Follow is {status:?}");
+
+    Ok(FollowResponse {
+        user_id: followee.user_id,
+        status,
+    })
+}
+
+#[derive(Debug, Error)]
+pub enum FollowError {
+    #[error("An error occurred with the database when following a user: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(String),
+    #[error("Users cannot follow themselves")]
+    SelfFollow,
+    #[error("Only signed in users can follow other users")]
+    Unauthenticated,
+}
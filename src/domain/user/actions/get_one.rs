@@ -2,50 +2,173 @@ use std::fmt::Display;
 
 use crate::{
     database::Database,
-    domain::user::{dto::GetUserResponse, User},
+    domain::user::{
+        cache::UserCache, dto::GetUserResponse, follow::FollowStatus, handle, privacy::Viewer, User,
+    },
 };
+use chrono::Utc;
+use std::collections::HashMap;
 use thiserror::Error;
 use uuid::Uuid;
 
 /// Action for retrieving a single user by it's ID.
 #[tracing::instrument]
-pub async fn get_one(db: &Database, user_id: &Uuid) -> Result<User, GetOneError> {
-    tracing::debug!("Requesting user from db");
-    let user = sqlx::query_as::<_, User>(
-        r#"
-        SELECT * FROM user_ WHERE id = $1
-    "#,
-    )
-    .bind(user_id)
-    .fetch_optional(db.inner())
-    .await?
-    .ok_or(GetOneError::NotFound(UserIdType::Uuid(*user_id)))?;
+pub async fn get_one(
+    db: &Database,
+    cache: &UserCache,
+    user_id: &Uuid,
+) -> Result<User, GetOneError> {
+    tracing::debug!("Requesting user");
+    let user = cache
+        .find_by_id(db, user_id)
+        .await?
+        .ok_or(GetOneError::NotFound(UserIdType::Uuid(*user_id)))?;
 
     tracing::debug!("User found");
 
     Ok(user)
 }
 
-/// Action for retrieving a single user by it's ID.
+/// Action for retrieving a single user by it's ID, as seen by the viewer. Users
+/// who the viewer cannot find, such as hidden accounts or users who have blocked
+/// the viewer, are reported as not found.
 #[tracing::instrument]
 pub async fn get_one_by_str_id(
     db: &Database,
+    cache: &UserCache,
     user_id: &str,
+    viewer: &Viewer,
 ) -> Result<GetUserResponse, GetOneError> {
-    tracing::debug!("Requesting user from db");
-    let user = sqlx::query_as::<_, User>(
+    tracing::debug!("Requesting user");
+    let user = cache.find(db, user_id).await?;
+
+    let user = match user {
+        Some(user) => user,
+        None => {
+            tracing::debug!("User not found, checking for a reserved user_id");
+            let current_user = sqlx::query_as::<_, User>(
+                r#"
+                SELECT user_.* FROM user_id_reservation
+                    INNER JOIN user_ ON user_.id = user_id_reservation.owner_id
+                    WHERE user_id_reservation.canonical_user_id = $1
+                        AND user_id_reservation.reserved_until > $2
+            "#,
+            )
+            .bind(handle::canonicalize(user_id))
+            .bind(Utc::now().naive_utc())
+            .fetch_optional(db.inner())
+            .await?;
+
+            return Err(match current_user {
+                Some(current_user) if viewer.can_find(db, &current_user).await? => {
+                    GetOneError::Moved(current_user.user_id)
+                }
+                _ => GetOneError::NotFound(UserIdType::Str(user_id.to_owned())),
+            });
+        }
+    };
+
+    if !viewer.can_find(db, &user).await? {
+        tracing::debug!("User is hidden from the viewer");
+        return Err(GetOneError::NotFound(UserIdType::Str(user_id.to_owned())));
+    }
+
+    tracing::debug!("User found");
+
+    let counts = follow_counts(db, &[user.id])
+        .await?
+        .remove(&user.id)
+        .unwrap_or_default();
+    let mut user = GetUserResponse::for_viewer(user, viewer);
+    (user.followers_count, user.following_count) = (Some(counts.0), Some(counts.1));
+
+    Ok(user)
+}
+
+/// Action for retrieving several users by their IDs at once, as seen by the
+/// viewer. Users the viewer cannot find are left out, as are `user_id`s which
+/// are only reserved, so the result is keyed by those `user_id`s which were found.
+#[tracing::instrument]
+pub async fn get_many_by_str_ids(
+    db: &Database,
+    user_ids: &[String],
+    viewer: &Viewer,
+) -> Result<HashMap<String, GetUserResponse>, GetOneError> {
+    tracing::debug!("Requesting {} users", user_ids.len());
+    let canonical: Vec<String> = user_ids.iter().map(|id| handle::canonicalize(id)).collect();
+    let users = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = ANY($1)")
+        .bind(&canonical)
+        .fetch_all(db.inner())
+        .await?;
+
+    let users = viewer.findable(db, users, |user| user).await?;
+    tracing::debug!("Found {} users", users.len());
+
+    let ids: Vec<Uuid> = users.iter().map(|user| user.id).collect();
+    let counts = follow_counts(db, &ids).await?;
+
+    let found: HashMap<String, GetUserResponse> = users
+        .into_iter()
+        .map(|user| {
+            let canonical = handle::canonicalize(&user.user_id);
+            let counts = counts.get(&user.id).copied().unwrap_or_default();
+            let mut user = GetUserResponse::for_viewer(user, viewer);
+            (user.followers_count, user.following_count) = (Some(counts.0), Some(counts.1));
+            (canonical, user)
+        })
+        .collect();
+
+    Ok(user_ids
+        .iter()
+        .filter_map(|user_id| {
+            let user = found.get(&handle::canonicalize(user_id))?;
+            Some((user_id.clone(), user.clone()))
+        })
+        .collect())
+}
+
+/// Look up a user by their `user_id`, treating users which are hidden from the
+/// viewer as if they did not exist.
+pub(super) async fn find_visible(
+    db: &Database,
+    user_id: &str,
+    viewer: &Viewer,
+) -> Result<Option<User>, sqlx::Error> {
+    let user = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = $1")
+        .bind(handle::canonicalize(user_id))
+        .fetch_optional(db.inner())
+        .await?;
+
+    match user {
+        Some(user) if viewer.can_find(db, &user).await? => Ok(Some(user)),
+        _ => Ok(None),
+    }
+}
+
+/// The number of accepted followers of each user and of users they follow,
+/// keyed by the id of the user.
+async fn follow_counts(
+    db: &Database,
+    ids: &[Uuid],
+) -> Result<HashMap<Uuid, (i64, i64)>, sqlx::Error> {
+    let counts = sqlx::query_as::<_, (Uuid, i64, i64)>(
         r#"
-        SELECT * FROM user_ WHERE user_id = $1
+        SELECT
+            user_.id,
+            (SELECT count(*) FROM follow WHERE followee_id = user_.id AND status = $2),
+            (SELECT count(*) FROM follow WHERE follower_id = user_.id AND status = $2)
+        FROM user_ WHERE user_.id = ANY($1)
     "#,
     )
-    .bind(user_id)
-    .fetch_optional(db.inner())
-    .await?
-    .ok_or(GetOneError::NotFound(UserIdType::Str(user_id.to_owned())))?;
-
-    tracing::debug!("User found");
+    .bind(ids)
+    .bind(FollowStatus::Accepted)
+    .fetch_all(db.inner())
+    .await?;
 
-    Ok(user.into())
+    Ok(counts
+        .into_iter()
+        .map(|(id, followers, following)| (id, (followers, following)))
+        .collect())
 }
 
 #[derive(Debug, Error)]
@@ -54,6 +177,8 @@ pub enum GetOneError {
     DatabaseError(#[from] sqlx::Error),
     #[error("A user with the id '{0}' was not found")]
     NotFound(UserIdType),
+    #[error("The user has changed their id to '{0}'")]
+    Moved(String),
 }
 
 #[derive(Debug)]
@@ -0,0 +1,169 @@
+use crate::{
+    database::Database,
+    domain::user::{
+        actions::get_one::find_visible,
+        dto::GetUserResponse,
+        follow::{FollowList, FollowListing, FollowRow, FollowStatus},
+        handle,
+        privacy::{Viewer, Visibility},
+        User,
+    },
+    pagination::{ListQuery, Page},
+};
+use sqlx::{Postgres, QueryBuilder};
+use std::collections::HashMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Action for listing one side of a user's follow graph, a page at a time, from
+/// the most recent follow to the oldest. Pending requests can only be listed by
+/// the user who received them, and leave out the users they muted.
+#[tracing::instrument]
+pub async fn list_follows(
+    db: &Database,
+    viewer: &Viewer,
+    user_id: &str,
+    list: FollowList,
+    query: &ListQuery<FollowListing>,
+) -> Result<Page<GetUserResponse>, ListFollowsError> {
+    tracing::debug!("Requesting user from db");
+    let user = find_visible(db, user_id, viewer)
+        .await?
+        .ok_or(ListFollowsError::NotFound(user_id.to_owned()))?;
+    tracing::debug!("User found");
+
+    if list == FollowList::Requests && viewer.clearance(&user) != Visibility::OnlyMe {
+        return Err(ListFollowsError::Forbidden);
+    }
+
+    let mut muted = Vec::new();
+    if list == FollowList::Requests && viewer.id() == Some(user.id) {
+        if let Some(restrictions) = viewer.restrictions(db).await? {
+            muted = restrictions.muted.iter().copied().collect();
+        }
+    }
+
+    let mut pages = pages(db, viewer, &[user.id], list, muted, query).await?;
+    Ok(pages
+        .remove(&user.id)
+        .unwrap_or_else(|| Page::new(vec![], None)))
+}
+
+/// Action for listing the same page of one side of the follow graph for several
+/// users, with a single query for all of them. Users the viewer cannot find are
+/// left out. Pending requests are only listed one user at a time.
+#[tracing::instrument]
+pub async fn list_many_follows(
+    db: &Database,
+    viewer: &Viewer,
+    user_ids: &[String],
+    list: FollowList,
+    query: &ListQuery<FollowListing>,
+) -> Result<HashMap<String, Page<GetUserResponse>>, ListFollowsError> {
+    if list == FollowList::Requests {
+        return Err(ListFollowsError::Forbidden);
+    }
+
+    tracing::debug!("Requesting {} users from db", user_ids.len());
+    let canonical: Vec<String> = user_ids.iter().map(|id| handle::canonicalize(id)).collect();
+    let users = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = ANY($1)")
+        .bind(&canonical)
+        .fetch_all(db.inner())
+        .await?;
+    let users: HashMap<String, Uuid> = viewer
+        .findable(db, users, |user| user)
+        .await?
+        .into_iter()
+        .map(|user| (handle::canonicalize(&user.user_id), user.id))
+        .collect();
+    tracing::debug!("Found {} users", users.len());
+
+    let owners: Vec<Uuid> = users.values().copied().collect();
+    let mut pages = pages(db, viewer, &owners, list, vec![], query).await?;
+
+    Ok(user_ids
+        .iter()
+        .filter_map(|user_id| {
+            let owner = users.get(&handle::canonicalize(user_id))?;
+            Some((user_id.clone(), pages.remove(owner)?))
+        })
+        .collect())
+}
+
+/// A row of [pages], along with the user whose list it belongs to.
+#[derive(sqlx::FromRow)]
+struct OwnedFollowRow {
+    owner_id: Uuid,
+    #[sqlx(flatten)]
+    follow: FollowRow,
+}
+
+/// The page of `list` of each of the `owners`, leaving out the users in
+/// `excluded`. Every owner gets a page, even when it is empty.
+async fn pages(
+    db: &Database,
+    viewer: &Viewer,
+    owners: &[Uuid],
+    list: FollowList,
+    excluded: Vec<Uuid>,
+    query: &ListQuery<FollowListing>,
+) -> Result<HashMap<Uuid, Page<GetUserResponse>>, ListFollowsError> {
+    let (joined, filtered, status) = match list {
+        FollowList::Followers => ("follower_id", "followee_id", FollowStatus::Accepted),
+        FollowList::Following => ("followee_id", "follower_id", FollowStatus::Accepted),
+        FollowList::Requests => ("follower_id", "followee_id", FollowStatus::Pending),
+    };
+
+    // Each page is limited on its own, and numbered in its order so that the rows
+    // of a page come back in the same order as they would for that owner alone.
+    let mut builder = QueryBuilder::<Postgres>::new("SELECT page.* FROM unnest(");
+    builder.push_bind(owners.to_vec()).push(
+        "::uuid[]) AS owner(id) CROSS JOIN LATERAL (SELECT user_.*, \
+             follow.created_at AS followed_at, owner.id AS owner_id, row_number() OVER (ORDER BY ",
+    );
+    query.push_order(&mut builder);
+    builder
+        .push(") AS ordinal FROM follow INNER JOIN user_ ON user_.id = follow.")
+        .push(joined)
+        .push(" WHERE follow.")
+        .push(filtered)
+        .push(" = owner.id AND follow.status = ")
+        .push_bind(status)
+        .push(" AND user_.id <> ALL(")
+        .push_bind(excluded)
+        .push(")");
+    query.push_to(&mut builder);
+    builder.push(") AS page ORDER BY page.owner_id, page.ordinal");
+
+    let rows = builder
+        .build_query_as::<OwnedFollowRow>()
+        .fetch_all(db.inner())
+        .await?;
+    tracing::debug!("Found {} follows", rows.len());
+
+    let mut grouped: HashMap<Uuid, Vec<FollowRow>> =
+        owners.iter().map(|owner| (*owner, vec![])).collect();
+    for row in rows {
+        grouped.entry(row.owner_id).or_default().push(row.follow);
+    }
+
+    let mut pages = HashMap::with_capacity(grouped.len());
+    for (owner, rows) in grouped {
+        let mut page = query.page(rows);
+        page.items = viewer.findable(db, page.items, |row| &row.user).await?;
+        let page = page.map(|row| GetUserResponse::for_viewer(row.user, viewer));
+        pages.insert(owner, page);
+    }
+
+    Ok(pages)
+}
+
+#[derive(Debug, Error)]
+pub enum ListFollowsError {
+    #[error("An error occurred with the database when listing follows: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(String),
+    #[error("Follow requests can only be listed by the user who received them")]
+    Forbidden,
+}
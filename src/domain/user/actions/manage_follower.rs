@@ -0,0 +1,81 @@
+use crate::{
+    database::Database,
+    domain::user::{follow::FollowStatus, handle, privacy::Viewer},
+};
+use thiserror::Error;
+
+/// Action for approving a pending follow of the viewer by the given user.
+#[tracing::instrument]
+pub async fn approve_follower(
+    db: &Database,
+    viewer: &Viewer,
+    follower_user_id: &str,
+) -> Result<(), ManageFollowerError> {
+    let Viewer::User { id, .. } = viewer else {
+        return Err(ManageFollowerError::Unauthenticated);
+    };
+
+    let result = sqlx::query(
+        r#"
+        UPDATE follow SET status = $1
+            FROM user_
+            WHERE follow.follower_id = user_.id
+                AND user_.canonical_user_id = $2
+                AND follow.followee_id = $3
+                AND follow.status = $4;
+    "#,
+    )
+    .bind(FollowStatus::Accepted)
+    .bind(handle::canonicalize(follower_user_id))
+    .bind(id)
+    .bind(FollowStatus::Pending)
+    .execute(db.inner())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ManageFollowerError::NotFound(follower_user_id.to_owned()));
+    }
+
+    Ok(())
+}
+
+/// Action for removing a follower of the viewer, or rejecting their pending follow.
+#[tracing::instrument]
+pub async fn remove_follower(
+    db: &Database,
+    viewer: &Viewer,
+    follower_user_id: &str,
+) -> Result<(), ManageFollowerError> {
+    let Viewer::User { id, .. } = viewer else {
+        return Err(ManageFollowerError::Unauthenticated);
+    };
+
+    let result = sqlx::query(
+        r#"
+        DELETE FROM follow USING user_
+            WHERE follow.follower_id = user_.id
+                AND user_.canonical_user_id = $1
+                AND follow.followee_id = $2;
+    "#,
+    )
+    .bind(handle::canonicalize(follower_user_id))
+    .bind(id)
+    .execute(db.inner())
+    .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ManageFollowerError::NotFound(follower_user_id.to_owned()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum ManageFollowerError {
+    #[error("An error occurred with the database when managing a follower: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("The user '{0}' does not follow and has not requested to follow this user")]
+    NotFound(String),
+    #[error("Only signed in users can manage their followers")]
+    Unauthenticated,
+}
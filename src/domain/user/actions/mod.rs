@@ -1,17 +1,51 @@
+mod change_user_id;
 mod delete;
+mod follow;
 mod get_one;
+mod list_follows;
+mod manage_follower;
+mod restrict;
+mod search;
 mod signin;
 mod signup;
+mod unfollow;
+mod update_privacy;
 mod update_user;
+mod upload_avatar;
 
+pub use change_user_id::change_user_id;
+pub use change_user_id::ChangeUserIdError;
 pub use delete::delete;
 pub use delete::DeleteError;
+pub use follow::follow;
+pub use follow::FollowError;
+pub use get_one::get_many_by_str_ids;
 pub use get_one::get_one;
 pub use get_one::get_one_by_str_id;
 pub use get_one::GetOneError;
+pub use get_one::UserIdType;
+pub use list_follows::list_follows;
+pub use list_follows::list_many_follows;
+pub use list_follows::ListFollowsError;
+pub use manage_follower::approve_follower;
+pub use manage_follower::remove_follower;
+pub use manage_follower::ManageFollowerError;
+pub use restrict::list_restrictions;
+pub use restrict::restrict;
+pub use restrict::unrestrict;
+pub use restrict::RestrictionError;
+pub use search::search;
+pub use search::SearchError;
 pub use signin::signin;
 pub use signin::SigninError;
 pub use signup::signup;
 pub use signup::SignupError;
+pub use unfollow::unfollow;
+pub use unfollow::UnfollowError;
+pub use update_privacy::update_privacy;
+pub use update_privacy::PrivacyError;
 pub use update_user::update_user;
+pub use update_user::ExpectedVersion;
 pub use update_user::UpdateError;
+pub use upload_avatar::upload_avatar;
+pub use upload_avatar::AvatarError;
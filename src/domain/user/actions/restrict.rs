@@ -0,0 +1,154 @@
+use crate::{
+    database::Database,
+    domain::user::{
+        actions::get_one::find_visible,
+        dto::GetUserResponse,
+        handle,
+        privacy::Viewer,
+        restriction::{RestrictionKind, RestrictionListing, RestrictionRow},
+    },
+    pagination::{ListQuery, Page},
+};
+use chrono::Utc;
+use sqlx::{Postgres, QueryBuilder};
+use thiserror::Error;
+
+/// Action for blocking or muting another user. Blocking a user also removes any
+/// follows between the two users. Restricting a user twice has no further effect.
+#[tracing::instrument]
+pub async fn restrict(
+    db: &Database,
+    viewer: &Viewer,
+    user_id: &str,
+    kind: RestrictionKind,
+) -> Result<(), RestrictionError> {
+    let Viewer::User { id: owner_id, .. } = viewer else {
+        return Err(RestrictionError::Unauthenticated);
+    };
+
+    tracing::debug!("Requesting user from db");
+    let target = find_visible(db, user_id, viewer)
+        .await?
+        .ok_or(RestrictionError::NotFound(user_id.to_owned()))?;
+    tracing::debug!("User found");
+
+    if target.id == *owner_id {
+        return Err(RestrictionError::SelfRestriction);
+    }
+
+    let mut tx = db.begin().await?;
+
+    sqlx::query(
+        r#"
+        INSERT INTO restriction (owner_id, target_id, kind, created_at)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING;
+    "#,
+    )
+    .bind(owner_id)
+    .bind(target.id)
+    .bind(kind)
+    .bind(Utc::now().naive_utc())
+    .execute(&mut *tx)
+    .await?;
+
+    if kind == RestrictionKind::Block {
+        tracing::debug!("Removing follows between the users");
+        sqlx::query(
+            r#"
+            DELETE FROM follow
+                WHERE (follower_id = $1 AND followee_id = $2)
+                    OR (follower_id = $2 AND followee_id = $1);
+        "#,
+        )
+        .bind(owner_id)
+        .bind(target.id)
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Action for lifting a block or mute. Lifting a restriction which does not
+/// exist succeeds without doing anything.
+#[tracing::instrument]
+pub async fn unrestrict(
+    db: &Database,
+    viewer: &Viewer,
+    user_id: &str,
+    kind: RestrictionKind,
+) -> Result<(), RestrictionError> {
+    let Viewer::User { id: owner_id, .. } = viewer else {
+        return Err(RestrictionError::Unauthenticated);
+    };
+
+    let result = sqlx::query(
+        r#"
+        DELETE FROM restriction USING user_
+            WHERE restriction.target_id = user_.id
+                AND user_.canonical_user_id = $1
+                AND restriction.owner_id = $2
+                AND restriction.kind = $3;
+    "#,
+    )
+    .bind(handle::canonicalize(user_id))
+    .bind(owner_id)
+    .bind(kind)
+    .execute(db.inner())
+    .await?;
+    tracing::debug!("Removed {} restrictions", result.rows_affected());
+
+    Ok(())
+}
+
+/// Action for listing the users who the viewer has blocked or muted, a page at a
+/// time, from the most recent to the oldest.
+#[tracing::instrument]
+pub async fn list_restrictions(
+    db: &Database,
+    viewer: &Viewer,
+    kind: RestrictionKind,
+    query: &ListQuery<RestrictionListing>,
+) -> Result<Page<GetUserResponse>, RestrictionError> {
+    let Viewer::User { id: owner_id, .. } = viewer else {
+        return Err(RestrictionError::Unauthenticated);
+    };
+
+    let mut builder = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT user_.*, restriction.created_at AS restricted_at FROM restriction
+            INNER JOIN user_ ON user_.id = restriction.target_id
+            WHERE restriction.owner_id = "#,
+    );
+    builder
+        .push_bind(*owner_id)
+        .push(" AND restriction.kind = ")
+        .push_bind(kind);
+    query.push_to(&mut builder);
+
+    let rows = builder
+        .build_query_as::<RestrictionRow>()
+        .fetch_all(db.inner())
+        .await?;
+    tracing::debug!("Found {} restricted users", rows.len());
+
+    let mut page = query.page(rows);
+    page.items = viewer.findable(db, page.items, |row| &row.user).await?;
+
+    Ok(page.map(|row| GetUserResponse::for_viewer(row.user, viewer)))
+}
+
+#[derive(Debug, Error)]
+pub enum RestrictionError {
+    #[error("An error occurred with the database when restricting a user: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(String),
+    #[error("Users cannot block or mute themselves")]
+    SelfRestriction,
+    #[error("Only signed in users can block or mute other users")]
+    Unauthenticated,
+}
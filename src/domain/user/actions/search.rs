@@ -0,0 +1,142 @@
+use crate::{
+    database::Database,
+    domain::user::{
+        dto::GetUserResponse,
+        handle,
+        privacy::{Viewer, Visibility},
+        search::{escape_like, SearchListing, SearchRateLimiter, SearchRow},
+    },
+    pagination::{ListQuery, Page},
+};
+use sqlx::{Postgres, QueryBuilder};
+use thiserror::Error;
+use uuid::Uuid;
+
+const MAX_QUERY_LENGTH: usize = 50;
+
+/// Action for searching users by `user_id` and nickname. Users who cannot be
+/// found by the viewer or who the viewer blocked or muted are left out, and
+/// nicknames are only matched where the viewer may see them.
+#[tracing::instrument(skip(limiter))]
+pub async fn search(
+    db: &Database,
+    viewer: &Viewer,
+    limiter: &SearchRateLimiter,
+    list: &ListQuery<SearchListing>,
+) -> Result<Page<GetUserResponse>, SearchError> {
+    let q = list.parameter("q").unwrap_or_default().trim();
+    if q.is_empty() || q.chars().count() > MAX_QUERY_LENGTH {
+        return Err(SearchError::InvalidQuery);
+    }
+
+    limiter
+        .check(&viewer.id().unwrap_or_default())
+        .map_err(|wait| SearchError::RateLimited {
+            retry_after: wait.as_secs().max(1),
+        })?;
+
+    let user_id = handle::canonicalize(q);
+    let nickname = q.to_lowercase();
+    let viewer_id = viewer.id().unwrap_or_default();
+    let clearance = viewer.clearance_of_others();
+    let (blocked_by, excluded): (Vec<Uuid>, Vec<Uuid>) = match viewer.restrictions(db).await? {
+        Some(restrictions) => (
+            restrictions.blocked_by.iter().copied().collect(),
+            restrictions
+                .blocking
+                .union(&restrictions.muted)
+                .copied()
+                .collect(),
+        ),
+        None => (vec![], vec![]),
+    };
+    let visible: Vec<String> = [
+        Visibility::Public,
+        Visibility::Authenticated,
+        Visibility::OnlyMe,
+    ]
+    .into_iter()
+    .filter(|visibility| *visibility <= clearance)
+    .map(|visibility| visibility.as_str().to_owned())
+    .collect();
+
+    let mut query = QueryBuilder::<Postgres>::new("SELECT * FROM (SELECT user_.*, ");
+
+    // Whether the nickname of the user may be matched by this viewer.
+    let nickname_visible = |query: &mut QueryBuilder<'static, Postgres>| {
+        query
+            .push("(user_.id = ")
+            .push_bind(viewer_id)
+            .push(" OR COALESCE(user_.profile_visibility->>'nickname', 'public') = ANY(")
+            .push_bind(visible.clone())
+            .push("))");
+    };
+
+    query
+        .push("(GREATEST(similarity(user_.canonical_user_id, ")
+        .push_bind(user_id.clone())
+        .push("), CASE WHEN ");
+    nickname_visible(&mut query);
+    query
+        .push(" THEN similarity(lower(user_.nickname), ")
+        .push_bind(nickname.clone())
+        .push(") ELSE 0 END) + CASE WHEN user_.canonical_user_id LIKE ")
+        .push_bind(format!("{}%", escape_like(&user_id)))
+        .push(" OR (");
+    nickname_visible(&mut query);
+    query
+        .push(" AND lower(user_.nickname) LIKE ")
+        .push_bind(format!("{}%", escape_like(&nickname)))
+        .push(") THEN 1 ELSE 0 END)::real AS rank FROM user_ WHERE (user_.canonical_user_id % ")
+        .push_bind(user_id.clone())
+        .push(" OR user_.canonical_user_id LIKE ")
+        .push_bind(format!("{}%", escape_like(&user_id)))
+        .push(" OR (");
+    nickname_visible(&mut query);
+    query
+        .push(" AND (lower(user_.nickname) % ")
+        .push_bind(nickname.clone())
+        .push(" OR lower(user_.nickname) LIKE ")
+        .push_bind(format!("{}%", escape_like(&nickname)))
+        .push(")))");
+
+    if clearance != Visibility::OnlyMe {
+        query
+            .push(" AND (user_.id = ")
+            .push_bind(viewer_id)
+            .push(" OR (NOT user_.hidden AND user_.id <> ALL(")
+            .push_bind(blocked_by)
+            .push(")))");
+    }
+
+    // Users the viewer blocked or muted are left out even for admins, as those are
+    // the viewer's own choices.
+    query
+        .push(" AND user_.id <> ALL(")
+        .push_bind(excluded)
+        .push(")");
+
+    query.push(") AS result WHERE true");
+    list.push_to(&mut query);
+
+    let rows = query
+        .build_query_as::<SearchRow>()
+        .fetch_all(db.inner())
+        .await?;
+    tracing::debug!("Found {} users", rows.len());
+
+    let mut page = list.page(rows);
+    page.items = viewer.findable(db, page.items, |row| &row.user).await?;
+
+    Ok(page.map(|row| GetUserResponse::for_viewer(row.user, viewer)))
+}
+
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("An error occurred with the database when searching users: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("The query must be between 1 and {MAX_QUERY_LENGTH} characters")]
+    InvalidQuery,
+    #[error("Too many searches; try again in {retry_after} seconds")]
+    RateLimited { retry_after: u64 },
+}
@@ -1,7 +1,11 @@
 use crate::{
     auth::{issue_jwt, verify_password, JwtError},
     database::Database,
-    domain::user::{dto, User},
+    domain::user::{
+        cache::UserCache,
+        dto,
+        events::{self, AccountEvent},
+    },
 };
 use secrecy::Secret;
 use thiserror::Error;
@@ -11,6 +15,7 @@ use thiserror::Error;
 #[tracing::instrument]
 pub async fn signin(
     db: &Database,
+    cache: &UserCache,
     user_info: &dto::Signin,
     jwt_secret: &Secret<String>,
 ) -> Result<String, SigninError> {
@@ -18,16 +23,16 @@ pub async fn signin(
         "Requesting user from db where user_id is {}",
         &user_info.user_id
     );
-    let user = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE user_id = $1")
-        .bind(&user_info.user_id)
-        .fetch_optional(db.inner())
+    let user = cache
+        .find(db, &user_info.user_id)
         .await?
         .ok_or(SigninError::UserNotFound)?;
     tracing::debug!("User found");
 
     verify_password(&user.password, &user_info.password)?;
 
-    let token = issue_jwt(&user.id, jwt_secret)?;
+    let token = issue_jwt(&user.id, None, jwt_secret)?;
+    events::publish(db.inner(), user.id, AccountEvent::SignedIn).await?;
 
     Ok(token)
 }
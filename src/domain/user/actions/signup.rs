@@ -1,10 +1,15 @@
 use crate::{
     auth::hash_password,
+    configuration::user::UserSettings,
     database::Database,
-    domain::user::{
-        dto::{self, Signup, SignupResponse},
-        User,
+    domain::{
+        user::{
+            dto::{self, GetUserResponse, Signup, SignupResponse},
+            handle, User,
+        },
+        webhook::{self, WebhookEvent},
     },
+    i18n::Message,
 };
 use argon2::password_hash::{self};
 use chrono::Utc;
@@ -14,31 +19,62 @@ use uuid::Uuid;
 
 /// Performs the necessary procedures required for signing up a new user.
 #[tracing::instrument]
-pub async fn signup(db: &Database, user_dto: dto::Signup) -> Result<SignupResponse, SignupError> {
+pub async fn signup(
+    db: &Database,
+    settings: &UserSettings,
+    user_dto: dto::Signup,
+) -> Result<SignupResponse, SignupError> {
     tracing::debug!("Validating request integrity...");
     let ValidSignup { user_id, password } = user_dto.try_into()?;
+    user_id.check_policy(settings)?;
     tracing::debug!("Request contains required fields");
 
     tracing::debug!("Checking if user already exists...");
-    let user = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE user_id = $1")
-        .bind(user_id.as_ref())
+    let user = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE user_id_skeleton = $1")
+        .bind(user_id.skeleton())
         .fetch_optional(db.inner())
         .await?;
 
-    if user.is_some() {
-        Err(SignupError::UserAlreadyExists(user_id.as_ref().to_owned()))?;
+    if let Some(user) = user {
+        if handle::matches(&user.user_id, user_id.as_ref()) {
+            Err(SignupError::UserAlreadyExists(user_id.as_ref().to_owned()))?;
+        }
+
+        Err(SignupError::Confusable(user_id.as_ref().to_owned()))?;
     }
     tracing::debug!("User does not exist");
 
+    tracing::debug!("Checking if user_id is reserved...");
+    let reserved = sqlx::query_scalar::<_, bool>(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM user_id_reservation
+            WHERE user_id_skeleton = $1 AND reserved_until > $2
+        );
+    "#,
+    )
+    .bind(user_id.skeleton())
+    .bind(Utc::now().naive_utc())
+    .fetch_one(db.inner())
+    .await?;
+
+    if reserved {
+        Err(SignupError::UserAlreadyExists(user_id.as_ref().to_owned()))?;
+    }
+    tracing::debug!("User id is not reserved");
+
     tracing::debug!("Hashing password");
     let hashed_password = hash_password(&password).map_err(SignupError::PasswordHash)?;
     tracing::debug!("Password hash success");
 
     tracing::debug!("Inserting user into DB");
+    let mut tx = db.begin().await?;
     let user = sqlx::query_as::<_, User>(
         r#"
-        INSERT INTO user_ (id, user_id, password, created_at, nickname)
-        VALUES($1, $2, $3, $4, $5)
+        INSERT INTO user_ (
+            id, user_id, password, created_at, nickname, canonical_user_id, user_id_skeleton
+        )
+        VALUES($1, $2, $3, $4, $5, $6, $7)
         RETURNING user_id, nickname, id, password;
     "#,
     )
@@ -47,8 +83,19 @@ pub async fn signup(db: &Database, user_dto: dto::Signup) -> Result<SignupRespon
     .bind(hashed_password)
     .bind(Utc::now())
     .bind(user_id.as_ref()) // DEFAULT
-    .fetch_one(db.inner())
-    .await?;
+    .bind(user_id.canonical())
+    .bind(user_id.skeleton())
+    .fetch_one(&mut *tx)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::Database(ref db_error) if db_error.is_unique_violation() => {
+            SignupError::UserAlreadyExists(user_id.as_ref().to_owned())
+        }
+        e => e.into(),
+    })?;
+    let created = GetUserResponse::from(user.clone());
+    webhook::actions::enqueue(&mut *tx, WebhookEvent::UserCreated, &created).await?;
+    tx.commit().await?;
     tracing::debug!("Insert user success");
 
     Ok(user.into())
@@ -66,8 +113,10 @@ pub enum SignupError {
     InvalidPayload,
     #[error("A user with id {0} already exists")]
     UserAlreadyExists(String),
+    #[error("The user id {0} is too similar to the id of an existing user")]
+    Confusable(String),
     #[error("Invalid data was submitted: {field} {reason}")]
-    Validation { field: String, reason: String },
+    Validation { field: String, reason: Message },
 }
 
 #[derive(Debug)]
@@ -102,23 +151,71 @@ pub struct UserId(String);
 impl UserId {
     const MAX_LENGTH: usize = 20;
     const MIN_LENGTH: usize = 8;
+
+    /// Confirm that the user id only uses the characters allowed by the configuration
+    /// and that it is not, and does not look like, one of the reserved user ids.
+    pub fn check_policy(&self, settings: &UserSettings) -> Result<(), SignupError> {
+        let allowed = self.0.chars().all(|c| {
+            settings.user_id_allowed_characters.allows(c)
+                || settings.user_id_allowed_symbols.contains(c)
+        });
+
+        if !allowed {
+            return Err(SignupError::Validation {
+                field: "user_id".into(),
+                reason: Message::new("validation-user-id-characters")
+                    .arg(
+                        "characters",
+                        settings.user_id_allowed_characters.to_string(),
+                    )
+                    .arg("symbols", settings.user_id_allowed_symbols.as_str()),
+            });
+        }
+
+        let skeleton = self.skeleton();
+        if settings
+            .reserved_user_ids
+            .iter()
+            .any(|reserved| handle::skeleton(reserved) == skeleton)
+        {
+            return Err(SignupError::Validation {
+                field: "user_id".into(),
+                reason: Message::new("validation-reserved"),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// The case folded form of the user id which is used for lookups.
+    pub fn canonical(&self) -> String {
+        handle::canonicalize(&self.0)
+    }
+
+    /// The confusable skeleton of the user id.
+    pub fn skeleton(&self) -> String {
+        handle::skeleton(&self.0)
+    }
 }
 
 impl TryFrom<String> for UserId {
     type Error = SignupError;
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value.len() >= UserId::MAX_LENGTH {
+        let value = handle::normalize(&value);
+        let length = value.chars().count();
+
+        if length > UserId::MAX_LENGTH {
             return Err(SignupError::Validation {
                 field: "user_id".into(),
-                reason: format!("must be less or equal to {}", Self::MAX_LENGTH),
+                reason: Message::new("validation-too-long").arg("max", Self::MAX_LENGTH),
             });
         }
 
-        if value.len() < UserId::MIN_LENGTH {
+        if length < UserId::MIN_LENGTH {
             return Err(SignupError::Validation {
                 field: "user_id".into(),
-                reason: format!("must be greater than or equal to {}", Self::MAX_LENGTH),
+                reason: Message::new("validation-too-short").arg("min", Self::MIN_LENGTH),
             });
         }
 
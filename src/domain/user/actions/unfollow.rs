@@ -0,0 +1,42 @@
+use crate::{
+    database::Database,
+    domain::user::{handle, privacy::Viewer},
+};
+use thiserror::Error;
+
+/// Action for unfollowing a user, which also withdraws a pending follow. Unfollowing
+/// a user who is not followed succeeds without doing anything, so that it does not
+/// reveal whether the user exists.
+#[tracing::instrument]
+pub async fn unfollow(db: &Database, viewer: &Viewer, user_id: &str) -> Result<(), UnfollowError> {
+    let Viewer::User {
+        id: follower_id, ..
+    } = viewer
+    else {
+        return Err(UnfollowError::Unauthenticated);
+    };
+
+    let result = sqlx::query(
+        r#"
+        DELETE FROM follow USING user_
+            WHERE follow.followee_id = user_.id
+                AND user_.canonical_user_id = $1
+                AND follow.follower_id = $2;
+    "#,
+    )
+    .bind(handle::canonicalize(user_id))
+    .bind(follower_id)
+    .execute(db.inner())
+    .await?;
+    tracing::debug!("Removed {} follows", result.rows_affected());
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum UnfollowError {
+    #[error("An error occurred with the database when unfollowing a user: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Only signed in users can unfollow other users")]
+    Unauthenticated,
+}
@@ -0,0 +1,84 @@
+use crate::{
+    database::Database,
+    domain::user::{
+        actions::get_one::UserIdType,
+        cache::UserCache,
+        dto::{PrivacyResponse, UpdatePrivacy},
+        events::{self, AccountEvent},
+        follow::FollowStatus,
+        handle,
+        privacy::ProfileVisibility,
+        BasicId, User,
+    },
+};
+use sqlx::types::Json;
+use thiserror::Error;
+
+/// Action for changing who can see a user's profile. Only the fields included in
+/// the update are changed.
+#[tracing::instrument]
+pub async fn update_privacy(
+    db: &Database,
+    cache: &UserCache,
+    user_id: &BasicId,
+    update: &UpdatePrivacy,
+) -> Result<PrivacyResponse, PrivacyError> {
+    tracing::debug!("Updating privacy settings");
+    let visibility: ProfileVisibility = update.visibility.clone().into();
+
+    let mut tx = db.begin().await?;
+
+    let user = sqlx::query_as::<_, User>(
+        r#"
+        UPDATE user_
+            SET
+                hidden = COALESCE($1, hidden),
+                approve_followers = COALESCE($2, approve_followers),
+                profile_visibility = profile_visibility || $3
+            WHERE canonical_user_id = $4
+            RETURNING *;
+    "#,
+    )
+    .bind(update.hidden)
+    .bind(update.approve_followers)
+    .bind(Json(visibility))
+    .bind(handle::canonicalize(user_id.as_str()))
+    .fetch_optional(&mut *tx)
+    .await?
+    .ok_or(PrivacyError::NotFound(UserIdType::Str(user_id.into())))?;
+    tracing::debug!("Success: {:?}", user);
+
+    if !user.approve_followers {
+        tracing::debug!("Accepting pending follow requests");
+        sqlx::query("UPDATE follow SET status = $1 WHERE followee_id = $2 AND status = $3")
+            .bind(FollowStatus::Accepted)
+            .bind(user.id)
+            .bind(FollowStatus::Pending)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    let event = AccountEvent::ProfileUpdated {
+        version: user.version,
+    };
+    events::publish(&mut *tx, user.id, event).await?;
+    tx.commit().await?;
+    cache.invalidate(&user.id);
+
+    Ok(user.into())
+}
+
+#[derive(Debug, Error)]
+pub enum PrivacyError {
+    #[error("An error occurred with the database when updating privacy settings: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(UserIdType),
+    #[error(
+        "A user with the id '{requester}' does not have permission to update user '{requested}'"
+    )]
+    Forbidden {
+        requester: String,
+        requested: String,
+    },
+}
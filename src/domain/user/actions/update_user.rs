@@ -1,51 +1,102 @@
+use sqlx::{Postgres, QueryBuilder};
 use thiserror::Error;
 
 use crate::{
     database::Database,
-    domain::user::{
-        actions::{get_one::UserIdType, GetOneError},
-        dto::{GetUserResponse, UpdateUserDto},
-        User,
+    domain::{
+        user::{
+            actions::{get_one::UserIdType, GetOneError, SignupError},
+            cache::UserCache,
+            dto::{GetUserResponse, UpdateUserDto},
+            events::{self, AccountEvent},
+            handle,
+            patch::ProfileChange,
+            profile::{AvatarUrl, Bio, Comment, DisplayName, Locale, Timezone, Website},
+            User,
+        },
+        webhook::{self, WebhookEvent},
     },
+    i18n::Message,
 };
 
 use super::signup::UserId;
 
-/// Action for retrieving a single user by it's ID.
+/// The versions of a user which an update may be applied to, taken from the
+/// `If-Match` header.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpectedVersion {
+    /// Any version, as long as the user exists.
+    Any,
+    OneOf(Vec<i64>),
+}
+
+impl ExpectedVersion {
+    pub fn matches(&self, version: i64) -> bool {
+        match self {
+            ExpectedVersion::Any => true,
+            ExpectedVersion::OneOf(versions) => versions.contains(&version),
+        }
+    }
+}
+
+/// Action for updating the profile of a user. For merge patches, fields which are
+/// absent from the update are left untouched while fields which are explicitly
+/// `null` are cleared. The user is locked while the change is applied, so the
+/// `test` operations of a JSON Patch and the expected version hold until the
+/// update is written.
 #[tracing::instrument]
 pub async fn update_user(
     db: &Database,
+    cache: &UserCache,
     user_id: &str,
-    update_user: &UpdateUserDto,
+    change: &ProfileChange,
+    expected: Option<&ExpectedVersion>,
 ) -> Result<GetUserResponse, UpdateError> {
-    tracing::debug!("Updating user: {:?}", update_user);
-
-    let update_user: ValidUpdate = update_user.try_into()?;
-    let nickname = update_user
-        .nickname
-        .as_ref()
-        .map(|nickname| Some(nickname.as_ref()));
-
-    let comment = update_user
-        .comment
-        .as_ref()
-        .map(|comment| Some(comment.as_ref()));
+    tracing::debug!("Updating user: {:?}", change);
+
+    let mut tx = db.begin().await?;
+    let current =
+        sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = $1 FOR UPDATE")
+            .bind(handle::canonicalize(user_id))
+            .fetch_optional(&mut *tx)
+            .await?
+            .ok_or(GetOneError::NotFound(UserIdType::Str(user_id.to_owned())))?;
+
+    if expected.is_some_and(|expected| !expected.matches(current.version)) {
+        return Err(UpdateError::PreconditionFailed);
+    }
 
-    let user = sqlx::query_as::<_, User>(
-        r#"
-        UPDATE user_
-            SET
-                nickname = COALESCE($1, nickname),
-                comment = COALESCE($2, comment)
-            WHERE user_id = $3
-            RETURNING user_id, nickname, comment, password, id;
-    "#,
-    )
-    .bind(nickname)
-    .bind(comment)
-    .bind(user_id)
-    .fetch_one(db.inner())
-    .await?;
+    let update_user: ValidUpdate = (&change.to_update(&current)?).try_into()?;
+    let assignments = update_user.assignments();
+
+    let user = if assignments.is_empty() {
+        tracing::debug!("No changes submitted");
+        current
+    } else {
+        let mut query = QueryBuilder::<Postgres>::new("UPDATE user_ SET ");
+        let mut set = query.separated(", ");
+        for (column, value) in assignments {
+            set.push(column)
+                .push_unseparated(" = ")
+                .push_bind_unseparated(value);
+        }
+        let user = query
+            .push(" WHERE id = ")
+            .push_bind(current.id)
+            .push(" RETURNING *")
+            .build_query_as::<User>()
+            .fetch_one(&mut *tx)
+            .await?;
+        let event = AccountEvent::ProfileUpdated {
+            version: user.version,
+        };
+        events::publish(&mut *tx, user.id, event).await?;
+        let updated = GetUserResponse::from(user.clone());
+        webhook::actions::enqueue(&mut *tx, WebhookEvent::UserUpdated, &updated).await?;
+        user
+    };
+    tx.commit().await?;
+    cache.invalidate(&user.id);
     tracing::debug!("Success: {:?}", user);
 
     let mut user: GetUserResponse = user.into();
@@ -62,10 +113,10 @@ pub async fn get_one_by_str_id(
     tracing::debug!("Requesting user from db");
     let user = sqlx::query_as::<_, User>(
         r#"
-        SELECT * FROM user_ WHERE user_id = $1
+        SELECT * FROM user_ WHERE canonical_user_id = $1
     "#,
     )
-    .bind(user_id)
+    .bind(handle::canonicalize(user_id))
     .fetch_optional(db.inner())
     .await?
     .ok_or(GetOneError::NotFound(UserIdType::Str(user_id.to_owned())))?;
@@ -89,72 +140,99 @@ pub enum UpdateError {
         requested: String,
     },
     #[error("Value for field '{field}' is invalid: '{reason}'")]
-    Validation { field: String, reason: String },
+    Validation { field: String, reason: Message },
+    #[error("The user has been changed since it was last read")]
+    PreconditionFailed,
+    #[error("Updates must be made conditional with the If-Match header")]
+    PreconditionRequired,
+    #[error("The test of '{0}' failed")]
+    TestFailed(String),
+    #[error("The body is invalid: {0}")]
+    InvalidPayload(String),
+    #[error("The content type '{0}' is not supported")]
+    UnsupportedMediaType(String),
 }
 
 type Nickname = UserId;
 
+/// A validated update. The outer [Option] of each field indicates whether the
+/// field should be changed and the inner [Option] whether it should be cleared.
 #[derive(Debug)]
 pub struct ValidUpdate {
-    comment: Option<Comment>,
-    nickname: Option<Nickname>,
+    comment: Option<Option<Comment>>,
+    nickname: Option<Option<Nickname>>,
+    display_name: Option<Option<DisplayName>>,
+    bio: Option<Option<Bio>>,
+    avatar_url: Option<Option<AvatarUrl>>,
+    locale: Option<Option<Locale>>,
+    timezone: Option<Option<Timezone>>,
+    website: Option<Option<Website>>,
 }
 
-impl TryFrom<&UpdateUserDto> for ValidUpdate {
-    type Error = UpdateError;
-
-    fn try_from(value: &UpdateUserDto) -> Result<Self, Self::Error> {
-        let comment: Option<Comment> = match &value.comment {
-            Some(id) => Some(id.to_owned().try_into()?),
-            None => None,
-        };
-
-        let nickname = match &value.nickname {
-            Some(nickname) => {
-                Some(
-                    nickname
-                        .to_owned()
-                        .try_into()
-                        .map_err(|_| UpdateError::Validation {
-                            field: "comment".into(),
-                            reason: format!(
-                                "Field must be less than or equal to{}",
-                                Comment::MAX_LENGTH
-                            ),
-                        })?,
-                )
+impl ValidUpdate {
+    /// The columns which should be changed along with their new values.
+    fn assignments(&self) -> Vec<(&'static str, Option<&str>)> {
+        fn assign<'a, T: AsRef<str>>(
+            assignments: &mut Vec<(&'static str, Option<&'a str>)>,
+            column: &'static str,
+            value: &'a Option<Option<T>>,
+        ) {
+            if let Some(value) = value {
+                assignments.push((column, value.as_ref().map(AsRef::as_ref)));
             }
-            None => None,
-        };
+        }
 
-        Ok(Self { comment, nickname })
+        let mut assignments = Vec::new();
+        assign(&mut assignments, "nickname", &self.nickname);
+        assign(&mut assignments, "comment", &self.comment);
+        assign(&mut assignments, "display_name", &self.display_name);
+        assign(&mut assignments, "bio", &self.bio);
+        assign(&mut assignments, "avatar_url", &self.avatar_url);
+        assign(&mut assignments, "locale", &self.locale);
+        assign(&mut assignments, "timezone", &self.timezone);
+        assign(&mut assignments, "website", &self.website);
+        assignments
     }
 }
 
-#[derive(Debug)]
-pub struct Comment(String);
-
-impl Comment {
-    const MAX_LENGTH: usize = 30;
-}
-
-impl TryFrom<String> for Comment {
+impl TryFrom<&UpdateUserDto> for ValidUpdate {
     type Error = UpdateError;
 
-    fn try_from(value: String) -> Result<Self, Self::Error> {
-        if value.len() >= Self::MAX_LENGTH {
-            return Err(UpdateError::Validation {
-                field: "comment".into(),
-                reason: format!("must be less or equal to {}", Self::MAX_LENGTH),
-            });
-        }
-
-        Ok(Self(value))
+    fn try_from(value: &UpdateUserDto) -> Result<Self, Self::Error> {
+        let nickname = validate(&value.nickname, |nickname| {
+            Nickname::try_from(nickname).map_err(|e| match e {
+                SignupError::Validation { reason, .. } => UpdateError::Validation {
+                    field: "nickname".into(),
+                    reason,
+                },
+                e => UpdateError::Validation {
+                    field: "nickname".into(),
+                    reason: e.to_string().into(),
+                },
+            })
+        })?;
+
+        Ok(Self {
+            comment: validate(&value.comment, Comment::try_from)?,
+            nickname,
+            display_name: validate(&value.display_name, DisplayName::try_from)?,
+            bio: validate(&value.bio, Bio::try_from)?,
+            avatar_url: validate(&value.avatar_url, AvatarUrl::try_from)?,
+            locale: validate(&value.locale, Locale::try_from)?,
+            timezone: validate(&value.timezone, Timezone::try_from)?,
+            website: validate(&value.website, Website::try_from)?,
+        })
     }
 }
 
-impl AsRef<str> for Comment {
-    fn as_ref(&self) -> &str {
-        self.0.as_ref()
+/// Validate a field which may be absent, explicitly `null`, or set to a new value.
+fn validate<T>(
+    value: &Option<Option<String>>,
+    parse: impl Fn(String) -> Result<T, UpdateError>,
+) -> Result<Option<Option<T>>, UpdateError> {
+    match value {
+        Some(Some(value)) => Ok(Some(Some(parse(value.to_owned())?))),
+        Some(None) => Ok(Some(None)),
+        None => Ok(None),
     }
 }
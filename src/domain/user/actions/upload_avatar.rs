@@ -0,0 +1,181 @@
+use crate::{
+    blob::{BlobError, BlobStore},
+    database::Database,
+    domain::user::{
+        avatar::{self, THUMBNAIL_SIZES},
+        cache::UserCache,
+        dto::GetUserResponse,
+        events::{self, AccountEvent},
+        handle, BasicId, User,
+    },
+};
+use sqlx::types::Json;
+use std::collections::BTreeMap;
+use thiserror::Error;
+use uuid::Uuid;
+
+/// Action for replacing a user's avatar. The uploaded image is validated and
+/// resized into thumbnails, which are written to the blob store. The user is then
+/// updated with the row locked, and the thumbnails of the avatar which that
+/// update replaced are removed, so concurrent uploads each remove the avatar they
+/// replaced. When the update fails, the new thumbnails are removed instead.
+#[tracing::instrument(skip(upload))]
+pub async fn upload_avatar(
+    db: &Database,
+    cache: &UserCache,
+    store: &dyn BlobStore,
+    user_id: &BasicId,
+    upload: Vec<u8>,
+) -> Result<GetUserResponse, AvatarError> {
+    tracing::debug!("Processing avatar image of {} bytes", upload.len());
+    let thumbnails = actix_web::rt::task::spawn_blocking(move || avatar::process(&upload))
+        .await
+        .map_err(|_| AvatarError::Processing)??;
+    tracing::debug!("Image processing success");
+
+    tracing::debug!("Requesting user from db");
+    let user = sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = $1")
+        .bind(handle::canonicalize(user_id.as_str()))
+        .fetch_optional(db.inner())
+        .await?
+        .ok_or(AvatarError::NotFound(user_id.into()))?;
+    tracing::debug!("User found");
+
+    let avatar_key = format!("avatars/{}/{}", user.id, Uuid::new_v4());
+    let mut urls = BTreeMap::new();
+
+    tracing::debug!("Storing thumbnails under {avatar_key}");
+    for thumbnail in thumbnails {
+        let key = thumbnail_key(&avatar_key, thumbnail.size);
+        if let Err(e) = store.put(&key, "image/png", thumbnail.data).await {
+            remove(store, &avatar_key).await;
+            return Err(e.into());
+        }
+        urls.insert(thumbnail.size.to_string(), store.url(&key));
+    }
+    tracing::debug!("Thumbnails stored");
+
+    let avatar_url = THUMBNAIL_SIZES
+        .iter()
+        .max()
+        .map(|size| store.url(&thumbnail_key(&avatar_key, *size)));
+
+    let replaced = match replace(db, user.id, &avatar_key, urls, avatar_url).await {
+        Ok(Some(replaced)) => replaced,
+        Ok(None) => {
+            remove(store, &avatar_key).await;
+            return Err(AvatarError::NotFound(user_id.into()));
+        }
+        Err(e) => {
+            remove(store, &avatar_key).await;
+            return Err(e.into());
+        }
+    };
+    let updated = replaced.user;
+    cache.invalidate(&updated.id);
+    tracing::debug!("Success: {:?}", updated);
+
+    if let Some(previous) = replaced.previous_avatar_key {
+        tracing::debug!("Removing previous avatar {previous}");
+        remove(store, &previous).await;
+    }
+
+    Ok(updated.into())
+}
+
+/// The user after their avatar was replaced, along with the key of the avatar
+/// which was replaced.
+#[derive(sqlx::FromRow)]
+struct Replaced {
+    #[sqlx(flatten)]
+    user: User,
+    previous_avatar_key: Option<String>,
+}
+
+/// Set the avatar of the user with the id `id`, and announce the change. Gives
+/// nothing when the user no longer exists.
+async fn replace(
+    db: &Database,
+    id: Uuid,
+    avatar_key: &str,
+    urls: BTreeMap<String, String>,
+    avatar_url: Option<String>,
+) -> Result<Option<Replaced>, sqlx::Error> {
+    let mut tx = db.begin().await?;
+    let replaced = sqlx::query_as::<_, Replaced>(
+        r#"
+        WITH previous AS (
+            SELECT id, avatar_key FROM user_ WHERE id = $4 FOR UPDATE
+        )
+        UPDATE user_
+            SET
+                avatar_key = $1,
+                avatar_thumbnails = $2,
+                avatar_url = $3
+            FROM previous
+            WHERE user_.id = previous.id
+            RETURNING user_.*, previous.avatar_key AS previous_avatar_key;
+    "#,
+    )
+    .bind(avatar_key)
+    .bind(Json(urls))
+    .bind(avatar_url)
+    .bind(id)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let Some(replaced) = replaced else {
+        return Ok(None);
+    };
+
+    let event = AccountEvent::ProfileUpdated {
+        version: replaced.user.version,
+    };
+    events::publish(&mut *tx, id, event).await?;
+    tx.commit().await?;
+
+    Ok(Some(replaced))
+}
+
+/// Remove the thumbnails of an avatar, which are only logged when they fail.
+async fn remove(store: &dyn BlobStore, avatar_key: &str) {
+    for size in THUMBNAIL_SIZES {
+        if let Err(e) = store.delete(&thumbnail_key(avatar_key, size)).await {
+            tracing::warn!("Failed to remove avatar thumbnail: {e}");
+        }
+    }
+}
+
+fn thumbnail_key(avatar_key: &str, size: u32) -> String {
+    format!("{avatar_key}/{size}.png")
+}
+
+#[derive(Debug, Error)]
+pub enum AvatarError {
+    #[error("An error occurred with the database when updating an avatar: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("A user with the id '{0}' was not found")]
+    NotFound(String),
+    #[error("Failed to store the avatar: {0}")]
+    Blob(#[from] BlobError),
+    #[error("The uploaded file could not be read as an image: {0}")]
+    InvalidImage(#[from] image::ImageError),
+    #[error("The uploaded file is not a png, jpeg, gif or webp image")]
+    UnsupportedType,
+    #[error("The uploaded file is too large")]
+    TooLarge,
+    #[error(
+        "The image is {width}x{height} pixels; each side must be between {min} and {max} pixels"
+    )]
+    Dimensions {
+        width: u32,
+        height: u32,
+        min: u32,
+        max: u32,
+    },
+    #[error("The request did not contain an image file")]
+    MissingFile,
+    #[error("Failed to read the upload: {0}")]
+    Upload(String),
+    #[error("The image processing task failed")]
+    Processing,
+}
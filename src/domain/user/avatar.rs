@@ -0,0 +1,75 @@
+//! Validation and resizing of uploaded avatar images. Uploaded images are decoded
+//! and re-encoded as PNG thumbnails, which strips any EXIF or other metadata that
+//! was embedded in the original file.
+
+use super::actions::AvatarError;
+use image::{imageops::FilterType, ImageFormat};
+use std::io::Cursor;
+
+/// The largest upload which we will attempt to process.
+pub const MAX_UPLOAD_BYTES: usize = 5 * 1024 * 1024;
+
+/// The edge lengths, in pixels, of the square thumbnails generated for each avatar.
+pub const THUMBNAIL_SIZES: [u32; 3] = [64, 128, 256];
+
+const ALLOWED_FORMATS: [ImageFormat; 4] = [
+    ImageFormat::Png,
+    ImageFormat::Jpeg,
+    ImageFormat::Gif,
+    ImageFormat::WebP,
+];
+const MIN_DIMENSION: u32 = 64;
+const MAX_DIMENSION: u32 = 4096;
+
+/// A square thumbnail of an avatar, encoded as PNG.
+#[derive(Debug)]
+pub struct Thumbnail {
+    pub size: u32,
+    pub data: Vec<u8>,
+}
+
+/// Validate an uploaded image and produce a thumbnail for each of the [THUMBNAIL_SIZES].
+/// Non-square images are cropped around their center.
+///
+/// This is CPU bound, so it should not be called directly from an async context.
+pub fn process(upload: &[u8]) -> Result<Vec<Thumbnail>, AvatarError> {
+    if upload.len() > MAX_UPLOAD_BYTES {
+        return Err(AvatarError::TooLarge);
+    }
+
+    let format = image::guess_format(upload).map_err(|_| AvatarError::UnsupportedType)?;
+    if !ALLOWED_FORMATS.contains(&format) {
+        return Err(AvatarError::UnsupportedType);
+    }
+
+    // Check the dimensions from the header before decoding the whole image so
+    // that we never allocate a buffer for an absurdly large image.
+    let reader = image::io::Reader::with_format(Cursor::new(upload), format);
+    let (width, height) = reader.into_dimensions()?;
+    if width < MIN_DIMENSION
+        || height < MIN_DIMENSION
+        || width > MAX_DIMENSION
+        || height > MAX_DIMENSION
+    {
+        return Err(AvatarError::Dimensions {
+            width,
+            height,
+            min: MIN_DIMENSION,
+            max: MAX_DIMENSION,
+        });
+    }
+
+    let image = image::load_from_memory_with_format(upload, format)?;
+
+    THUMBNAIL_SIZES
+        .iter()
+        .map(|&size| {
+            let mut data = Vec::new();
+            image
+                .resize_to_fill(size, size, FilterType::Lanczos3)
+                .write_to(&mut Cursor::new(&mut data), ImageFormat::Png)?;
+
+            Ok(Thumbnail { size, data })
+        })
+        .collect()
+}
@@ -0,0 +1,266 @@
+//! A bounded, in-process cache in front of the lookups of users. Profile reads
+//! and authentication far outnumber changes, so users are kept for a short time
+//! after they are read. Every action which changes or deletes a user calls
+//! [UserCache::invalidate]. The database also announces each change on
+//! [CHANNEL], so that several instances can evict a user together with
+//! [listen].
+
+use super::{handle, User};
+use crate::{configuration::cache::CacheSettings, database::Database};
+use serde::Serialize;
+use sqlx::postgres::PgListener;
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex, MutexGuard,
+    },
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// The channel on which the database announces the id of each changed user.
+pub const CHANNEL: &str = "user_changed";
+
+/// How long to wait before listening again after the connection failed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+pub struct UserCache {
+    capacity: usize,
+    ttl: Duration,
+    state: Mutex<State>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+    evictions: AtomicU64,
+}
+
+#[derive(Default)]
+struct State {
+    users: HashMap<Uuid, (Instant, User)>,
+    /// The id of each cached user, keyed by their canonical `user_id`.
+    ids: HashMap<String, Uuid>,
+    /// Incremented on every invalidation, so that a user which was read before
+    /// it was invalidated is not added afterwards.
+    generation: u64,
+}
+
+/// The counters of a [UserCache].
+#[derive(Debug, Serialize)]
+pub struct CacheStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub evictions: u64,
+    pub entries: usize,
+    pub capacity: usize,
+}
+
+impl Debug for UserCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("UserCache")
+            .field("capacity", &self.capacity)
+            .field("ttl", &self.ttl)
+            .finish()
+    }
+}
+
+impl Default for UserCache {
+    fn default() -> Self {
+        Self::new(&CacheSettings::default())
+    }
+}
+
+impl UserCache {
+    pub fn new(settings: &CacheSettings) -> Self {
+        Self {
+            capacity: settings.user_capacity,
+            ttl: Duration::from_secs(settings.user_ttl_seconds),
+            state: Mutex::default(),
+            hits: AtomicU64::default(),
+            misses: AtomicU64::default(),
+            evictions: AtomicU64::default(),
+        }
+    }
+
+    /// Look up a user by their `user_id`, reading them from the database when
+    /// they are not cached.
+    pub async fn find(&self, db: &Database, user_id: &str) -> Result<Option<User>, sqlx::Error> {
+        let canonical = handle::canonicalize(user_id);
+        let cached = self.read(|state| state.ids.get(&canonical).copied());
+        self.read_through(cached, || {
+            sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE canonical_user_id = $1")
+                .bind(&canonical)
+                .fetch_optional(db.inner())
+        })
+        .await
+    }
+
+    /// Look up a user by their id, reading them from the database when they are
+    /// not cached.
+    pub async fn find_by_id(&self, db: &Database, id: &Uuid) -> Result<Option<User>, sqlx::Error> {
+        self.read_through(Some(*id), || {
+            sqlx::query_as::<_, User>("SELECT * FROM user_ WHERE id = $1")
+                .bind(id)
+                .fetch_optional(db.inner())
+        })
+        .await
+    }
+
+    /// Evict the user with the given id. Must be called after every change to a
+    /// user has been committed.
+    pub fn invalidate(&self, id: &Uuid) {
+        if let Some(mut state) = self.lock() {
+            state.generation += 1;
+            if let Some((_, user)) = state.users.remove(id) {
+                state.ids.remove(&handle::canonicalize(&user.user_id));
+            }
+        }
+    }
+
+    /// Evict every user, for when changes may have been missed.
+    pub fn clear(&self) {
+        if let Some(mut state) = self.lock() {
+            let generation = state.generation + 1;
+            *state = State {
+                generation,
+                ..Default::default()
+            };
+        }
+    }
+
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            evictions: self.evictions.load(Ordering::Relaxed),
+            entries: self.read(|state| Some(state.users.len())).unwrap_or(0),
+            capacity: self.capacity,
+        }
+    }
+
+    async fn read_through<F>(
+        &self,
+        id: Option<Uuid>,
+        load: impl FnOnce() -> F,
+    ) -> Result<Option<User>, sqlx::Error>
+    where
+        F: std::future::Future<Output = Result<Option<User>, sqlx::Error>>,
+    {
+        if self.capacity == 0 {
+            return load().await;
+        }
+
+        let now = Instant::now();
+        let cached = self.read(|state| {
+            let (cached_at, user) = state.users.get(&id?)?;
+            (now.duration_since(*cached_at) < self.ttl).then(|| user.clone())
+        });
+        if let Some(user) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(Some(user));
+        }
+        self.misses.fetch_add(1, Ordering::Relaxed);
+
+        let generation = self.read(|state| Some(state.generation)).unwrap_or(0);
+        let user = load().await?;
+        if let Some(user) = &user {
+            self.insert(user, generation);
+        }
+
+        Ok(user)
+    }
+
+    /// Cache a user, unless the cache was invalidated since they were read.
+    /// When the cache is full, expired users are dropped first and then the
+    /// user who was cached the longest ago.
+    fn insert(&self, user: &User, generation: u64) {
+        let Some(mut state) = self.lock() else {
+            return;
+        };
+        if state.generation != generation {
+            return;
+        }
+
+        let now = Instant::now();
+        if !state.users.contains_key(&user.id) && state.users.len() >= self.capacity {
+            let before = state.users.len();
+            state
+                .users
+                .retain(|_, (cached_at, _)| now.duration_since(*cached_at) < self.ttl);
+
+            if state.users.len() >= self.capacity {
+                let oldest = state
+                    .users
+                    .iter()
+                    .min_by_key(|(_, (cached_at, _))| *cached_at)
+                    .map(|(id, _)| *id);
+                if let Some(oldest) = oldest {
+                    state.users.remove(&oldest);
+                }
+            }
+
+            let State { users, ids, .. } = &mut *state;
+            ids.retain(|_, id| users.contains_key(id));
+            self.evictions
+                .fetch_add((before - users.len()) as u64, Ordering::Relaxed);
+        }
+
+        state
+            .ids
+            .insert(handle::canonicalize(&user.user_id), user.id);
+        state.users.insert(user.id, (now, user.clone()));
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&State) -> Option<T>) -> Option<T> {
+        self.lock().and_then(|state| f(&state))
+    }
+
+    /// The state of the cache, unless it is disabled.
+    fn lock(&self) -> Option<MutexGuard<'_, State>> {
+        (self.capacity > 0).then(|| self.state.lock().expect("The user cache is not poisoned"))
+    }
+}
+
+/// Evict users from the cache as the database announces changes to them. When
+/// the connection is lost, announcements may have been missed, so the whole
+/// cache is cleared.
+#[tracing::instrument(skip(cache, db))]
+pub async fn listen(cache: &UserCache, db: &Database) {
+    loop {
+        let mut listener = match PgListener::connect_with(db.inner()).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("Failed to connect the listener for user changes: {e}");
+                actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        if let Err(e) = listener.listen(CHANNEL).await {
+            tracing::error!("Failed to listen for user changes: {e}");
+            actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        tracing::debug!("Listening for user changes");
+        cache.clear();
+
+        loop {
+            match listener.try_recv().await {
+                Ok(Some(notification)) => match notification.payload().parse() {
+                    Ok(id) => cache.invalidate(&id),
+                    Err(_) => tracing::warn!("Ignoring user change {:?}", notification.payload()),
+                },
+                Ok(None) => {
+                    tracing::warn!("Lost the connection for user changes, clearing the cache");
+                    cache.clear();
+                }
+                Err(e) => {
+                    tracing::error!("Failed to receive user changes: {e}");
+                    break;
+                }
+            }
+        }
+
+        actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+    }
+}
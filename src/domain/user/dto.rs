@@ -1,16 +1,22 @@
+use super::follow::FollowStatus;
+use super::privacy::{ProfileField, ProfileVisibility, Viewer, Visibility};
 use super::User;
+use chrono::NaiveDateTime;
 use secrecy::Secret;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
 
 /// User submitted data for signing up
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct Signup {
     pub user_id: Option<String>,
+    #[schema(value_type = Option<String>, format = Password)]
     pub password: Option<Secret<String>>,
 }
 
 /// Response format when user is requested
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ToSchema)]
 pub struct SignupResponse {
     pub user_id: String,
     pub nickname: String,
@@ -25,38 +31,180 @@ impl From<User> for SignupResponse {
     }
 }
 
-/// User submitted data for signing up
-#[derive(Debug, Serialize)]
+/// Response format when a user's profile is requested
+#[derive(Debug, Clone, Serialize, ToSchema)]
 pub struct GetUserResponse {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user_id: Option<String>,
     pub nickname: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_url: Option<String>,
+    /// Urls of the uploaded avatar, keyed by the edge length of the thumbnail.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar_thumbnails: Option<BTreeMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub followers_count: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub following_count: Option<i64>,
+    /// The version of the user, which is sent as the ETag rather than in the body.
+    #[serde(skip)]
+    pub version: i64,
+    /// When the user was last changed, which is sent as Last-Modified.
+    #[serde(skip)]
+    pub updated_at: NaiveDateTime,
+    /// Whether every viewer who can find the user gets the same response, so
+    /// that shared caches may store it.
+    #[serde(skip)]
+    pub shared: bool,
 }
 
 impl From<User> for GetUserResponse {
     fn from(value: User) -> Self {
         Self {
-            user_id: Some(value.user_id.clone()),
-            nickname: value.user_id,
+            nickname: value.nickname.unwrap_or_else(|| value.user_id.clone()),
+            user_id: Some(value.user_id),
             comment: value.comment,
+            display_name: value.display_name,
+            bio: value.bio,
+            avatar_url: value.avatar_url,
+            avatar_thumbnails: value.avatar_thumbnails.map(|thumbnails| thumbnails.0),
+            locale: value.locale,
+            timezone: value.timezone,
+            website: value.website,
+            followers_count: None,
+            following_count: None,
+            version: value.version,
+            updated_at: value.updated_at,
+            shared: false,
         }
     }
 }
 
-/// User submitted data for modifying their account
-#[derive(Debug, Deserialize)]
+impl GetUserResponse {
+    /// Build the response for the given viewer, leaving out each field of the
+    /// profile which the viewer is not allowed to see.
+    pub fn for_viewer(user: User, viewer: &Viewer) -> Self {
+        let can_see = |field| viewer.can_see(&user, field);
+        let nickname = can_see(ProfileField::Nickname);
+        let comment = can_see(ProfileField::Comment);
+        let display_name = can_see(ProfileField::DisplayName);
+        let bio = can_see(ProfileField::Bio);
+        let avatar = can_see(ProfileField::Avatar);
+        let locale = can_see(ProfileField::Locale);
+        let timezone = can_see(ProfileField::Timezone);
+        let website = can_see(ProfileField::Website);
+        let shared = !user.hidden && user.profile_visibility.is_public();
+
+        let mut response = Self::from(user);
+        if !nickname {
+            response.nickname = response.user_id.clone().unwrap_or_default();
+        }
+        response.comment = response.comment.filter(|_| comment);
+        response.display_name = response.display_name.filter(|_| display_name);
+        response.bio = response.bio.filter(|_| bio);
+        response.avatar_url = response.avatar_url.filter(|_| avatar);
+        response.avatar_thumbnails = response.avatar_thumbnails.filter(|_| avatar);
+        response.locale = response.locale.filter(|_| locale);
+        response.timezone = response.timezone.filter(|_| timezone);
+        response.website = response.website.filter(|_| website);
+        response.shared = shared;
+
+        response
+    }
+}
+
+/// User submitted data for modifying their account. For each field, leaving it out
+/// keeps the current value and setting it to `null` clears it.
+#[derive(Debug, Default, Clone, Deserialize, ToSchema)]
 pub struct UpdateUserDto {
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub nickname: Option<String>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub comment: Option<String>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub nickname: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub comment: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub display_name: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub bio: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub avatar_url: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub locale: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub timezone: Option<Option<String>>,
+    #[serde(default, deserialize_with = "double_option")]
+    pub website: Option<Option<String>>,
+}
+
+/// Distinguishes between a field which is missing, which deserializes to `None`
+/// thanks to `#[serde(default)]`, and a field which is `null`, which deserializes
+/// to `Some(None)`.
+fn double_option<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+{
+    Deserialize::deserialize(deserializer).map(Some)
 }
 
 /// User submitted data used for signing in.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct Signin {
     pub user_id: String,
+    #[schema(value_type = String, format = Password)]
     pub password: Secret<String>,
 }
+
+/// User submitted changes to the privacy settings of an account. Fields which are
+/// left out of `visibility` keep their current setting.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct UpdatePrivacy {
+    pub hidden: Option<bool>,
+    /// Whether new followers need to be approved. Turning this off approves all
+    /// pending follow requests.
+    pub approve_followers: Option<bool>,
+    #[serde(default)]
+    pub visibility: BTreeMap<ProfileField, Visibility>,
+}
+
+/// Response format for the privacy settings of an account
+#[derive(Debug, Serialize, ToSchema)]
+pub struct PrivacyResponse {
+    pub hidden: bool,
+    pub approve_followers: bool,
+    pub visibility: ProfileVisibility,
+}
+
+impl From<User> for PrivacyResponse {
+    fn from(value: User) -> Self {
+        Self {
+            hidden: value.hidden,
+            approve_followers: value.approve_followers,
+            visibility: value.profile_visibility.0,
+        }
+    }
+}
+
+/// Response format for a follow of another user
+#[derive(Debug, Serialize, ToSchema)]
+pub struct FollowResponse {
+    pub user_id: String,
+    pub status: FollowStatus,
+}
+
+/// User submitted data for changing their `user_id`
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct ChangeUserId {
+    pub user_id: String,
+}
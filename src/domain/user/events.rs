@@ -0,0 +1,175 @@
+//! Events which happen to an account, streamed to the user so that other devices
+//! notice them without polling. Domain actions [publish] each event on
+//! [CHANNEL], within the transaction of the change where there is one, so that
+//! it is only announced once the change commits. Every instance [listen]s on the
+//! channel and hands the events to the subscribers of its [EventHub], which
+//! means that subscribers see the events of changes made through any instance.
+
+use crate::database::Database;
+use serde::{Deserialize, Serialize};
+use sqlx::postgres::PgListener;
+use sqlx::PgExecutor;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{broadcast, watch};
+use tokio_stream::wrappers::{errors::BroadcastStreamRecvError, BroadcastStream};
+use tokio_stream::{Stream, StreamExt};
+use uuid::Uuid;
+
+/// The channel on which the events of every account are announced.
+pub const CHANNEL: &str = "account_event";
+
+/// How many events are kept for subscribers which fall behind.
+const CAPACITY: usize = 256;
+
+/// How long to wait before listening again after the connection failed.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// How long a subscriber waits for the hub to be listening.
+const LISTENING_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum AccountEvent {
+    /// The profile, privacy settings, avatar or `user_id` of the user changed.
+    ProfileUpdated { version: i64 },
+    /// Someone signed in as the user.
+    SignedIn,
+}
+
+impl AccountEvent {
+    /// The name of the event, which is also its `type`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::ProfileUpdated { .. } => "profile_updated",
+            Self::SignedIn => "signed_in",
+        }
+    }
+}
+
+/// An event as it is announced on [CHANNEL].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Notification {
+    user: Uuid,
+    event: AccountEvent,
+}
+
+/// Announce that `event` happened to the user with the id `user`.
+#[tracing::instrument(skip(executor))]
+pub async fn publish<'e>(
+    executor: impl PgExecutor<'e>,
+    user: Uuid,
+    event: AccountEvent,
+) -> Result<(), sqlx::Error> {
+    let payload = serde_json::to_string(&Notification { user, event })
+        .expect("Account events can be serialized");
+
+    sqlx::query("SELECT pg_notify($1, $2)")
+        .bind(CHANNEL)
+        .bind(payload)
+        .execute(executor)
+        .await?;
+
+    Ok(())
+}
+
+/// Hands the events announced on [CHANNEL] to the subscribers of this instance.
+pub struct EventHub {
+    sender: broadcast::Sender<Notification>,
+    listening: watch::Sender<bool>,
+}
+
+impl Default for EventHub {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CAPACITY).0,
+            listening: watch::channel(false).0,
+        }
+    }
+}
+
+impl EventHub {
+    /// The events of the user with the id `user`, from now on. It waits until the
+    /// hub is listening, so that no event announced afterwards is missed, and
+    /// fails when the hub is not listening within [LISTENING_TIMEOUT].
+    pub async fn subscribe(
+        &self,
+        user: Uuid,
+    ) -> Result<impl Stream<Item = AccountEvent>, EventError> {
+        let receiver = self.sender.subscribe();
+        let mut listening = self.listening.subscribe();
+        let waited = actix_web::rt::time::timeout(
+            LISTENING_TIMEOUT,
+            listening.wait_for(|listening| *listening),
+        )
+        .await;
+        match waited {
+            Ok(Ok(_)) => {}
+            Ok(Err(_)) => {
+                tracing::warn!("The event hub was dropped before it was listening");
+                return Err(EventError::NotListening);
+            }
+            Err(_) => {
+                tracing::warn!("The event hub is not listening for account events");
+                return Err(EventError::NotListening);
+            }
+        }
+
+        Ok(
+            BroadcastStream::new(receiver).filter_map(move |received| match received {
+                Ok(notification) => (notification.user == user).then_some(notification.event),
+                Err(BroadcastStreamRecvError::Lagged(missed)) => {
+                    tracing::warn!("A subscriber missed {missed} account events");
+                    None
+                }
+            }),
+        )
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum EventError {
+    #[error("Account events cannot be streamed until the connection to the database is listening for them")]
+    NotListening,
+}
+
+/// Hand the events announced on [CHANNEL] to the subscribers of `hub`.
+#[tracing::instrument(skip(hub, db))]
+pub async fn listen(hub: &EventHub, db: &Database) {
+    loop {
+        let mut listener = match PgListener::connect_with(db.inner()).await {
+            Ok(listener) => listener,
+            Err(e) => {
+                tracing::error!("Failed to connect the listener for account events: {e}");
+                actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+                continue;
+            }
+        };
+
+        if let Err(e) = listener.listen(CHANNEL).await {
+            tracing::error!("Failed to listen for account events: {e}");
+            actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        tracing::debug!("Listening for account events");
+        hub.listening.send_replace(true);
+
+        loop {
+            match listener.try_recv().await {
+                Ok(Some(notification)) => match serde_json::from_str(notification.payload()) {
+                    // Sending only fails when nobody is subscribed.
+                    Ok(notification) => _ = hub.sender.send(notification),
+                    Err(_) => tracing::warn!("Ignoring account event {:?}", notification.payload()),
+                },
+                Ok(None) => tracing::warn!("Lost the connection for account events"),
+                Err(e) => {
+                    tracing::error!("Failed to receive account events: {e}");
+                    break;
+                }
+            }
+        }
+
+        hub.listening.send_replace(false);
+        actix_web::rt::time::sleep(RECONNECT_DELAY).await;
+    }
+}
@@ -0,0 +1,62 @@
+//! The follow graph between users. A follow of an account which requires
+//! approval stays [FollowStatus::Pending] until the followed user approves it,
+//! and only accepted follows are counted or listed as followers.
+
+use super::User;
+use crate::pagination::{Field, FieldKind, Listing, Value};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum FollowStatus {
+    Pending,
+    Accepted,
+}
+
+/// Which side of the follow graph to list for a user.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FollowList {
+    /// Users who follow the user.
+    Followers,
+    /// Users who the user follows.
+    Following,
+    /// Users who are waiting for the user to approve their follow.
+    Requests,
+}
+
+/// A user in a follow list along with the time at which the follow was made.
+#[derive(Debug, sqlx::FromRow)]
+pub struct FollowRow {
+    #[sqlx(flatten)]
+    pub user: User,
+    pub followed_at: NaiveDateTime,
+}
+
+/// Follow lists are ordered from the most recent follow to the oldest.
+#[derive(Debug)]
+pub struct FollowListing;
+
+impl Listing for FollowListing {
+    type Row = FollowRow;
+
+    const SORTS: &'static [Field] = &[Field {
+        name: "followed_at",
+        column: "follow.created_at",
+        kind: FieldKind::Timestamp,
+    }];
+    const FILTERS: &'static [Field] = &[];
+    const DEFAULT_SORT: &'static str = "-followed_at";
+    const TIE_BREAKER: &'static str = "user_.id";
+
+    fn sort_value(row: &FollowRow, _: &Field) -> Value {
+        Value::Timestamp(row.followed_at)
+    }
+
+    fn tie_breaker(row: &FollowRow) -> Uuid {
+        row.user.id
+    }
+}
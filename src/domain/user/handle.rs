@@ -0,0 +1,35 @@
+//! Helpers for normalizing and comparing user handles (the `user_id` chosen by a user).
+//!
+//! Three forms of a handle are used throughout the application:
+//!
+//! * the normalized form ([normalize]) is what we store and display. It is the
+//!   NFKC normalization of what the user submitted.
+//! * the canonical form ([canonicalize]) is case folded and is what we use for
+//!   lookups and for the unique index, so that `TaroYamada` and `taroyamada` are
+//!   the same user.
+//! * the skeleton ([skeleton]) maps visually confusable characters onto a common
+//!   prototype as described in UTS #39. Two handles with the same skeleton look
+//!   alike, so only one of them may exist at a time.
+
+use caseless::default_case_fold_str;
+use unicode_normalization::UnicodeNormalization;
+
+/// Apply NFKC normalization to a handle.
+pub fn normalize(value: &str) -> String {
+    value.nfkc().collect()
+}
+
+/// Produce the case folded form of a handle which is used for lookups.
+pub fn canonicalize(value: &str) -> String {
+    default_case_fold_str(&normalize(value)).nfkc().collect()
+}
+
+/// Produce the confusable skeleton of a handle.
+pub fn skeleton(value: &str) -> String {
+    unicode_security::skeleton(&canonicalize(value)).collect()
+}
+
+/// Determine whether two handles refer to the same user.
+pub fn matches(left: &str, right: &str) -> bool {
+    canonicalize(left) == canonicalize(right)
+}
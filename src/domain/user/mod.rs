@@ -1,8 +1,23 @@
+use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use std::collections::BTreeMap;
 use uuid::Uuid;
 
+use self::privacy::ProfileVisibility;
+
 pub mod actions;
+pub mod avatar;
+pub mod cache;
 pub mod dto;
+pub mod events;
+pub mod follow;
+pub mod handle;
+pub mod patch;
+pub mod privacy;
+pub mod profile;
+pub mod restriction;
+pub mod search;
 
 /// Represents a user as stored in the database.
 #[derive(Debug, Serialize, Deserialize, sqlx::FromRow, Clone)]
@@ -16,6 +31,64 @@ pub struct User {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[sqlx(default)]
     pub comment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub user_id_changed_at: Option<NaiveDateTime>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub display_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub bio: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub avatar_url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub locale: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub timezone: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub website: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub avatar_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[sqlx(default)]
+    pub avatar_thumbnails: Option<Json<BTreeMap<String, String>>>,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub role: Role,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub profile_visibility: Json<ProfileVisibility>,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub hidden: bool,
+    #[serde(default)]
+    #[sqlx(default)]
+    pub approve_followers: bool,
+    /// Incremented by the database on every change to the user.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub version: i64,
+    /// When the user was last changed, kept up to date by the database.
+    #[serde(default)]
+    #[sqlx(default)]
+    pub updated_at: NaiveDateTime,
+}
+
+/// What a user is allowed to do beyond managing their own account.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum Role {
+    #[default]
+    User,
+    /// Can see every profile in full, regardless of its privacy settings.
+    Admin,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
@@ -0,0 +1,145 @@
+//! JSON Patch (RFC 6902) documents for the profile of a user. Only the `test`,
+//! `replace` and `remove` operations are supported, and every path must name a
+//! single profile field, such as `/comment`. The operations are applied to the
+//! current profile one after another, so a `test` sees the changes of the
+//! operations before it.
+
+use super::{actions::UpdateError, dto::UpdateUserDto, User};
+use crate::i18n::Message;
+use serde::Deserialize;
+use serde_json::Value;
+use utoipa::ToSchema;
+
+/// An operation of a JSON Patch document.
+#[derive(Debug, Clone, Deserialize, ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum PatchOperation {
+    /// Fails the whole patch unless the field has the given value.
+    Test {
+        path: String,
+        value: Value,
+    },
+    Replace {
+        path: String,
+        value: Value,
+    },
+    /// Clears the field.
+    Remove {
+        path: String,
+    },
+}
+
+/// A change to the profile of a user in one of the supported formats.
+#[derive(Debug)]
+pub enum ProfileChange {
+    /// A JSON Merge Patch (RFC 7396), where `null` clears a field. Plain JSON
+    /// bodies are treated the same way.
+    Merge(UpdateUserDto),
+    /// A JSON Patch (RFC 6902).
+    Patch(Vec<PatchOperation>),
+}
+
+impl ProfileChange {
+    /// The change as an update of the given user. For a JSON Patch, this is
+    /// where the `test` operations are checked.
+    pub fn to_update(&self, user: &User) -> Result<UpdateUserDto, UpdateError> {
+        let operations = match self {
+            ProfileChange::Merge(update) => return Ok(update.clone()),
+            ProfileChange::Patch(operations) => operations,
+        };
+
+        let mut update = UpdateUserDto::default();
+        for operation in operations {
+            match operation {
+                PatchOperation::Test { path, value } => {
+                    let name = field_name(path)?;
+                    let current = match update.field(name) {
+                        Some(changed) => changed.clone(),
+                        None => current_value(user, name),
+                    };
+
+                    if current.as_ref() != value_as_str(path, value)?.as_ref() {
+                        return Err(UpdateError::TestFailed(path.to_owned()));
+                    }
+                }
+                PatchOperation::Replace { path, value } => {
+                    let value = value_as_str(path, value)?;
+                    *update.field(field_name(path)?) = Some(value);
+                }
+                PatchOperation::Remove { path } => {
+                    *update.field(field_name(path)?) = Some(None);
+                }
+            }
+        }
+
+        Ok(update)
+    }
+}
+
+impl UpdateUserDto {
+    /// The field of the update with the given name, which must be one of the
+    /// names returned by [field_name].
+    fn field(&mut self, name: &str) -> &mut Option<Option<String>> {
+        match name {
+            "nickname" => &mut self.nickname,
+            "comment" => &mut self.comment,
+            "display_name" => &mut self.display_name,
+            "bio" => &mut self.bio,
+            "avatar_url" => &mut self.avatar_url,
+            "locale" => &mut self.locale,
+            "timezone" => &mut self.timezone,
+            _ => &mut self.website,
+        }
+    }
+}
+
+/// The profile field which a JSON Pointer refers to.
+fn field_name(path: &str) -> Result<&'static str, UpdateError> {
+    let name = path
+        .strip_prefix('/')
+        .filter(|name| !name.contains('/'))
+        .map(|name| name.replace("~1", "/").replace("~0", "~"));
+
+    let field = [
+        "nickname",
+        "comment",
+        "display_name",
+        "bio",
+        "avatar_url",
+        "locale",
+        "timezone",
+        "website",
+    ]
+    .into_iter()
+    .find(|field| name.as_deref() == Some(field));
+
+    field.ok_or_else(|| UpdateError::Validation {
+        field: path.to_owned(),
+        reason: Message::new("validation-not-patchable"),
+    })
+}
+
+fn current_value(user: &User, name: &str) -> Option<String> {
+    match name {
+        "nickname" => user.nickname.clone(),
+        "comment" => user.comment.clone(),
+        "display_name" => user.display_name.clone(),
+        "bio" => user.bio.clone(),
+        "avatar_url" => user.avatar_url.clone(),
+        "locale" => user.locale.clone(),
+        "timezone" => user.timezone.clone(),
+        _ => user.website.clone(),
+    }
+}
+
+/// Profile fields are strings which may be cleared with `null`.
+fn value_as_str(path: &str, value: &Value) -> Result<Option<String>, UpdateError> {
+    match value {
+        Value::Null => Ok(None),
+        Value::String(value) => Ok(Some(value.clone())),
+        _ => Err(UpdateError::Validation {
+            field: path.to_owned(),
+            reason: Message::new("validation-string-or-null"),
+        }),
+    }
+}
@@ -0,0 +1,227 @@
+//! Controls over who may see a user's profile. Each field of the profile has its
+//! own [Visibility] and the account as a whole may be hidden from lookups. Users
+//! who have been blocked cannot find the user who blocked them. The owner of the
+//! account and admins can always see everything.
+
+use super::{restriction::RestrictionKind, Role, User};
+use crate::database::Database;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+use tokio::sync::OnceCell;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Who may see a field of a user's profile. The variants are ordered from the
+/// least to the most restrictive.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Visibility {
+    /// Anyone, including viewers who are not signed in.
+    #[default]
+    Public,
+    /// Any signed in user.
+    Authenticated,
+    /// Only the owner of the profile.
+    OnlyMe,
+}
+
+impl Visibility {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Visibility::Public => "public",
+            Visibility::Authenticated => "authenticated",
+            Visibility::OnlyMe => "only_me",
+        }
+    }
+}
+
+/// The fields of a profile which can be restricted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProfileField {
+    Nickname,
+    Comment,
+    DisplayName,
+    Bio,
+    /// Covers both the avatar url and the generated thumbnails.
+    Avatar,
+    Locale,
+    Timezone,
+    Website,
+}
+
+/// The visibility of each field of a profile. Fields which have not been set
+/// are [Visibility::Public].
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(transparent)]
+pub struct ProfileVisibility(BTreeMap<ProfileField, Visibility>);
+
+impl ProfileVisibility {
+    pub fn get(&self, field: ProfileField) -> Visibility {
+        self.0.get(&field).copied().unwrap_or_default()
+    }
+
+    /// Whether every field of the profile may be seen by anyone.
+    pub fn is_public(&self) -> bool {
+        self.0
+            .values()
+            .all(|visibility| *visibility == Visibility::Public)
+    }
+}
+
+impl From<BTreeMap<ProfileField, Visibility>> for ProfileVisibility {
+    fn from(value: BTreeMap<ProfileField, Visibility>) -> Self {
+        Self(value)
+    }
+}
+
+/// The restrictions between a signed in viewer and other users.
+#[derive(Debug, Default)]
+pub struct Restrictions {
+    /// The users who have blocked the viewer.
+    pub blocked_by: HashSet<Uuid>,
+    /// The users who the viewer has blocked.
+    pub blocking: HashSet<Uuid>,
+    /// The users who the viewer has muted.
+    pub muted: HashSet<Uuid>,
+}
+
+/// The identity of whoever is looking at a profile.
+#[derive(Debug, Clone)]
+pub enum Viewer {
+    Anonymous,
+    User {
+        id: Uuid,
+        role: Role,
+        /// Loaded by [Viewer::restrictions] the first time they are needed, and
+        /// shared between the clones of the viewer.
+        restrictions: Arc<OnceCell<Restrictions>>,
+    },
+}
+
+impl Viewer {
+    /// The given user as a viewer. Their restrictions are not loaded until a
+    /// lookup needs them, so requests which only concern the user themselves do
+    /// not query them.
+    pub fn new(user: &User) -> Self {
+        Viewer::User {
+            id: user.id,
+            role: user.role,
+            restrictions: Arc::default(),
+        }
+    }
+
+    /// The restrictions between the viewer and other users, loaded on first use.
+    /// Anonymous viewers have none.
+    pub async fn restrictions(&self, db: &Database) -> Result<Option<&Restrictions>, sqlx::Error> {
+        let Viewer::User {
+            id, restrictions, ..
+        } = self
+        else {
+            return Ok(None);
+        };
+
+        let restrictions = restrictions
+            .get_or_try_init(|| async {
+                let rows = sqlx::query_as::<_, (Uuid, Uuid, RestrictionKind)>(
+                    r#"
+                    SELECT owner_id, target_id, kind FROM restriction
+                        WHERE (target_id = $1 AND kind = $2) OR owner_id = $1
+                "#,
+                )
+                .bind(id)
+                .bind(RestrictionKind::Block)
+                .fetch_all(db.inner())
+                .await?;
+
+                let mut restrictions = Restrictions::default();
+                for (owner_id, target_id, kind) in rows {
+                    match kind {
+                        _ if target_id == *id => restrictions.blocked_by.insert(owner_id),
+                        RestrictionKind::Block => restrictions.blocking.insert(target_id),
+                        RestrictionKind::Mute => restrictions.muted.insert(target_id),
+                    };
+                }
+
+                Ok::<_, sqlx::Error>(restrictions)
+            })
+            .await?;
+
+        Ok(Some(restrictions))
+    }
+
+    /// The id of the user who is viewing, unless they are anonymous.
+    pub fn id(&self) -> Option<Uuid> {
+        match self {
+            Viewer::Anonymous => None,
+            Viewer::User { id, .. } => Some(*id),
+        }
+    }
+
+    /// Whether the viewer is an admin.
+    pub fn is_admin(&self) -> bool {
+        matches!(self, Viewer::User { role, .. } if *role == Role::Admin)
+    }
+
+    /// The most restrictive [Visibility] which this viewer may see on the profile
+    /// of any user other than themselves.
+    pub fn clearance_of_others(&self) -> Visibility {
+        match self {
+            Viewer::Anonymous => Visibility::Public,
+            Viewer::User { role, .. } if *role == Role::Admin => Visibility::OnlyMe,
+            Viewer::User { .. } => Visibility::Authenticated,
+        }
+    }
+
+    /// The most restrictive [Visibility] which this viewer may see on the profile
+    /// of the given user.
+    pub fn clearance(&self, owner: &User) -> Visibility {
+        match self {
+            Viewer::Anonymous => Visibility::Public,
+            Viewer::User { id, role, .. } if *id == owner.id || *role == Role::Admin => {
+                Visibility::OnlyMe
+            }
+            Viewer::User { .. } => Visibility::Authenticated,
+        }
+    }
+
+    /// Whether the given user can be found by this viewer at all. Every lookup of
+    /// another user goes through this check.
+    pub async fn can_find(&self, db: &Database, user: &User) -> Result<bool, sqlx::Error> {
+        if self.clearance(user) == Visibility::OnlyMe {
+            return Ok(true);
+        }
+
+        let blocked = match self.restrictions(db).await? {
+            Some(restrictions) => restrictions.blocked_by.contains(&user.id),
+            None => false,
+        };
+
+        Ok(!user.hidden && !blocked)
+    }
+
+    /// Keep only the users this viewer can find, as decided by [Viewer::can_find].
+    pub async fn findable<T>(
+        &self,
+        db: &Database,
+        items: Vec<T>,
+        user: impl Fn(&T) -> &User,
+    ) -> Result<Vec<T>, sqlx::Error> {
+        let mut found = Vec::with_capacity(items.len());
+        for item in items {
+            if self.can_find(db, user(&item)).await? {
+                found.push(item);
+            }
+        }
+
+        Ok(found)
+    }
+
+    /// Whether this viewer may see the given field of the user's profile.
+    pub fn can_see(&self, user: &User, field: ProfileField) -> bool {
+        user.profile_visibility.get(field) <= self.clearance(user)
+    }
+}
@@ -0,0 +1,215 @@
+//! Validated values for the fields of a user's profile. Each type can only be
+//! constructed from a value which is valid for its field.
+
+use super::actions::UpdateError;
+use crate::i18n::Message;
+use chrono_tz::Tz;
+use language_tags::LanguageTag;
+use url::Url;
+
+#[derive(Debug)]
+pub struct Comment(String);
+
+impl Comment {
+    pub const MAX_LENGTH: usize = 30;
+}
+
+impl TryFrom<String> for Comment {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.chars().count() > Self::MAX_LENGTH {
+            return Err(UpdateError::Validation {
+                field: "comment".into(),
+                reason: Message::new("validation-too-long").arg("max", Self::MAX_LENGTH),
+            });
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl AsRef<str> for Comment {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// The name a user would like to be shown as. Unlike the `user_id`, it does not
+/// need to be unique.
+#[derive(Debug)]
+pub struct DisplayName(String);
+
+impl DisplayName {
+    pub const MAX_LENGTH: usize = 50;
+}
+
+impl TryFrom<String> for DisplayName {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let value = value.trim().to_owned();
+
+        if value.is_empty() {
+            return Err(UpdateError::Validation {
+                field: "display_name".into(),
+                reason: Message::new("validation-blank"),
+            });
+        }
+
+        if value.chars().count() > Self::MAX_LENGTH {
+            return Err(UpdateError::Validation {
+                field: "display_name".into(),
+                reason: Message::new("validation-too-long").arg("max", Self::MAX_LENGTH),
+            });
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl AsRef<str> for DisplayName {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// A free form description which the user writes about themselves.
+#[derive(Debug)]
+pub struct Bio(String);
+
+impl Bio {
+    pub const MAX_LENGTH: usize = 300;
+}
+
+impl TryFrom<String> for Bio {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.chars().count() > Self::MAX_LENGTH {
+            return Err(UpdateError::Validation {
+                field: "bio".into(),
+                reason: Message::new("validation-too-long").arg("max", Self::MAX_LENGTH),
+            });
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl AsRef<str> for Bio {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// A link to an image which represents the user.
+#[derive(Debug)]
+pub struct AvatarUrl(String);
+
+impl TryFrom<String> for AvatarUrl {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(parse_web_url("avatar_url", &value)?))
+    }
+}
+
+impl AsRef<str> for AvatarUrl {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// A link to the user's personal website.
+#[derive(Debug)]
+pub struct Website(String);
+
+impl TryFrom<String> for Website {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Ok(Self(parse_web_url("website", &value)?))
+    }
+}
+
+impl AsRef<str> for Website {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// The user's preferred language as a BCP 47 language tag, for example `ja-JP`.
+#[derive(Debug)]
+pub struct Locale(String);
+
+impl TryFrom<String> for Locale {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let invalid = |error: String| UpdateError::Validation {
+            field: "locale".into(),
+            reason: Message::new("validation-language-tag").arg("error", error),
+        };
+
+        let tag = LanguageTag::parse(&value)
+            .map_err(|e| invalid(e.to_string()))?
+            .canonicalize()
+            .map_err(|e| invalid(e.to_string()))?;
+
+        Ok(Self(tag.to_string()))
+    }
+}
+
+impl AsRef<str> for Locale {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// The user's time zone as an IANA time zone name, for example `Asia/Tokyo`.
+#[derive(Debug)]
+pub struct Timezone(String);
+
+impl TryFrom<String> for Timezone {
+    type Error = UpdateError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let tz: Tz = value.parse().map_err(|_| UpdateError::Validation {
+            field: "timezone".into(),
+            reason: Message::new("validation-time-zone"),
+        })?;
+
+        Ok(Self(tz.name().to_owned()))
+    }
+}
+
+impl AsRef<str> for Timezone {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+const MAX_URL_LENGTH: usize = 2048;
+
+fn parse_web_url(field: &str, value: &str) -> Result<String, UpdateError> {
+    let invalid = |reason: Message| UpdateError::Validation {
+        field: field.into(),
+        reason,
+    };
+
+    if value.len() > MAX_URL_LENGTH {
+        return Err(invalid(
+            Message::new("validation-too-long").arg("max", MAX_URL_LENGTH),
+        ));
+    }
+
+    let url = Url::parse(value)
+        .map_err(|e| invalid(Message::new("validation-url").arg("error", e.to_string())))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(Message::new("validation-url-scheme")));
+    }
+
+    Ok(url.into())
+}
@@ -0,0 +1,52 @@
+//! Blocks and mutes which users place on each other. A block hides the blocker
+//! from the blocked user entirely and removes any follows between them, which is
+//! enforced by [Viewer::can_find](super::privacy::Viewer::can_find). A mute leaves
+//! the muted user out of the muter's search results and follow requests; the
+//! muted user is not affected.
+
+use super::User;
+use crate::pagination::{Field, FieldKind, Listing, Value};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum RestrictionKind {
+    Block,
+    Mute,
+}
+
+/// A restricted user along with the time at which they were restricted.
+#[derive(Debug, sqlx::FromRow)]
+pub struct RestrictionRow {
+    #[sqlx(flatten)]
+    pub user: User,
+    pub restricted_at: NaiveDateTime,
+}
+
+/// Restricted users are listed from the most recent restriction to the oldest.
+#[derive(Debug)]
+pub struct RestrictionListing;
+
+impl Listing for RestrictionListing {
+    type Row = RestrictionRow;
+
+    const SORTS: &'static [Field] = &[Field {
+        name: "restricted_at",
+        column: "restriction.created_at",
+        kind: FieldKind::Timestamp,
+    }];
+    const FILTERS: &'static [Field] = &[];
+    const DEFAULT_SORT: &'static str = "-restricted_at";
+    const TIE_BREAKER: &'static str = "user_.id";
+
+    fn sort_value(row: &RestrictionRow, _: &Field) -> Value {
+        Value::Timestamp(row.restricted_at)
+    }
+
+    fn tie_breaker(row: &RestrictionRow) -> Uuid {
+        row.user.id
+    }
+}
@@ -0,0 +1,93 @@
+//! Searching for users by `user_id` and nickname. Matches are ranked by trigram
+//! similarity, and prefix matches are ranked above everything else. Searches
+//! are limited per user, since each one scans the trigram indexes.
+
+use super::User;
+use crate::pagination::{Field, FieldKind, Listing, Value};
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use uuid::Uuid;
+
+/// The length of the window in which searches are counted.
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// A user who matched a search along with how well they matched.
+#[derive(Debug, sqlx::FromRow)]
+pub struct SearchRow {
+    #[sqlx(flatten)]
+    pub user: User,
+    pub rank: f32,
+}
+
+/// Search results are ordered from the best match to the worst. The search term
+/// is the `q` parameter.
+#[derive(Debug)]
+pub struct SearchListing;
+
+impl Listing for SearchListing {
+    type Row = SearchRow;
+
+    const SORTS: &'static [Field] = &[Field {
+        name: "rank",
+        column: "rank",
+        kind: FieldKind::Real,
+    }];
+    const FILTERS: &'static [Field] = &[];
+    const DEFAULT_SORT: &'static str = "-rank";
+    const TIE_BREAKER: &'static str = "id";
+    const PARAMETERS: &'static [&'static str] = &["q"];
+
+    fn sort_value(row: &SearchRow, _: &Field) -> Value {
+        Value::Real(row.rank)
+    }
+
+    fn tie_breaker(row: &SearchRow) -> Uuid {
+        row.user.id
+    }
+}
+
+/// Counts the searches of each user in fixed windows of one minute.
+#[derive(Debug)]
+pub struct SearchRateLimiter {
+    requests_per_minute: u32,
+    windows: Mutex<HashMap<Uuid, (Instant, u32)>>,
+}
+
+impl SearchRateLimiter {
+    pub fn new(requests_per_minute: u32) -> Self {
+        Self {
+            requests_per_minute,
+            windows: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a search by the given user. If they have used up their searches,
+    /// returns how long they have to wait until the next one.
+    pub fn check(&self, user_id: &Uuid) -> Result<(), Duration> {
+        let now = Instant::now();
+        let mut windows = self
+            .windows
+            .lock()
+            .expect("The rate limiter is not poisoned");
+        windows.retain(|_, (started, _)| now.duration_since(*started) < WINDOW);
+
+        let (started, count) = windows.entry(*user_id).or_insert((now, 0));
+        if *count >= self.requests_per_minute {
+            return Err(WINDOW - now.duration_since(*started));
+        }
+        *count += 1;
+
+        Ok(())
+    }
+}
+
+/// Escape the characters which have a special meaning in a `LIKE` pattern.
+pub fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,93 @@
+use super::subscription::authorize;
+use super::WebhookError;
+use crate::{
+    database::Database,
+    domain::{
+        user::privacy::Viewer,
+        webhook::{
+            dto::{DeliveryQuery, DeliveryResponse},
+            Delivery, DeliveryStatus,
+        },
+    },
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+/// How many deliveries are listed at most.
+const LIST_LIMIT: i64 = 100;
+
+/// Action for listing the most recent deliveries, optionally only those with a
+/// given status.
+#[tracing::instrument]
+pub async fn list_deliveries(
+    db: &Database,
+    viewer: &Viewer,
+    query: &DeliveryQuery,
+) -> Result<Vec<DeliveryResponse>, WebhookError> {
+    authorize(viewer)?;
+
+    let deliveries = sqlx::query_as::<_, Delivery>(
+        r#"
+        SELECT * FROM webhook_delivery
+            WHERE $1::varchar IS NULL OR status = $1
+            ORDER BY created_at DESC, id DESC
+            LIMIT $2;
+    "#,
+    )
+    .bind(query.status)
+    .bind(LIST_LIMIT)
+    .fetch_all(db.inner())
+    .await?;
+
+    Ok(deliveries.into_iter().map(Into::into).collect())
+}
+
+/// Action for sending a dead delivery again. It is attempted as often as a new
+/// delivery would be.
+#[tracing::instrument]
+pub async fn replay(
+    db: &Database,
+    viewer: &Viewer,
+    id: Uuid,
+) -> Result<DeliveryResponse, WebhookError> {
+    authorize(viewer)?;
+
+    let delivery = sqlx::query_as::<_, Delivery>(
+        r#"
+        UPDATE webhook_delivery
+            SET
+                status = $1,
+                attempts = 0,
+                next_attempt_at = $2,
+                last_error = NULL
+            WHERE id = $3 AND status = $4
+            RETURNING *;
+    "#,
+    )
+    .bind(DeliveryStatus::Pending)
+    .bind(Utc::now().naive_utc())
+    .bind(id)
+    .bind(DeliveryStatus::Dead)
+    .fetch_optional(db.inner())
+    .await?;
+
+    match delivery {
+        Some(delivery) => {
+            tracing::debug!("Replaying delivery {id}");
+            Ok(delivery.into())
+        }
+        None => {
+            let exists = sqlx::query_scalar::<_, bool>(
+                "SELECT EXISTS (SELECT 1 FROM webhook_delivery WHERE id = $1)",
+            )
+            .bind(id)
+            .fetch_one(db.inner())
+            .await?;
+
+            Err(match exists {
+                true => WebhookError::NotDead(id),
+                false => WebhookError::DeliveryNotFound(id),
+            })
+        }
+    }
+}
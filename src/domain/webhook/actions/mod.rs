@@ -0,0 +1,11 @@
+mod delivery;
+mod outbox;
+mod subscription;
+
+pub use delivery::list_deliveries;
+pub use delivery::replay;
+pub use outbox::enqueue;
+pub use subscription::create_subscription;
+pub use subscription::delete_subscription;
+pub use subscription::list_subscriptions;
+pub use subscription::WebhookError;
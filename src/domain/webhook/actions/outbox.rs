@@ -0,0 +1,36 @@
+use crate::domain::webhook::{DeliveryStatus, WebhookEvent};
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{types::Json, PgExecutor};
+
+/// Write a delivery of `event` to the outbox for each subscription to it. It is
+/// called within the transaction of the change which the event announces, so that
+/// deliveries are only sent for changes which were committed.
+#[tracing::instrument(skip(executor, data))]
+pub async fn enqueue<'e>(
+    executor: impl PgExecutor<'e>,
+    event: WebhookEvent,
+    data: &impl Serialize,
+) -> Result<(), sqlx::Error> {
+    let data = serde_json::to_value(data).expect("Event data can be serialized");
+
+    let enqueued = sqlx::query(
+        r#"
+        INSERT INTO webhook_delivery (
+            id, subscription_id, event_type, payload, status, next_attempt_at, created_at
+        )
+        SELECT gen_random_uuid(), id, $1, $2, $3, $4, $4
+            FROM webhook_subscription
+            WHERE $1 = ANY(event_types);
+    "#,
+    )
+    .bind(event)
+    .bind(Json(data))
+    .bind(DeliveryStatus::Pending)
+    .bind(Utc::now().naive_utc())
+    .execute(executor)
+    .await?;
+    tracing::debug!("Enqueued {} deliveries", enqueued.rows_affected());
+
+    Ok(())
+}
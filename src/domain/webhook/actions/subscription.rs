@@ -0,0 +1,173 @@
+use crate::{
+    database::Database,
+    domain::{
+        user::privacy::Viewer,
+        webhook::{
+            dto::{CreateSubscription, SubscriptionResponse},
+            Subscription, WebhookEvent,
+        },
+    },
+    i18n::Message,
+};
+use chrono::Utc;
+use secrecy::ExposeSecret;
+use thiserror::Error;
+use url::Url;
+use uuid::Uuid;
+
+const MAX_URL_LENGTH: usize = 2048;
+const MIN_SECRET_LENGTH: usize = 16;
+const MAX_SECRET_LENGTH: usize = 256;
+
+/// Action for subscribing a url to events of accounts. Only admins can manage
+/// webhooks.
+#[tracing::instrument]
+pub async fn create_subscription(
+    db: &Database,
+    viewer: &Viewer,
+    subscription: &CreateSubscription,
+) -> Result<SubscriptionResponse, WebhookError> {
+    authorize(viewer)?;
+    let url = parse_url(&subscription.url)?;
+    let secret = subscription.secret.expose_secret();
+    if !(MIN_SECRET_LENGTH..=MAX_SECRET_LENGTH).contains(&secret.chars().count()) {
+        return Err(WebhookError::Validation {
+            field: "secret".into(),
+            reason: Message::new("validation-length-range")
+                .arg("min", MIN_SECRET_LENGTH)
+                .arg("max", MAX_SECRET_LENGTH),
+        });
+    }
+    let event_types = parse_event_types(&subscription.event_types)?;
+
+    let subscription = sqlx::query_as::<_, Subscription>(
+        r#"
+        INSERT INTO webhook_subscription (id, url, secret, event_types, created_at)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING *;
+    "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(url)
+    .bind(secret)
+    .bind(event_types)
+    .bind(Utc::now().naive_utc())
+    .fetch_one(db.inner())
+    .await?;
+    tracing::debug!("Created subscription {}", subscription.id);
+
+    Ok(subscription.into())
+}
+
+/// Action for listing every subscription, from the oldest.
+#[tracing::instrument]
+pub async fn list_subscriptions(
+    db: &Database,
+    viewer: &Viewer,
+) -> Result<Vec<SubscriptionResponse>, WebhookError> {
+    authorize(viewer)?;
+
+    let subscriptions = sqlx::query_as::<_, Subscription>(
+        "SELECT * FROM webhook_subscription ORDER BY created_at, id",
+    )
+    .fetch_all(db.inner())
+    .await?;
+
+    Ok(subscriptions.into_iter().map(Into::into).collect())
+}
+
+/// Action for removing a subscription along with its deliveries.
+#[tracing::instrument]
+pub async fn delete_subscription(
+    db: &Database,
+    viewer: &Viewer,
+    id: Uuid,
+) -> Result<(), WebhookError> {
+    authorize(viewer)?;
+
+    let deleted = sqlx::query("DELETE FROM webhook_subscription WHERE id = $1")
+        .bind(id)
+        .execute(db.inner())
+        .await?;
+
+    if deleted.rows_affected() == 0 {
+        return Err(WebhookError::SubscriptionNotFound(id));
+    }
+
+    Ok(())
+}
+
+pub(super) fn authorize(viewer: &Viewer) -> Result<(), WebhookError> {
+    match viewer.is_admin() {
+        true => Ok(()),
+        false => Err(WebhookError::Forbidden),
+    }
+}
+
+fn parse_url(value: &str) -> Result<String, WebhookError> {
+    let invalid = |reason: Message| WebhookError::Validation {
+        field: "url".into(),
+        reason,
+    };
+
+    if value.len() > MAX_URL_LENGTH {
+        return Err(invalid(
+            Message::new("validation-too-long").arg("max", MAX_URL_LENGTH),
+        ));
+    }
+
+    let url = Url::parse(value)
+        .map_err(|e| invalid(Message::new("validation-url").arg("error", e.to_string())))?;
+
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid(Message::new("validation-url-scheme")));
+    }
+
+    Ok(url.into())
+}
+
+/// The distinct event types of a subscription, of which there must be at least one.
+fn parse_event_types(values: &[String]) -> Result<Vec<String>, WebhookError> {
+    let known: Vec<&str> = WebhookEvent::ALL
+        .iter()
+        .map(|event| event.as_str())
+        .collect();
+    let mut event_types: Vec<String> = Vec::new();
+
+    for value in values {
+        if !known.contains(&value.as_str()) {
+            return Err(WebhookError::Validation {
+                field: "event_types".into(),
+                reason: Message::new("validation-event-type").arg("types", known.join(", ")),
+            });
+        }
+        if !event_types.contains(value) {
+            event_types.push(value.clone());
+        }
+    }
+
+    if event_types.is_empty() {
+        return Err(WebhookError::Validation {
+            field: "event_types".into(),
+            reason: Message::new("validation-blank"),
+        });
+    }
+
+    Ok(event_types)
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("An error occurred with the database when managing webhooks: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Only admins can manage webhooks")]
+    Forbidden,
+    #[error("A subscription with the id '{0}' was not found")]
+    SubscriptionNotFound(Uuid),
+    #[error("A delivery with the id '{0}' was not found")]
+    DeliveryNotFound(Uuid),
+    #[error("The delivery '{0}' has not failed, so it cannot be replayed")]
+    NotDead(Uuid),
+    #[error("Invalid data was submitted: {field} {reason}")]
+    Validation { field: String, reason: Message },
+}
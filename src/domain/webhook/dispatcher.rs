@@ -0,0 +1,234 @@
+//! Sends the deliveries in the outbox. Due deliveries are claimed by moving their
+//! next attempt past a lease before they are sent, so any number of instances can
+//! dispatch without sending a delivery twice and without holding locks while
+//! waiting for receivers. A delivery whose instance stopped while sending it is
+//! attempted again once the lease has run out. Each request carries these
+//! headers:
+//!
+//! * `Webhook-Id`, the id of the delivery, which stays the same across retries
+//! * `Webhook-Event`, the type of the event
+//! * `Webhook-Timestamp`, when the request was signed, in seconds since the epoch
+//! * `Webhook-Signature`, `sha256=` followed by the [signature] of the request
+//!
+//! Deliveries which are not answered with a 2xx status are retried with an
+//! exponential backoff, and are dead after the last attempt.
+
+use super::{Delivery, DeliveryStatus};
+use crate::configuration::webhook::WebhookSettings;
+use crate::database::Database;
+use chrono::{NaiveDateTime, SubsecRound, Utc};
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use reqwest::header;
+use sha2::Sha256;
+use std::time::Duration;
+
+/// The largest power of two by which the backoff is multiplied.
+const MAX_BACKOFF_EXPONENT: i32 = 16;
+
+/// How much longer than the timeout of a request a delivery stays claimed.
+const LEASE_MARGIN: Duration = Duration::from_secs(30);
+
+/// A due delivery along with where to send it.
+#[derive(Debug, sqlx::FromRow)]
+struct DueDelivery {
+    #[sqlx(flatten)]
+    delivery: Delivery,
+    url: String,
+    secret: String,
+}
+
+/// Send due deliveries until the returned future is dropped.
+#[tracing::instrument(skip_all)]
+pub async fn dispatch(db: &Database, settings: &WebhookSettings) {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(settings.timeout_seconds))
+        .build()
+        .expect("The webhook client can be built");
+    let poll_interval = Duration::from_millis(settings.poll_interval_ms);
+
+    loop {
+        match deliver_due(db, &client, settings).await {
+            Ok(0) => actix_web::rt::time::sleep(poll_interval).await,
+            Ok(sent) => tracing::debug!("Sent {sent} webhook deliveries"),
+            Err(e) => {
+                tracing::error!("Failed to dispatch webhook deliveries: {e}");
+                actix_web::rt::time::sleep(poll_interval).await;
+            }
+        }
+    }
+}
+
+/// Send a batch of due deliveries, returning how many were attempted.
+async fn deliver_due(
+    db: &Database,
+    client: &reqwest::Client,
+    settings: &WebhookSettings,
+) -> Result<usize, sqlx::Error> {
+    // Postgres stores microseconds, so the lease is rounded to compare equal to
+    // the stored value when the result is recorded.
+    let now = Utc::now().naive_utc();
+    let lease =
+        (now + Duration::from_secs(settings.timeout_seconds) + LEASE_MARGIN).trunc_subsecs(6);
+    let due = sqlx::query_as::<_, DueDelivery>(
+        r#"
+        WITH claimed AS (
+            UPDATE webhook_delivery
+                SET next_attempt_at = $4
+                WHERE id IN (
+                    SELECT id FROM webhook_delivery
+                        WHERE status = $1 AND next_attempt_at <= $2
+                        ORDER BY next_attempt_at
+                        LIMIT $3
+                        FOR UPDATE SKIP LOCKED
+                )
+                RETURNING *
+        )
+        SELECT claimed.*, webhook_subscription.url, webhook_subscription.secret
+            FROM claimed
+            INNER JOIN webhook_subscription
+                ON webhook_subscription.id = claimed.subscription_id;
+    "#,
+    )
+    .bind(DeliveryStatus::Pending)
+    .bind(now)
+    .bind(settings.batch_size)
+    .bind(lease)
+    .fetch_all(db.inner())
+    .await?;
+
+    let recorded = join_all(
+        due.iter()
+            .map(|due| attempt(db, client, settings, due, lease)),
+    )
+    .await;
+    for result in recorded {
+        result?;
+    }
+
+    Ok(due.len())
+}
+
+/// Send a claimed delivery and record the result. The result is only recorded
+/// while the delivery is still claimed with `lease`, so that it does not
+/// overwrite the result of an instance which claimed it after the lease ran out.
+async fn attempt(
+    db: &Database,
+    client: &reqwest::Client,
+    settings: &WebhookSettings,
+    due: &DueDelivery,
+    lease: NaiveDateTime,
+) -> Result<(), sqlx::Error> {
+    let DueDelivery {
+        delivery,
+        url,
+        secret,
+    } = due;
+    let attempts = delivery.attempts + 1;
+    let result = send(client, delivery, url, secret).await;
+    let now = Utc::now().naive_utc();
+
+    let (status, next_attempt_at, last_error, delivered_at) = match result {
+        Ok(()) => (DeliveryStatus::Delivered, now, None, Some(now)),
+        Err(e) if attempts >= settings.max_attempts => {
+            tracing::warn!("Webhook delivery {} is dead: {e}", delivery.id);
+            (DeliveryStatus::Dead, now, Some(e), None)
+        }
+        Err(e) => {
+            tracing::info!("Webhook delivery {} failed: {e}", delivery.id);
+            let next_attempt_at = now + backoff(settings, attempts);
+            (DeliveryStatus::Pending, next_attempt_at, Some(e), None)
+        }
+    };
+
+    let updated = sqlx::query(
+        r#"
+        UPDATE webhook_delivery
+            SET
+                status = $1,
+                attempts = $2,
+                next_attempt_at = $3,
+                last_error = $4,
+                delivered_at = $5
+            WHERE id = $6 AND status = $7 AND next_attempt_at = $8;
+    "#,
+    )
+    .bind(status)
+    .bind(attempts)
+    .bind(next_attempt_at)
+    .bind(last_error)
+    .bind(delivered_at)
+    .bind(delivery.id)
+    .bind(DeliveryStatus::Pending)
+    .bind(lease)
+    .execute(db.inner())
+    .await?;
+
+    if updated.rows_affected() == 0 {
+        tracing::warn!(
+            "Webhook delivery {} was claimed again before it was recorded",
+            delivery.id
+        );
+    }
+
+    Ok(())
+}
+
+async fn send(
+    client: &reqwest::Client,
+    delivery: &Delivery,
+    url: &str,
+    secret: &str,
+) -> Result<(), String> {
+    let body = serde_json::to_vec(&serde_json::json!({
+        "id": delivery.id,
+        "type": delivery.event_type,
+        "created_at": delivery.created_at,
+        "data": delivery.payload.0,
+    }))
+    .expect("Webhook bodies can be serialized");
+    let timestamp = Utc::now().timestamp();
+
+    let response = client
+        .post(url)
+        .header(header::CONTENT_TYPE, "application/json")
+        .header("Webhook-Id", delivery.id.to_string())
+        .header("Webhook-Event", delivery.event_type.as_str())
+        .header("Webhook-Timestamp", timestamp)
+        .header(
+            "Webhook-Signature",
+            format!("sha256={}", signature(secret, timestamp, &body)),
+        )
+        .body(body)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    match response.status().is_success() {
+        true => Ok(()),
+        false => Err(format!("The receiver answered {}", response.status())),
+    }
+}
+
+/// The signature of a request: the HMAC-SHA256 of the timestamp, a `.` and the
+/// body, keyed with the secret of the subscription, in lowercase hex. Receivers
+/// compute it the same way and should reject requests with old timestamps.
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(body);
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// How long to wait before attempting a delivery again which failed `attempts`
+/// times.
+fn backoff(settings: &WebhookSettings, attempts: i32) -> chrono::Duration {
+    let factor = 1_i64 << (attempts - 1).clamp(0, MAX_BACKOFF_EXPONENT);
+    chrono::Duration::seconds(settings.retry_backoff_seconds as i64 * factor)
+}
@@ -0,0 +1,80 @@
+use super::{Delivery, DeliveryStatus, Subscription};
+use chrono::NaiveDateTime;
+use secrecy::Secret;
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+/// Admin submitted data for subscribing to events. Deliveries are signed with
+/// `secret`, which is never returned.
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CreateSubscription {
+    pub url: String,
+    #[schema(value_type = String, format = Password)]
+    pub secret: Secret<String>,
+    /// The events to deliver, such as `user.created`.
+    pub event_types: Vec<String>,
+}
+
+/// Response format when a subscription is requested
+#[derive(Debug, Serialize, ToSchema)]
+pub struct SubscriptionResponse {
+    pub id: Uuid,
+    pub url: String,
+    pub event_types: Vec<String>,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<Subscription> for SubscriptionResponse {
+    fn from(value: Subscription) -> Self {
+        Self {
+            id: value.id,
+            url: value.url,
+            event_types: value.event_types,
+            created_at: value.created_at,
+        }
+    }
+}
+
+/// Response format when a delivery is requested
+#[derive(Debug, Serialize, ToSchema)]
+pub struct DeliveryResponse {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: String,
+    /// The `data` of the event.
+    pub payload: serde_json::Value,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+impl From<Delivery> for DeliveryResponse {
+    fn from(value: Delivery) -> Self {
+        Self {
+            id: value.id,
+            subscription_id: value.subscription_id,
+            event_type: value.event_type.as_str().to_owned(),
+            payload: value.payload.0,
+            status: value.status,
+            attempts: value.attempts,
+            next_attempt_at: value.next_attempt_at,
+            last_error: value.last_error,
+            created_at: value.created_at,
+            delivered_at: value.delivered_at,
+        }
+    }
+}
+
+/// Query parameters for listing deliveries.
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DeliveryQuery {
+    /// Only list deliveries with this status.
+    pub status: Option<DeliveryStatus>,
+}
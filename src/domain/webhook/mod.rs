@@ -0,0 +1,78 @@
+//! Webhooks which tell downstream systems about changes to accounts. Domain
+//! actions [enqueue](actions::enqueue) a delivery for every subscription to an
+//! event in the transaction of the change, and the [dispatcher] sends them once
+//! they committed.
+
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+pub mod actions;
+pub mod dispatcher;
+pub mod dto;
+
+/// The events which can be subscribed to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[sqlx(type_name = "varchar")]
+pub enum WebhookEvent {
+    #[serde(rename = "user.created")]
+    #[sqlx(rename = "user.created")]
+    UserCreated,
+    #[serde(rename = "user.updated")]
+    #[sqlx(rename = "user.updated")]
+    UserUpdated,
+    #[serde(rename = "user.deleted")]
+    #[sqlx(rename = "user.deleted")]
+    UserDeleted,
+}
+
+impl WebhookEvent {
+    pub const ALL: [Self; 3] = [Self::UserCreated, Self::UserUpdated, Self::UserDeleted];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::UserCreated => "user.created",
+            Self::UserUpdated => "user.updated",
+            Self::UserDeleted => "user.deleted",
+        }
+    }
+}
+
+/// Where a delivery is on its way to the receiver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type, ToSchema)]
+#[serde(rename_all = "snake_case")]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+pub enum DeliveryStatus {
+    /// Waiting to be sent, either for the first time or again after a failure.
+    Pending,
+    Delivered,
+    /// Failed on every attempt, and is only sent again when it is replayed.
+    Dead,
+}
+
+/// Represents a subscription as stored in the database.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Subscription {
+    pub id: Uuid,
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    pub created_at: NaiveDateTime,
+}
+
+/// Represents a delivery of an event to a subscription as stored in the database.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct Delivery {
+    pub id: Uuid,
+    pub subscription_id: Uuid,
+    pub event_type: WebhookEvent,
+    pub payload: Json<serde_json::Value>,
+    pub status: DeliveryStatus,
+    pub attempts: i32,
+    pub next_attempt_at: NaiveDateTime,
+    pub last_error: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub delivered_at: Option<NaiveDateTime>,
+}
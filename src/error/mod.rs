@@ -1,21 +1,162 @@
 //! Contains error types that are used in multiple modules throughout the application.
+//!
+//! Every error is answered with an `application/problem+json` document as described by
+//! [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457). Each error type converts itself
+//! into an [ErrorResponse] carrying a stable `code`, which clients should match on
+//! instead of the `title` and `detail`. Those are rendered from the messages of the
+//! [catalog](crate::i18n) in the language of the client.
 
-use serde::Serialize;
+use crate::i18n::Message;
+use actix_web::{
+    error::JsonPayloadError, http::StatusCode, HttpResponse, HttpResponseBuilder, ResponseError,
+};
+use serde::{Deserialize, Serialize};
+use unic_langid::LanguageIdentifier;
+use utoipa::ToSchema;
 
-/// A standard error response format for consistent error formatting throughout
-/// the application. It defaults to an internal server error.
-#[derive(Debug, Serialize)]
+/// The media type of problem documents.
+pub const PROBLEM_JSON: &str = "application/problem+json";
+
+/// A problem document, the standard error response format of the application. It
+/// defaults to an internal server error.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ErrorResponse {
+    /// A URI reference identifying the type of problem. It is relative to the API
+    /// and derived from `code`.
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    /// A short summary of the type of problem, which is the same for every
+    /// occurrence of it.
+    pub title: String,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// An explanation specific to this occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    /// The path of the request which caused the problem.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub cause: Option<String>,
-    pub message: String,
+    pub instance: Option<String>,
+    /// A stable, machine-readable identifier of the type of problem.
+    pub code: String,
+    /// The fields of the submission which are invalid.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<FieldError>,
+    #[serde(skip)]
+    messages: Messages,
+}
+
+/// A field of a submission and what is wrong with it.
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldError {
+    pub field: String,
+    pub detail: String,
+}
+
+/// The messages which the text of a problem is rendered from, so that it can be
+/// rendered again in the language of the client.
+#[derive(Debug, Clone, Default)]
+struct Messages {
+    title: Option<Message>,
+    detail: Option<Message>,
+    errors: Vec<Message>,
 }
 
 impl Default for ErrorResponse {
     fn default() -> Self {
+        Self::new("internal_error")
+    }
+}
+
+impl ErrorResponse {
+    /// A problem of the type identified by `code`, which is also the id of its
+    /// title in the catalog. Its status is filled in when the response is built.
+    pub fn new(code: &'static str) -> Self {
+        let title = Message::new(code);
+
         Self {
-            cause: None,
-            message: "An internal server error occurred".into(),
+            problem_type: format!("/problems/{code}"),
+            title: title.to_string(),
+            status: StatusCode::INTERNAL_SERVER_ERROR.as_u16(),
+            detail: None,
+            instance: None,
+            code: code.into(),
+            errors: Vec::new(),
+            messages: Messages {
+                title: Some(title),
+                ..Default::default()
+            },
+        }
+    }
+
+    pub fn with_detail(mut self, detail: impl Into<Message>) -> Self {
+        let detail = detail.into();
+        self.detail = Some(detail.to_string());
+        self.messages.detail = Some(detail);
+        self
+    }
+
+    /// Add an invalid field. The detail of the problem names it too, for clients
+    /// which do not look at the individual fields.
+    pub fn with_field(mut self, field: &str, reason: &Message) -> Self {
+        self.errors.push(FieldError {
+            field: field.into(),
+            detail: reason.to_string(),
+        });
+        self.messages.errors.push(reason.clone());
+
+        self.with_detail(
+            Message::new("detail-invalid-field")
+                .arg("field", field)
+                .arg("reason", reason.clone()),
+        )
+    }
+
+    /// Render the text of the problem in `language`.
+    pub fn localize(&mut self, language: &LanguageIdentifier) {
+        if let Some(title) = &self.messages.title {
+            self.title = title.localize(language);
+        }
+        if let Some(detail) = &self.messages.detail {
+            self.detail = Some(detail.localize(language));
+        }
+        for (error, reason) in self.errors.iter_mut().zip(&self.messages.errors) {
+            error.detail = reason.localize(language);
+        }
+    }
+
+    /// Build the response carrying the problem, taking the status and any headers
+    /// from `builder`. The problem is also stored in the extensions of the response
+    /// so that the [instance](crate::middleware::problem) can be filled in and the
+    /// text localized later.
+    pub fn into_response(mut self, mut builder: HttpResponseBuilder) -> HttpResponse {
+        let response = builder.content_type(PROBLEM_JSON).finish();
+        self.status = response.status().as_u16();
+
+        let body = serde_json::to_string(&self).unwrap_or_default();
+        let mut response = response.set_body(body).map_into_boxed_body();
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+/// Respond to an error with the problem it converts into.
+pub fn respond<E>(error: &E) -> HttpResponse
+where
+    E: ResponseError,
+    for<'a> ErrorResponse: From<&'a E>,
+{
+    ErrorResponse::from(error).into_response(HttpResponse::build(error.status_code()))
+}
+
+impl From<&JsonPayloadError> for ErrorResponse {
+    fn from(value: &JsonPayloadError) -> Self {
+        match value {
+            JsonPayloadError::OverflowKnownLength { .. } | JsonPayloadError::Overflow { .. } => {
+                Self::new("payload_too_large")
+            }
+            JsonPayloadError::ContentType => Self::new("unsupported_media_type"),
+            _ => Self::new("invalid_body"),
         }
+        .with_detail(value.to_string())
     }
 }
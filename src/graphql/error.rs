@@ -0,0 +1,27 @@
+//! Errors of the GraphQL interface. Every error which is answered over HTTP with a
+//! problem document is reported in the `errors` of the GraphQL response instead.
+//! The detail of the problem becomes the message of the error, in the language of
+//! the client, and its code and status are added to the `extensions` of the error.
+
+use crate::error::ErrorResponse;
+use actix_web::ResponseError;
+use async_graphql::ErrorExtensions;
+use unic_langid::LanguageIdentifier;
+
+/// The GraphQL error of `error`, with its text in `language`.
+pub fn error<E>(error: &E, language: &LanguageIdentifier) -> async_graphql::Error
+where
+    E: ResponseError,
+    for<'a> ErrorResponse: From<&'a E>,
+{
+    let mut problem = ErrorResponse::from(error);
+    problem.localize(language);
+    let status = error.status_code().as_u16();
+
+    let message = problem.detail.unwrap_or(problem.title);
+    let code = problem.code;
+    async_graphql::Error::new(message).extend_with(|_, extensions| {
+        extensions.set("code", code.as_str());
+        extensions.set("status", status);
+    })
+}
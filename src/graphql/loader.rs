@@ -0,0 +1,117 @@
+use crate::database::Database;
+use crate::domain::user::actions::{GetOneError, ListFollowsError};
+use crate::domain::user::follow::{FollowList, FollowListing};
+use crate::domain::user::{self, dto::GetUserResponse, privacy::Viewer};
+use crate::pagination::{ListQuery, Page};
+use async_graphql::dataloader::Loader;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+/// Loads the users named in a request by their `user_id`, as seen by the viewer who
+/// sent it. Users the viewer cannot find are left out.
+pub struct UserLoader {
+    db: Database,
+    viewer: Viewer,
+}
+
+impl UserLoader {
+    pub fn new(db: Database, viewer: Viewer) -> Self {
+        Self { db, viewer }
+    }
+}
+
+impl Loader<String> for UserLoader {
+    type Value = GetUserResponse;
+    type Error = Arc<GetOneError>;
+
+    async fn load(&self, keys: &[String]) -> Result<HashMap<String, Self::Value>, Self::Error> {
+        user::actions::get_many_by_str_ids(&self.db, keys, &self.viewer)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load users: {e}");
+                Arc::new(e)
+            })
+    }
+}
+
+/// A page of follows to load: the user, the side of the follow graph, and the
+/// query which was given for it. Keys are equal when their query strings are.
+#[derive(Debug, Clone)]
+pub struct FollowKey {
+    pub user_id: String,
+    pub list: FollowList,
+    pub query_string: String,
+    pub query: Arc<ListQuery<FollowListing>>,
+}
+
+impl PartialEq for FollowKey {
+    fn eq(&self, other: &Self) -> bool {
+        (&self.user_id, self.list, &self.query_string)
+            == (&other.user_id, other.list, &other.query_string)
+    }
+}
+
+impl Eq for FollowKey {}
+
+impl Hash for FollowKey {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        (&self.user_id, self.list, &self.query_string).hash(state);
+    }
+}
+
+/// Loads the pages of follows asked for in a request, with one query for every
+/// users whose pages were asked for with the same query. Users the viewer cannot
+/// find are left out.
+pub struct FollowLoader {
+    db: Database,
+    viewer: Viewer,
+}
+
+impl FollowLoader {
+    pub fn new(db: Database, viewer: Viewer) -> Self {
+        Self { db, viewer }
+    }
+}
+
+impl Loader<FollowKey> for FollowLoader {
+    type Value = Page<GetUserResponse>;
+    type Error = Arc<ListFollowsError>;
+
+    async fn load(
+        &self,
+        keys: &[FollowKey],
+    ) -> Result<HashMap<FollowKey, Self::Value>, Self::Error> {
+        let mut batches: HashMap<(FollowList, &str), Vec<&FollowKey>> = HashMap::new();
+        for key in keys {
+            batches
+                .entry((key.list, &key.query_string))
+                .or_default()
+                .push(key);
+        }
+
+        let mut pages = HashMap::with_capacity(keys.len());
+        for ((list, _), keys) in batches {
+            let user_ids: Vec<String> = keys.iter().map(|key| key.user_id.clone()).collect();
+            let mut found = user::actions::list_many_follows(
+                &self.db,
+                &self.viewer,
+                &user_ids,
+                list,
+                &keys[0].query,
+            )
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to load follows: {e}");
+                Arc::new(e)
+            })?;
+            for key in keys {
+                if let Some(page) = found.remove(&key.user_id) {
+                    pages.insert(key.clone(), page);
+                }
+            }
+        }
+
+        Ok(pages)
+    }
+}
@@ -0,0 +1,180 @@
+//! The GraphQL interface, served at `/graphql` next to the REST API so that a
+//! client can read a user and the users around them in one round trip. Queries
+//! are authenticated like the private routes and every field is resolved with the
+//! same domain actions. Users are read through a [DataLoader] which is created for
+//! each request, so that the users named anywhere in a query are fetched together,
+//! and so are the pages of follows asked for with the same arguments. Queries are
+//! limited in depth and complexity, so that one request cannot walk the whole
+//! follow graph.
+//! A GraphiQL page to explore the schema is served at the same path.
+
+mod error;
+mod loader;
+mod user;
+
+use crate::configuration::user::UserSettings;
+use crate::database::Database;
+use crate::domain::user::actions::{GetOneError, UpdateError, UserIdType};
+use crate::domain::user::{self as domain, cache::UserCache, handle, patch::ProfileChange};
+use crate::domain::user::{privacy::Viewer, BasicId};
+use crate::middleware::{auth::process_basic, problem};
+use crate::negotiation::{self, Payload};
+use crate::pagination::CursorSigner;
+use actix_web::{web, HttpRequest, HttpResponse};
+use actix_web_httpauth::middleware::HttpAuthentication;
+use async_graphql::dataloader::DataLoader;
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{Context, EmptySubscription, Object, Result, Schema};
+use error::error;
+use loader::{FollowLoader, UserLoader};
+use std::sync::Arc;
+use unic_langid::LanguageIdentifier;
+use user::{ProfileInput, User};
+
+pub type ApiSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// The path at which the interface is served.
+const PATH: &str = "/graphql";
+
+/// How deeply fields may be nested in a query.
+const MAX_DEPTH: usize = 10;
+/// How many fields a query may resolve, where a page of follows counts each of
+/// its fields once for every user it may hold.
+const MAX_COMPLEXITY: usize = 1000;
+
+/// The schema, which signs the cursors of its pages with `signer` and updates
+/// users as `user_settings` require.
+pub fn schema(signer: CursorSigner, user_settings: UserSettings) -> ApiSchema {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .limit_depth(MAX_DEPTH)
+        .limit_complexity(MAX_COMPLEXITY)
+        .data(signer)
+        .data(user_settings)
+        .finish()
+}
+
+pub fn services(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource(PATH).route(web::get().to(graphiql)).route(
+            web::post()
+                .to(execute)
+                .wrap(HttpAuthentication::with_fn(process_basic)),
+        ),
+    );
+}
+
+/// What the resolvers of a request know about it.
+struct State {
+    db: Database,
+    cache: Arc<UserCache>,
+    user_id: BasicId,
+    language: &'static LanguageIdentifier,
+}
+
+#[tracing::instrument(skip_all)]
+async fn execute(
+    req: HttpRequest,
+    schema: web::Data<ApiSchema>,
+    db: web::Data<Database>,
+    cache: web::Data<UserCache>,
+    user_id: web::ReqData<BasicId>,
+    viewer: web::ReqData<Viewer>,
+    request: Payload<async_graphql::Request>,
+) -> HttpResponse {
+    let db = db.get_ref().clone();
+    let viewer = viewer.into_inner();
+    let users = UserLoader::new(db.clone(), viewer.clone());
+    let follows = FollowLoader::new(db.clone(), viewer);
+    let state = State {
+        db,
+        cache: cache.into_inner(),
+        user_id: user_id.into_inner(),
+        language: problem::language(&req).await,
+    };
+    let request = request
+        .into_inner()
+        .data(DataLoader::new(users, tokio::spawn))
+        .data(DataLoader::new(follows, tokio::spawn))
+        .data(state);
+
+    let response = schema.execute(request).await;
+    negotiation::respond(HttpResponse::Ok(), &response)
+}
+
+async fn graphiql() -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type("text/html; charset=utf-8")
+        .body(GraphiQLSource::build().endpoint(PATH).finish())
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// The signed in user.
+    async fn me(&self, ctx: &Context<'_>) -> Result<User> {
+        let state = ctx.data_unchecked::<State>();
+        let user_id = state.user_id.as_str();
+
+        user::load(ctx, user_id).await?.ok_or_else(|| {
+            let e = GetOneError::NotFound(UserIdType::Str(user_id.to_owned()));
+            error(&e, state.language)
+        })
+    }
+
+    /// The user with this `userId`, or null if the viewer cannot find them.
+    async fn user(&self, ctx: &Context<'_>, user_id: String) -> Result<Option<User>> {
+        user::load(ctx, &user_id).await
+    }
+}
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    /// Change the profile of the signed in user. Fields which are left out keep
+    /// their value and fields which are null are cleared. When `expectedVersion` is
+    /// given, the profile is only changed if it is still at that version. It must be
+    /// given when the server requires `If-Match` for updates.
+    async fn update_profile(
+        &self,
+        ctx: &Context<'_>,
+        user_id: String,
+        profile: ProfileInput,
+        expected_version: Option<i64>,
+    ) -> Result<User> {
+        let state = ctx.data_unchecked::<State>();
+
+        if !handle::matches(state.user_id.as_str(), &user_id) {
+            let e = UpdateError::Forbidden {
+                requester: state.user_id.as_str().to_owned(),
+                requested: user_id,
+            };
+            return Err(error(&e, state.language));
+        }
+
+        let expected =
+            expected_version.map(|version| domain::actions::ExpectedVersion::OneOf(vec![version]));
+        if expected.is_none() && ctx.data_unchecked::<UserSettings>().require_if_match {
+            return Err(error(&UpdateError::PreconditionRequired, state.language));
+        }
+        let change = ProfileChange::Merge(profile.into());
+
+        let mut user = domain::actions::update_user(
+            &state.db,
+            &state.cache,
+            &user_id,
+            &change,
+            expected.as_ref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Request failure: {e}");
+            error(&e, state.language)
+        })?;
+        // The REST API sends the user_id in the path rather than in the body.
+        user.user_id = Some(state.user_id.as_str().to_owned());
+
+        Ok(User::from(user))
+    }
+}
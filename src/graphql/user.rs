@@ -0,0 +1,245 @@
+use super::loader::{FollowKey, FollowLoader, UserLoader};
+use super::{error::error, State};
+use crate::domain::user::actions::ListFollowsError;
+use crate::domain::user::dto::{GetUserResponse, UpdateUserDto};
+use crate::domain::user::follow::{FollowList, FollowListing};
+use crate::pagination::{CursorSigner, ListQuery, Listing, Page};
+use async_graphql::dataloader::DataLoader;
+use async_graphql::{Context, InputObject, MaybeUndefined, Object, Result, SimpleObject};
+use std::sync::Arc;
+
+/// A user as seen by the viewer, without the fields they are not allowed to see.
+pub struct User(GetUserResponse);
+
+impl From<GetUserResponse> for User {
+    fn from(value: GetUserResponse) -> Self {
+        Self(value)
+    }
+}
+
+/// The user with this `user_id`, read through the loader of the request.
+pub async fn load(ctx: &Context<'_>, user_id: &str) -> Result<Option<User>> {
+    let state = ctx.data_unchecked::<State>();
+    let user = ctx
+        .data_unchecked::<DataLoader<UserLoader>>()
+        .load_one(user_id.to_owned())
+        .await
+        .map_err(|e| error(&*e, state.language))?;
+
+    Ok(user.map(User::from))
+}
+
+#[Object]
+impl User {
+    async fn user_id(&self) -> Option<&str> {
+        self.0.user_id.as_deref()
+    }
+
+    async fn nickname(&self) -> &str {
+        &self.0.nickname
+    }
+
+    async fn comment(&self) -> Option<&str> {
+        self.0.comment.as_deref()
+    }
+
+    async fn display_name(&self) -> Option<&str> {
+        self.0.display_name.as_deref()
+    }
+
+    async fn bio(&self) -> Option<&str> {
+        self.0.bio.as_deref()
+    }
+
+    async fn avatar_url(&self) -> Option<&str> {
+        self.0.avatar_url.as_deref()
+    }
+
+    /// Urls of the uploaded avatar, one for each edge length of the thumbnails.
+    async fn avatar_thumbnails(&self) -> Vec<Thumbnail> {
+        self.0
+            .avatar_thumbnails
+            .iter()
+            .flatten()
+            .map(|(size, url)| Thumbnail {
+                size: size.clone(),
+                url: url.clone(),
+            })
+            .collect()
+    }
+
+    async fn locale(&self) -> Option<&str> {
+        self.0.locale.as_deref()
+    }
+
+    async fn timezone(&self) -> Option<&str> {
+        self.0.timezone.as_deref()
+    }
+
+    async fn website(&self) -> Option<&str> {
+        self.0.website.as_deref()
+    }
+
+    /// The version of the user, to be sent as `expectedVersion` when changing it.
+    async fn version(&self) -> i64 {
+        self.0.version
+    }
+
+    async fn followers_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        Ok(self.counts(ctx).await?.0)
+    }
+
+    async fn following_count(&self, ctx: &Context<'_>) -> Result<i64> {
+        Ok(self.counts(ctx).await?.1)
+    }
+
+    /// A page of the followers of the user, from the most recent follow.
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn followers(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<FollowPage> {
+        self.follows(ctx, FollowList::Followers, cursor, limit)
+            .await
+    }
+
+    /// A page of the users the user follows, from the most recent follow.
+    #[graphql(complexity = "page_complexity(limit, child_complexity)")]
+    async fn following(
+        &self,
+        ctx: &Context<'_>,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<FollowPage> {
+        self.follows(ctx, FollowList::Following, cursor, limit)
+            .await
+    }
+}
+
+impl User {
+    /// The `user_id` of the user, which every user given to the schema comes with.
+    fn handle(&self) -> &str {
+        self.0.user_id.as_deref().unwrap_or_default()
+    }
+
+    /// The follow counts of the user. Users listed in a page of follows come
+    /// without them, so they are loaded together with those of the rest of the page.
+    async fn counts(&self, ctx: &Context<'_>) -> Result<(i64, i64)> {
+        if let (Some(followers), Some(following)) = (self.0.followers_count, self.0.following_count)
+        {
+            return Ok((followers, following));
+        }
+
+        let user = load(ctx, self.handle()).await?;
+        Ok(user.map_or((0, 0), |User(user)| {
+            (
+                user.followers_count.unwrap_or_default(),
+                user.following_count.unwrap_or_default(),
+            )
+        }))
+    }
+
+    async fn follows(
+        &self,
+        ctx: &Context<'_>,
+        list: FollowList,
+        cursor: Option<String>,
+        limit: Option<i64>,
+    ) -> Result<FollowPage> {
+        let state = ctx.data_unchecked::<State>();
+        // The arguments are read like the query string of the REST API, so that
+        // both validate them and sign cursors in the same way.
+        let query_string = {
+            let mut query = url::form_urlencoded::Serializer::new(String::new());
+            if let Some(cursor) = &cursor {
+                query.append_pair("cursor", cursor);
+            }
+            if let Some(limit) = limit {
+                query.append_pair("limit", &limit.to_string());
+            }
+            query.finish()
+        };
+        let signer = ctx.data_unchecked::<CursorSigner>().clone();
+        let query = ListQuery::<FollowListing>::parse(&query_string, signer)
+            .map_err(|e| error(&e, state.language))?;
+        let key = FollowKey {
+            user_id: self.handle().to_owned(),
+            list,
+            query_string,
+            query: Arc::new(query),
+        };
+
+        let page = ctx
+            .data_unchecked::<DataLoader<FollowLoader>>()
+            .load_one(key)
+            .await
+            .map_err(|e| error(&*e, state.language))?
+            .ok_or_else(|| {
+                let e = ListFollowsError::NotFound(self.handle().to_owned());
+                error(&e, state.language)
+            })?;
+
+        Ok(page.into())
+    }
+}
+
+/// The complexity of a page of follows, which grows with the number of users in
+/// it.
+fn page_complexity(limit: Option<i64>, child_complexity: usize) -> usize {
+    let limit = limit.unwrap_or(FollowListing::DEFAULT_LIMIT);
+    usize::try_from(limit).unwrap_or_default() * child_complexity
+}
+
+#[derive(SimpleObject)]
+pub struct Thumbnail {
+    /// The edge length of the thumbnail.
+    size: String,
+    url: String,
+}
+
+/// A page of users in a follow list. `nextCursor` is null on the last page.
+#[derive(SimpleObject)]
+pub struct FollowPage {
+    users: Vec<User>,
+    next_cursor: Option<String>,
+}
+
+impl From<Page<GetUserResponse>> for FollowPage {
+    fn from(value: Page<GetUserResponse>) -> Self {
+        Self {
+            users: value.items.into_iter().map(User::from).collect(),
+            next_cursor: value.next_cursor,
+        }
+    }
+}
+
+/// A change to the profile. Fields which are left out keep their value and fields
+/// which are null are cleared.
+#[derive(InputObject)]
+pub struct ProfileInput {
+    nickname: MaybeUndefined<String>,
+    comment: MaybeUndefined<String>,
+    display_name: MaybeUndefined<String>,
+    bio: MaybeUndefined<String>,
+    avatar_url: MaybeUndefined<String>,
+    locale: MaybeUndefined<String>,
+    timezone: MaybeUndefined<String>,
+    website: MaybeUndefined<String>,
+}
+
+impl From<ProfileInput> for UpdateUserDto {
+    fn from(value: ProfileInput) -> Self {
+        Self {
+            nickname: value.nickname.into(),
+            comment: value.comment.into(),
+            display_name: value.display_name.into(),
+            bio: value.bio.into(),
+            avatar_url: value.avatar_url.into(),
+            locale: value.locale.into(),
+            timezone: value.timezone.into(),
+            website: value.website.into(),
+        }
+    }
+}
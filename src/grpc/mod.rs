@@ -0,0 +1,34 @@
+//! The gRPC interface for internal services, defined by `proto/user.proto`. It
+//! runs on its own port next to the HTTP server and calls the same domain actions.
+//! Errors are mapped from the problems they are answered with over HTTP, see
+//! [status].
+
+pub mod status;
+mod user;
+
+pub use user::Users;
+
+use futures_util::future::BoxFuture;
+use std::net::TcpListener;
+use tokio_stream::wrappers::TcpListenerStream;
+use tonic::transport::Server;
+
+/// The code generated from the protocol buffers.
+pub mod proto {
+    tonic::include_proto!("track.user.v1");
+}
+
+/// Serve `users` on `listener` until the returned future is dropped.
+pub fn serve(
+    listener: TcpListener,
+    users: Users,
+) -> std::io::Result<BoxFuture<'static, Result<(), tonic::transport::Error>>> {
+    listener.set_nonblocking(true)?;
+    let listener = tokio::net::TcpListener::from_std(listener)?;
+
+    let server = Server::builder()
+        .add_service(proto::user_service_server::UserServiceServer::new(users))
+        .serve_with_incoming(TcpListenerStream::new(listener));
+
+    Ok(Box::pin(server))
+}
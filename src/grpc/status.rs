@@ -0,0 +1,59 @@
+//! Errors of the gRPC interface. Every error which is answered over HTTP with a
+//! problem document is answered over gRPC with the status closest to the HTTP
+//! status of the problem. The detail of the problem becomes the message of the
+//! status, in the language of the `accept-language` metadata, and its code is sent
+//! as `problem-code` metadata.
+
+use crate::error::ErrorResponse;
+use crate::i18n;
+use actix_web::http::StatusCode;
+use actix_web::ResponseError;
+use tonic::metadata::{MetadataMap, MetadataValue};
+use tonic::{Code, Status};
+use unic_langid::LanguageIdentifier;
+
+/// The status of `error`, with its text in `language`.
+pub fn status<E>(error: &E, language: &LanguageIdentifier) -> Status
+where
+    E: ResponseError,
+    for<'a> ErrorResponse: From<&'a E>,
+{
+    let mut problem = ErrorResponse::from(error);
+    problem.localize(language);
+
+    let message = problem.detail.unwrap_or(problem.title);
+    let mut status = Status::new(code(error.status_code()), message);
+    if let Ok(value) = MetadataValue::try_from(problem.code.as_str()) {
+        status.metadata_mut().insert("problem-code", value);
+    }
+    status
+}
+
+/// The language negotiated from the `accept-language` metadata of a request.
+pub fn language(metadata: &MetadataMap) -> &'static LanguageIdentifier {
+    let accepted = metadata
+        .get("accept-language")
+        .and_then(|value| value.to_str().ok())
+        .map(i18n::accepted_languages)
+        .unwrap_or_default();
+
+    i18n::negotiate(accepted)
+}
+
+/// The gRPC code closest to an HTTP status. Redirects only happen when a user
+/// changed their `user_id`, which gRPC clients see as the user not being found.
+fn code(status: StatusCode) -> Code {
+    match status {
+        StatusCode::BAD_REQUEST | StatusCode::UNSUPPORTED_MEDIA_TYPE => Code::InvalidArgument,
+        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::CONFLICT => Code::AlreadyExists,
+        StatusCode::PRECONDITION_FAILED | StatusCode::PRECONDITION_REQUIRED => {
+            Code::FailedPrecondition
+        }
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        status if status.is_redirection() => Code::NotFound,
+        _ => Code::Internal,
+    }
+}
@@ -0,0 +1,270 @@
+use super::proto::{self, user_service_server::UserService};
+use super::status::{language, status};
+use crate::auth::verify_jwt;
+use crate::configuration::{auth::AuthSettings, user::UserSettings};
+use crate::database::Database;
+use crate::domain::user::actions::{ExpectedVersion, UpdateError};
+use crate::domain::user::dto::{GetUserResponse, Signin, Signup, UpdateUserDto};
+use crate::domain::user::{self, cache::UserCache, handle, patch::ProfileChange, privacy::Viewer};
+use crate::domain::user::{BasicId, User};
+use crate::i18n::Message;
+use crate::middleware::auth::AuthError;
+use secrecy::Secret;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+
+/// The fields of the profile which can be named in `clear`.
+const CLEARABLE: [&str; 8] = [
+    "nickname",
+    "comment",
+    "display_name",
+    "bio",
+    "avatar_url",
+    "locale",
+    "timezone",
+    "website",
+];
+
+/// The implementation of `UserService`, holding the same state as the HTTP
+/// handlers.
+pub struct Users {
+    db: Database,
+    cache: Arc<UserCache>,
+    auth_settings: AuthSettings,
+    user_settings: UserSettings,
+}
+
+impl Users {
+    pub fn new(
+        db: Database,
+        cache: Arc<UserCache>,
+        auth_settings: AuthSettings,
+        user_settings: UserSettings,
+    ) -> Self {
+        Self {
+            db,
+            cache,
+            auth_settings,
+            user_settings,
+        }
+    }
+
+    /// The user who sent the JWT in the `authorization` metadata of `request`.
+    async fn authenticate<T>(&self, request: &Request<T>) -> Result<User, Status> {
+        let language = language(request.metadata());
+        let token = request
+            .metadata()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or_else(|| status(&AuthError::InvalidCredentials, language))?;
+
+        let claims = verify_jwt(token, &self.auth_settings.jwtsecret).map_err(|e| {
+            tracing::error!("Failed to decode auth token: {e}");
+            status(&AuthError::InvalidToken(e), language)
+        })?;
+        let id = uuid::Uuid::parse_str(&claims.sub)
+            .map_err(|_| status(&AuthError::InvalidCredentials, language))?;
+
+        // The user may have closed their account since the token was issued.
+        self.cache
+            .find_by_id(&self.db, &id)
+            .await
+            .map_err(|e| status(&AuthError::DatabaseError(e), language))?
+            .ok_or_else(|| status(&AuthError::InvalidCredentials, language))
+    }
+}
+
+#[tonic::async_trait]
+impl UserService for Users {
+    #[tracing::instrument(skip_all)]
+    async fn signup(
+        &self,
+        request: Request<proto::SignupRequest>,
+    ) -> Result<Response<proto::SignupResponse>, Status> {
+        let language = language(request.metadata());
+        let request = request.into_inner();
+        let signup = Signup {
+            user_id: Some(request.user_id).filter(|user_id| !user_id.is_empty()),
+            password: Some(request.password)
+                .filter(|password| !password.is_empty())
+                .map(Secret::new),
+        };
+
+        let user = user::actions::signup(&self.db, &self.user_settings, signup)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to persist user: {e}");
+                status(&e, language)
+            })?;
+        tracing::info!("Signup success: {user:?}");
+
+        Ok(Response::new(proto::SignupResponse {
+            user_id: user.user_id,
+            nickname: user.nickname,
+        }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn signin(
+        &self,
+        request: Request<proto::SigninRequest>,
+    ) -> Result<Response<proto::SigninResponse>, Status> {
+        let language = language(request.metadata());
+        let request = request.into_inner();
+        let signin = Signin {
+            user_id: request.user_id,
+            password: Secret::new(request.password),
+        };
+
+        let token = user::actions::signin(
+            &self.db,
+            &self.cache,
+            &signin,
+            &self.auth_settings.jwtsecret,
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Signin failed: {e}");
+            status(&e, language)
+        })?;
+
+        Ok(Response::new(proto::SigninResponse { token }))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn get_user(
+        &self,
+        request: Request<proto::GetUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let language = language(request.metadata());
+        let requester = self.authenticate(&request).await?;
+        let viewer = Viewer::new(&requester);
+        let user_id = request.into_inner().user_id;
+
+        let user = user::actions::get_one_by_str_id(&self.db, &self.cache, &user_id, &viewer)
+            .await
+            .map_err(|e| {
+                tracing::error!("Request failure: {e}");
+                status(&e, language)
+            })?;
+
+        Ok(Response::new(user.into()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn update_user(
+        &self,
+        request: Request<proto::UpdateUserRequest>,
+    ) -> Result<Response<proto::User>, Status> {
+        let language = language(request.metadata());
+        let requester = self.authenticate(&request).await?;
+        let request = request.into_inner();
+
+        if !handle::matches(&requester.user_id, &request.user_id) {
+            let error = UpdateError::Forbidden {
+                requester: requester.user_id,
+                requested: request.user_id,
+            };
+            return Err(status(&error, language));
+        }
+        if let Some(field) = request
+            .clear
+            .iter()
+            .find(|f| !CLEARABLE.contains(&f.as_str()))
+        {
+            let error = UpdateError::Validation {
+                field: field.clone(),
+                reason: Message::new("validation-not-patchable"),
+            };
+            return Err(status(&error, language));
+        }
+
+        let expected = request
+            .expected_version
+            .map(|version| ExpectedVersion::OneOf(vec![version]));
+        if expected.is_none() && self.user_settings.require_if_match {
+            return Err(status(&UpdateError::PreconditionRequired, language));
+        }
+        let change = ProfileChange::Merge(profile_change(&request));
+
+        let user = user::actions::update_user(
+            &self.db,
+            &self.cache,
+            &request.user_id,
+            &change,
+            expected.as_ref(),
+        )
+        .await
+        .map_err(|e| {
+            tracing::error!("Request failure: {e}");
+            status(&e, language)
+        })?;
+
+        Ok(Response::new(user.into()))
+    }
+
+    #[tracing::instrument(skip_all)]
+    async fn close_account(
+        &self,
+        request: Request<proto::CloseAccountRequest>,
+    ) -> Result<Response<proto::CloseAccountResponse>, Status> {
+        let language = language(request.metadata());
+        let requester = self.authenticate(&request).await?;
+        let requester_id = BasicId::from(requester.user_id);
+
+        user::actions::delete(&self.db, &self.cache, &requester_id)
+            .await
+            .map_err(|e| {
+                tracing::error!("Request failure: {e}");
+                status(&e, language)
+            })?;
+        tracing::info!("Deleted user {}", requester_id.as_str());
+
+        Ok(Response::new(proto::CloseAccountResponse {}))
+    }
+}
+
+/// The merge patch described by `request`. Fields named in `clear` take precedence
+/// over the values they were given.
+fn profile_change(request: &proto::UpdateUserRequest) -> UpdateUserDto {
+    let field = |name: &str, value: Option<String>| match request.clear.iter().any(|f| f == name) {
+        true => Some(None),
+        false => value.map(Some),
+    };
+
+    UpdateUserDto {
+        nickname: field("nickname", request.nickname.clone()),
+        comment: field("comment", request.comment.clone()),
+        display_name: field("display_name", request.display_name.clone()),
+        bio: field("bio", request.bio.clone()),
+        avatar_url: field("avatar_url", request.avatar_url.clone()),
+        locale: field("locale", request.locale.clone()),
+        timezone: field("timezone", request.timezone.clone()),
+        website: field("website", request.website.clone()),
+    }
+}
+
+impl From<GetUserResponse> for proto::User {
+    fn from(value: GetUserResponse) -> Self {
+        Self {
+            user_id: value.user_id,
+            nickname: value.nickname,
+            comment: value.comment,
+            display_name: value.display_name,
+            bio: value.bio,
+            avatar_url: value.avatar_url,
+            avatar_thumbnails: value
+                .avatar_thumbnails
+                .unwrap_or_default()
+                .into_iter()
+                .collect(),
+            locale: value.locale,
+            timezone: value.timezone,
+            website: value.website,
+            followers_count: value.followers_count,
+            following_count: value.following_count,
+            version: value.version,
+        }
+    }
+}
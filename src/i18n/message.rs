@@ -0,0 +1,116 @@
+use super::{default_language, CATALOG};
+use fluent_bundle::{FluentArgs, FluentValue};
+use std::fmt::{self, Display};
+use unic_langid::LanguageIdentifier;
+
+/// A message of the catalog along with the values to interpolate into it. It is
+/// only rendered into text once the language of the client is known, and its
+/// [Display] implementation renders it in the default language, which is what
+/// ends up in the logs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message(Kind);
+
+#[derive(Debug, Clone, PartialEq)]
+enum Kind {
+    Catalog {
+        id: &'static str,
+        args: Vec<(&'static str, Arg)>,
+    },
+    /// Text which is shown as it is in every language, such as the description
+    /// of an error raised by a library.
+    Text(String),
+}
+
+/// A value which is interpolated into a [Message].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Arg {
+    Text(String),
+    Number(i64),
+    /// A message which is rendered in the same language as the one it is part of.
+    Message(Message),
+}
+
+impl Message {
+    /// The message of the catalog with the given id.
+    pub fn new(id: &'static str) -> Self {
+        Self(Kind::Catalog {
+            id,
+            args: Vec::new(),
+        })
+    }
+
+    /// Text which is not translated.
+    pub fn text(text: impl Into<String>) -> Self {
+        Self(Kind::Text(text.into()))
+    }
+
+    /// Set the value of the variable `$name` of the message.
+    pub fn arg(mut self, name: &'static str, value: impl Into<Arg>) -> Self {
+        if let Kind::Catalog { args, .. } = &mut self.0 {
+            args.push((name, value.into()));
+        }
+        self
+    }
+
+    /// Render the message in `language`.
+    pub fn localize(&self, language: &LanguageIdentifier) -> String {
+        match &self.0 {
+            Kind::Catalog { id, args } => {
+                let mut fluent_args = FluentArgs::new();
+                for (name, value) in args {
+                    let value = match value {
+                        Arg::Text(text) => FluentValue::from(text.as_str()),
+                        Arg::Number(number) => FluentValue::from(*number),
+                        Arg::Message(message) => FluentValue::from(message.localize(language)),
+                    };
+                    fluent_args.set(*name, value);
+                }
+
+                CATALOG.format(language, id, &fluent_args)
+            }
+            Kind::Text(text) => text.clone(),
+        }
+    }
+}
+
+impl Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localize(default_language()))
+    }
+}
+
+impl From<String> for Message {
+    fn from(value: String) -> Self {
+        Self::text(value)
+    }
+}
+
+impl From<String> for Arg {
+    fn from(value: String) -> Self {
+        Self::Text(value)
+    }
+}
+
+impl From<&str> for Arg {
+    fn from(value: &str) -> Self {
+        Self::Text(value.to_owned())
+    }
+}
+
+impl From<usize> for Arg {
+    fn from(value: usize) -> Self {
+        Self::Number(value as i64)
+    }
+}
+
+impl From<i64> for Arg {
+    fn from(value: i64) -> Self {
+        Self::Number(value)
+    }
+}
+
+impl From<Message> for Arg {
+    fn from(value: Message) -> Self {
+        Self::Message(value)
+    }
+}
@@ -0,0 +1,102 @@
+//! Translations of the messages which are shown to clients. The messages live in
+//! [Fluent](https://projectfluent.org) catalogs under `locales/`, one per supported
+//! language, and are referred to by id through [Message]. English is the default
+//! and the fallback for messages missing from another catalog.
+
+mod message;
+
+pub use message::{Arg, Message};
+
+use crate::negotiation;
+use fluent_bundle::{concurrent::FluentBundle, FluentArgs, FluentResource};
+use fluent_langneg::{negotiate_languages, NegotiationStrategy};
+use once_cell::sync::Lazy;
+use unic_langid::LanguageIdentifier;
+
+/// The catalogs compiled into the binary, by language.
+const SOURCES: [(&str, &str); 2] = [
+    ("en", include_str!("../../locales/en/errors.ftl")),
+    ("ja", include_str!("../../locales/ja/errors.ftl")),
+];
+
+static CATALOG: Lazy<Catalog> = Lazy::new(Catalog::load);
+
+struct Catalog {
+    languages: Vec<LanguageIdentifier>,
+    bundles: Vec<FluentBundle<FluentResource>>,
+}
+
+impl Catalog {
+    fn load() -> Self {
+        let mut languages = Vec::new();
+        let mut bundles = Vec::new();
+
+        for (language, source) in SOURCES {
+            let language: LanguageIdentifier =
+                language.parse().expect("Catalog languages are valid");
+            let resource = FluentResource::try_new(source.to_owned())
+                .unwrap_or_else(|(_, e)| panic!("Catalog for {language} is invalid: {e:?}"));
+
+            let mut bundle = FluentBundle::new_concurrent(vec![language.clone()]);
+            // Clients display the messages as plain text, so the Unicode isolation
+            // marks around interpolated values would only get in the way.
+            bundle.set_use_isolating(false);
+            bundle
+                .add_resource(resource)
+                .unwrap_or_else(|e| panic!("Catalog for {language} is invalid: {e:?}"));
+
+            languages.push(language);
+            bundles.push(bundle);
+        }
+
+        Self { languages, bundles }
+    }
+
+    fn format(&self, language: &LanguageIdentifier, id: &str, args: &FluentArgs) -> String {
+        let preferred = self.languages.iter().position(|l| l == language);
+
+        // Fall back to the default language for messages which are not translated.
+        for bundle in preferred.into_iter().chain([0]).map(|i| &self.bundles[i]) {
+            let Some(pattern) = bundle.get_message(id).and_then(|m| m.value()) else {
+                continue;
+            };
+
+            let mut errors = Vec::new();
+            let text = bundle.format_pattern(pattern, Some(args), &mut errors);
+            if !errors.is_empty() {
+                tracing::warn!("Failed to format message {id} for {language}: {errors:?}");
+            }
+            return text.into_owned();
+        }
+
+        tracing::error!("Message {id} is missing from the catalog");
+        id.to_owned()
+    }
+}
+
+/// The language which is used when no supported language was asked for.
+pub fn default_language() -> &'static LanguageIdentifier {
+    &CATALOG.languages[0]
+}
+
+/// Pick the supported language which best matches `requested`, which holds
+/// language tags in order of preference. Tags which cannot be parsed are ignored.
+pub fn negotiate<'a>(requested: impl IntoIterator<Item = &'a str>) -> &'static LanguageIdentifier {
+    let requested: Vec<LanguageIdentifier> = requested
+        .into_iter()
+        .filter_map(|tag| tag.parse().ok())
+        .collect();
+
+    negotiate_languages(
+        &requested,
+        &CATALOG.languages,
+        Some(default_language()),
+        NegotiationStrategy::Lookup,
+    )[0]
+}
+
+/// The language tags of an `Accept-Language` header, most preferred first.
+/// Languages with a quality of zero are left out.
+pub fn accepted_languages(header: &str) -> Vec<&str> {
+    negotiation::preferences(header)
+}
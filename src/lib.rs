@@ -3,11 +3,18 @@
 
 pub mod app;
 pub mod auth;
+pub mod blob;
 pub mod configuration;
 pub mod database;
 pub mod domain;
 pub mod error;
+mod graphql;
+pub mod grpc;
+pub mod i18n;
 mod middleware;
+pub mod negotiation;
+pub mod openapi;
+pub mod pagination;
 mod routes;
 pub mod telemetry;
 
@@ -16,4 +23,6 @@ pub use actix_web;
 pub use actix_web_httpauth;
 pub use anyhow;
 pub use once_cell;
+pub use secrecy;
+pub use tonic;
 pub use uuid;
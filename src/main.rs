@@ -11,6 +11,9 @@ async fn main() -> anyhow::Result<()> {
     let app = Application::build(config, db).await?;
 
     tracing::info!("App is running on port {}", app.port());
+    if let Some(port) = app.grpc_port() {
+        tracing::info!("gRPC is served on port {port}");
+    }
     app.run_until_stopped().await?;
 
     Ok(())
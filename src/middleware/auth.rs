@@ -1,19 +1,18 @@
 //! Middleware for extracting a JWT from the Authentication header and validating it.
 
-use crate::auth::{verify_password, TokenClaims};
+use crate::auth::{verify_jwt, verify_password, TokenClaims};
 use crate::configuration::auth::AuthSettings;
 use crate::database::Database;
 use crate::domain;
-use crate::domain::user::User;
-use crate::error::ErrorResponse;
+use crate::domain::user::{cache::UserCache, privacy::Viewer, User};
+use crate::error::{self, ErrorResponse};
 use actix_web::dev::ServiceRequest;
 use actix_web::http::StatusCode;
 use actix_web::{web, HttpMessage};
 use actix_web::{HttpResponse, ResponseError};
 use actix_web_httpauth::extractors::basic::BasicAuth;
 use actix_web_httpauth::extractors::bearer::BearerAuth;
-use jsonwebtoken::{decode, DecodingKey, Validation};
-use secrecy::{ExposeSecret, Secret};
+use secrecy::Secret;
 use thiserror::Error;
 
 /// Accepts a [ServiceRequest] and [BearerAuth] and confirms the token is valid.
@@ -34,14 +33,7 @@ pub async fn validator(
 
     let token = creds.token();
 
-    // TODO: move decode and validate logic to the auth module
-    let claims = match decode::<TokenClaims>(
-        token,
-        &DecodingKey::from_secret(settings.jwtsecret.expose_secret().as_ref()),
-        &Validation::default(),
-    )
-    .map(|c| c.claims)
-    .map_err(|e| {
+    let claims = match verify_jwt(token, &settings.jwtsecret).map_err(|e| {
         tracing::error!("Failed to decode auth token: {e}");
         AuthError::InvalidToken(e)
     }) {
@@ -52,6 +44,7 @@ pub async fn validator(
     let user_id = uuid::Uuid::parse_str(claims.sub.as_str()).unwrap();
     req.extensions_mut()
         .insert::<uuid::Uuid>(user_id.to_owned());
+    req.extensions_mut().insert::<TokenClaims>(claims);
 
     Ok(req)
 }
@@ -80,10 +73,7 @@ impl ResponseError for AuthError {
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        error::respond(self)
     }
 }
 
@@ -93,18 +83,11 @@ where
 {
     fn from(value: &AuthError) -> Self {
         match value {
-            AuthError::InvalidToken(..) => Self {
-                cause: Some(value.to_string()),
-                message: "Authentication Failed".into(),
-            },
-            AuthError::InvalidCredentials => Self {
-                cause: None,
-                message: "Authentication Failed".into(),
-            },
-            _ => Self {
-                cause: None,
-                message: Self::default().message,
-            },
+            AuthError::InvalidToken(..) => {
+                Self::new("invalid_token").with_detail(value.to_string())
+            }
+            AuthError::InvalidCredentials => Self::new("invalid_credentials"),
+            _ => Self::default(),
         }
     }
 }
@@ -127,6 +110,14 @@ pub async fn process_basic(
     };
     tracing::debug!("Database found.");
 
+    let cache = match req.extract::<web::Data<UserCache>>().await {
+        Ok(cache) => cache,
+        Err(e) => {
+            tracing::error!("Failed to load the user cache: {e}");
+            return Err((e, req));
+        }
+    };
+
     tracing::debug!("Extracting credentials...");
     let credentials = match credentials {
         Some(credentials) => credentials,
@@ -134,53 +125,50 @@ pub async fn process_basic(
     };
     tracing::debug!("Success");
 
-    tracing::debug!("Looking up user data...");
-    let user = match sqlx::query_as::<_, User>(
-        r#"
-        SELECT * FROM user_ WHERE user_id = $1
-    "#,
-    )
-    .bind(credentials.user_id())
-    .fetch_optional(db.inner())
-    .await
-    .map_err(AuthError::DatabaseError)
-    {
+    let user = match authenticate(&db, &cache, &credentials).await {
         Ok(user) => user,
-        Err(e) => {
-            tracing::error!("Failed to retrieve user info: {e}");
-            return Err((e.into(), req));
-        }
+        Err(e) => return Err((e.into(), req)),
     };
+
+    req.extensions_mut().insert::<Viewer>(Viewer::new(&user));
+    req.extensions_mut()
+        .insert::<domain::user::BasicId>(user.user_id.into());
+
+    Ok(req)
+}
+
+/// The user whose `user_id` and password are `credentials`.
+#[tracing::instrument(skip_all)]
+pub async fn authenticate(
+    db: &Database,
+    cache: &UserCache,
+    credentials: &BasicAuth,
+) -> Result<User, AuthError> {
+    tracing::debug!("Looking up user data...");
+    let user = cache.find(db, credentials.user_id()).await.map_err(|e| {
+        tracing::error!("Failed to retrieve user info: {e}");
+        AuthError::DatabaseError(e)
+    })?;
     tracing::debug!("Request to db succeeded");
 
     tracing::debug!("Checking if user was found...");
-    let user = match user.ok_or(AuthError::InvalidCredentials) {
-        Ok(user) => user,
-        Err(e) => {
-            tracing::error!("User was not found: {e}");
-            return Err((e.into(), req));
-        }
-    };
+    let user = user.ok_or_else(|| {
+        tracing::error!("User was not found");
+        AuthError::InvalidCredentials
+    })?;
     tracing::debug!("User found.");
 
     tracing::debug!("Extracting password from credentials...");
-    let submitted_password = match credentials.password() {
-        Some(credentials) => credentials,
-        None => {
-            tracing::error!("Password was not found");
-            return Err((AuthError::InvalidCredentials.into(), req));
-        }
-    }
-    .into();
+    let submitted_password = credentials.password().ok_or_else(|| {
+        tracing::error!("Password was not found");
+        AuthError::InvalidCredentials
+    })?;
     tracing::debug!("Password found");
 
-    if let Err(e) = verify_password(&user.password, &Secret::new(submitted_password)) {
+    if let Err(e) = verify_password(&user.password, &Secret::new(submitted_password.into())) {
         tracing::error!("Password verification failed: {e}");
-        return Err((AuthError::InvalidCredentials.into(), req));
+        return Err(AuthError::InvalidCredentials);
     }
 
-    req.extensions_mut()
-        .insert::<domain::user::BasicId>(credentials.user_id().into());
-
-    Ok(req)
+    Ok(user)
 }
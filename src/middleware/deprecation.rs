@@ -0,0 +1,85 @@
+//! Middleware which announces the deprecation of a version of the API. Responses of
+//! a deprecated version carry a `Deprecation` header as described by
+//! [RFC 9745](https://www.rfc-editor.org/rfc/rfc9745), a `Sunset` header as
+//! described by [RFC 8594](https://www.rfc-editor.org/rfc/rfc8594) and a link to
+//! the same resource in the version which supersedes it.
+
+use crate::configuration::api::{ApiSettings, Deprecation};
+use crate::routes::version::ApiVersion;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::http::header::{self, HeaderMap, HeaderValue};
+use actix_web::http::header::{HttpDate, TryIntoHeaderValue};
+use actix_web::{web, Error};
+use std::future::Future;
+use std::time::SystemTime;
+
+/// Wraps the scope of every version, see [actix_web::Scope::wrap_fn]. Versions
+/// which are not deprecated are left alone.
+pub fn announce<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    let version = req.app_data::<ApiVersion>().copied();
+    let deprecation = req
+        .app_data::<web::Data<ApiSettings>>()
+        .zip(version)
+        .and_then(|(settings, version)| deprecation(settings, version).cloned());
+    let successor = version.and_then(|version| {
+        let successor = version.successor()?;
+        let path = req.path().strip_prefix(version.prefix())?;
+        Some(format!("{}{path}", successor.prefix()))
+    });
+    let response = srv.call(req);
+
+    async move {
+        let Some(deprecation) = deprecation else {
+            return response.await;
+        };
+
+        match response.await {
+            Ok(mut response) => {
+                insert_headers(response.headers_mut(), &deprecation, successor.as_deref());
+                Ok(response)
+            }
+            // Errors returned by middleware, such as failed authentication, are
+            // only turned into responses once they reach the server.
+            Err(e) => {
+                let mut response = e.error_response();
+                insert_headers(response.headers_mut(), &deprecation, successor.as_deref());
+                Err(InternalError::from_response(e, response).into())
+            }
+        }
+    }
+}
+
+fn deprecation(settings: &ApiSettings, version: ApiVersion) -> Option<&Deprecation> {
+    match version {
+        ApiVersion::V1 => Some(&settings.v1),
+        ApiVersion::V2 => None,
+    }
+}
+
+fn insert_headers(headers: &mut HeaderMap, deprecation: &Deprecation, successor: Option<&str>) {
+    let deprecated_at = format!("@{}", deprecation.deprecated_at.timestamp());
+    if let Ok(value) = HeaderValue::from_str(&deprecated_at) {
+        headers.insert(header::HeaderName::from_static("deprecation"), value);
+    }
+
+    let sunset_at = HttpDate::from(SystemTime::from(deprecation.sunset_at));
+    if let Ok(value) = sunset_at.try_into_value() {
+        headers.insert(header::HeaderName::from_static("sunset"), value);
+    }
+
+    if let Some(successor) = successor {
+        let link = format!("<{successor}>; rel=\"successor-version\"");
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            headers.append(header::LINK, value);
+        }
+    }
+}
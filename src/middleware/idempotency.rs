@@ -0,0 +1,426 @@
+//! Middleware which makes POST requests safe to retry. A request with an
+//! `Idempotency-Key` header [claims](actions::claim) the key for the user it was
+//! authenticated as, or for everyone who sent no valid credentials, along with a
+//! fingerprint of its method, path, content type and body. Both are stored as
+//! HMACs keyed with a secret of the server, so that the stored digests cannot be
+//! used to guess passwords. The response is stored once the request has been handled, and replayed
+//! with an `Idempotent-Replayed` header to every retry with the same key until the
+//! key expires. A retry which arrives while the first request is still handled is
+//! answered with a conflict, and a key reused for a different request is rejected.
+//! Server errors, redirects and panics release the key, so that the request can be
+//! retried, and a key whose request never finished is taken over by a retry once
+//! its lease ended. Responses marked `Cache-Control: no-store`, such as the tokens
+//! of a signin, are not stored either and release the key, so that no credentials
+//! are kept in the database.
+//!
+//! The middleware wraps the whole application, so responses are stored as they
+//! were sent, once their body was [encoded](super::negotiation) and their
+//! [problem](super::problem) completed. That is also why it checks the
+//! credentials of a request itself, before the [authentication](super::auth) of
+//! the route does.
+
+use crate::auth::verify_jwt;
+use crate::configuration::{auth::AuthSettings, idempotency::IdempotencySettings};
+use crate::database::Database;
+use crate::domain::idempotency::{
+    actions::{self, Claim, IdempotencyError, Lease},
+    StoredResponse,
+};
+use crate::domain::user::{avatar, cache::UserCache};
+use crate::error::{self, ErrorResponse};
+use crate::middleware::auth::{self, AuthError};
+use crate::middleware::problem;
+use actix_web::body::{self, BoxBody, MessageBody};
+use actix_web::dev::{self, forward_ready, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::error::PayloadError;
+use actix_web::http::header::{self, HeaderMap, HeaderName, HeaderValue};
+use actix_web::http::{Method, StatusCode};
+use actix_web::web::{self, Bytes, BytesMut};
+use actix_web::{Error, HttpMessage, HttpResponse, ResponseError};
+use actix_web_httpauth::extractors::{basic::BasicAuth, bearer::BearerAuth};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use futures_util::{stream, StreamExt};
+use hmac::{Hmac, Mac};
+use secrecy::{ExposeSecret, Secret};
+use sha2::Sha256;
+use std::rc::Rc;
+use uuid::Uuid;
+
+/// The header which carries the key chosen by the client.
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// The header which marks a response as replayed.
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+/// The longest key which is accepted.
+pub const MAX_KEY_LENGTH: usize = 255;
+/// The principal of requests without valid credentials.
+const ANONYMOUS: &str = "anonymous";
+/// The largest body which is fingerprinted, enough for an avatar along with the
+/// rest of its multipart form.
+const LIMIT: usize = avatar::MAX_UPLOAD_BYTES + 64 * 1024;
+
+/// Wraps the whole application, see [actix_web::App::wrap].
+pub struct Idempotency;
+
+impl<S, B> Transform<S, ServiceRequest> for Idempotency
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Transform = IdempotencyMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyMiddleware {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+pub struct IdempotencyMiddleware<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for IdempotencyMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            if req.method() != Method::POST || !req.headers().contains_key(IDEMPOTENCY_KEY) {
+                let response = service.call(req).await?;
+                return Ok(response.map_into_boxed_body());
+            }
+
+            let (req, claimed) = claim(req).await;
+            let claimed = match claimed {
+                Ok(claimed) => claimed,
+                Err(e) => {
+                    tracing::warn!("Rejected the idempotency key: {e}");
+                    let (request, _) = req.into_parts();
+                    let response = problem::complete(e.error_response(), &request).await;
+                    return Ok(ServiceResponse::new(request, response));
+                }
+            };
+
+            match claimed {
+                Claimed::Completed(stored) => {
+                    tracing::info!("Replaying the response to the idempotency key");
+                    Ok(req.into_response(replay(stored)))
+                }
+                // The request is handled in a task of its own, so that its
+                // response is stored even when the client gives up waiting for
+                // it, as a retrying client does.
+                Claimed::Acquired(key) => {
+                    let acquired = key.clone();
+                    let handled = actix_web::rt::spawn(async move {
+                        let response = service.call(req).await;
+                        record(&acquired, response).await
+                    });
+                    match handled.await {
+                        Ok(response) => response,
+                        Err(e) => {
+                            release(&key).await;
+                            match e.try_into_panic() {
+                                Ok(panic) => std::panic::resume_unwind(panic),
+                                Err(e) => Err(actix_web::error::ErrorInternalServerError(e)),
+                            }
+                        }
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// A key acquired by a request, which has to be completed or released.
+#[derive(Clone)]
+struct AcquiredKey {
+    db: web::Data<Database>,
+    principal: String,
+    key: String,
+    lease: Lease,
+}
+
+enum Claimed {
+    Acquired(AcquiredKey),
+    Completed(StoredResponse),
+}
+
+/// Claim the key of `req`. The body of the request is read to fingerprint it,
+/// and put back for the handler.
+async fn claim(mut req: ServiceRequest) -> (ServiceRequest, Result<Claimed, IdempotencyError>) {
+    let claimed = async {
+        let key = key(req.headers())?;
+        let db = req
+            .app_data::<web::Data<Database>>()
+            .cloned()
+            .expect("The database is registered as app data");
+        let settings = req
+            .app_data::<web::Data<IdempotencySettings>>()
+            .map(|settings| settings.get_ref().clone())
+            .unwrap_or_default();
+
+        let secret = req
+            .app_data::<web::Data<AuthSettings>>()
+            .map(|settings| settings.idempotencysecret.clone())
+            .expect("The auth settings are registered as app data");
+
+        let body = read(req.take_payload()).await?;
+        let principal = principal(&mut req, &secret).await?;
+        let fingerprint = fingerprint(&req, &body, &secret);
+        req.set_payload(payload(body));
+
+        let claim = actions::claim(
+            &db,
+            &principal,
+            &key,
+            &fingerprint,
+            settings.ttl_seconds,
+            settings.lease_seconds,
+        )
+        .await?;
+        match claim {
+            Claim::Acquired(lease) => Ok(Claimed::Acquired(AcquiredKey {
+                db,
+                principal,
+                key,
+                lease,
+            })),
+            Claim::Completed(stored) => Ok(Claimed::Completed(stored)),
+        }
+    }
+    .await;
+
+    (req, claimed)
+}
+
+fn key(headers: &HeaderMap) -> Result<String, IdempotencyError> {
+    headers
+        .get(IDEMPOTENCY_KEY)
+        .and_then(|value| value.to_str().ok())
+        .filter(|key| (1..=MAX_KEY_LENGTH).contains(&key.len()))
+        .filter(|key| key.bytes().all(|byte| byte.is_ascii_graphic()))
+        .map(str::to_owned)
+        .ok_or(IdempotencyError::InvalidKey(MAX_KEY_LENGTH))
+}
+
+async fn read(mut payload: dev::Payload) -> Result<Bytes, IdempotencyError> {
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        // A body which cannot be read is rejected by the handler instead.
+        let Ok(chunk) = chunk else { break };
+        if body.len() + chunk.len() > LIMIT {
+            return Err(IdempotencyError::PayloadTooLarge(LIMIT));
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body.freeze())
+}
+
+/// A payload which yields `body` again.
+fn payload(body: Bytes) -> dev::Payload {
+    let stream = stream::once(ready(Ok::<_, PayloadError>(body)));
+    dev::Payload::from(stream.boxed_local())
+}
+
+/// A digest of the id of the user the request is authenticated as, or of
+/// [ANONYMOUS] when its credentials are missing or invalid.
+async fn principal(
+    req: &mut ServiceRequest,
+    secret: &Secret<String>,
+) -> Result<String, IdempotencyError> {
+    let principal = match authenticated(req).await {
+        Ok(id) => id.to_string(),
+        Err(AuthError::DatabaseError(e)) => return Err(e.into()),
+        Err(_) => ANONYMOUS.to_owned(),
+    };
+
+    Ok(digest(secret, &[b"principal", principal.as_bytes()]))
+}
+
+/// The id of the user whose token or `user_id` and password the request carries.
+async fn authenticated(req: &mut ServiceRequest) -> Result<Uuid, AuthError> {
+    if let Ok(bearer) = req.extract::<BearerAuth>().await {
+        let settings = req
+            .app_data::<web::Data<AuthSettings>>()
+            .ok_or(AuthError::MissingConfig)?;
+        let claims = verify_jwt(bearer.token(), &settings.jwtsecret)?;
+        return Uuid::parse_str(&claims.sub).map_err(|_| AuthError::InvalidCredentials);
+    }
+
+    let credentials = req
+        .extract::<BasicAuth>()
+        .await
+        .map_err(|_| AuthError::InvalidCredentials)?;
+    let db = req.extract::<web::Data<Database>>().await?;
+    let cache = req.extract::<web::Data<UserCache>>().await?;
+    let user = auth::authenticate(&db, &cache, &credentials).await?;
+
+    Ok(user.id)
+}
+
+fn fingerprint(req: &ServiceRequest, body: &[u8], secret: &Secret<String>) -> String {
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .map(HeaderValue::as_bytes)
+        .unwrap_or_default();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.as_str())
+        .unwrap_or_default();
+
+    digest(
+        secret,
+        &[
+            b"fingerprint",
+            req.method().as_str().as_bytes(),
+            path.as_bytes(),
+            content_type,
+            body,
+        ],
+    )
+}
+
+/// The HMAC-SHA256 of `parts`, each followed by a newline, in lowercase hex.
+fn digest(secret: &Secret<String>, parts: &[&[u8]]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.expose_secret().as_bytes())
+        .expect("HMAC accepts keys of any length");
+    for part in parts {
+        mac.update(part);
+        mac.update(b"\n");
+    }
+
+    mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Store the response to the request which acquired `key`, or release the key
+/// when the request failed or its response may not be stored.
+async fn record<B>(
+    key: &AcquiredKey,
+    response: Result<ServiceResponse<B>, Error>,
+) -> Result<ServiceResponse<BoxBody>, Error>
+where
+    B: MessageBody + 'static,
+{
+    let response = match response {
+        Ok(response) if is_final(response.status()) && is_storable(response.headers()) => response,
+        response => {
+            release(key).await;
+            return response.map(ServiceResponse::map_into_boxed_body);
+        }
+    };
+
+    let (request, response) = response.into_parts();
+    let (response, body) = response.into_parts();
+    let body = match body::to_bytes(body).await {
+        Ok(body) => body,
+        Err(e) => {
+            release(key).await;
+            let e: Box<dyn std::error::Error> = e.into();
+            return Err(actix_web::error::ErrorInternalServerError(e.to_string()));
+        }
+    };
+
+    let stored = StoredResponse {
+        status: response.status().as_u16(),
+        headers: response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_owned())))
+            .collect(),
+        body: body.to_vec(),
+    };
+    if let Err(e) = actions::complete(&key.db, &key.principal, &key.key, &key.lease, &stored).await
+    {
+        tracing::error!("Failed to store the response to the idempotency key: {e}");
+        release(key).await;
+    }
+
+    Ok(ServiceResponse::new(
+        request,
+        response.set_body(BoxBody::new(body)),
+    ))
+}
+
+/// Whether a response with `status` is stored. Server errors may not happen again,
+/// and a redirect is followed with another request, such as from a legacy path to
+/// the versioned one.
+fn is_final(status: StatusCode) -> bool {
+    !status.is_server_error() && !status.is_redirection()
+}
+
+/// Whether a response with `headers` may be stored. Responses which carry
+/// credentials are marked `no-store`, and are only sent to the request which
+/// asked for them.
+fn is_storable(headers: &HeaderMap) -> bool {
+    !headers
+        .get_all(header::CACHE_CONTROL)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|directive| directive.trim().eq_ignore_ascii_case("no-store"))
+}
+
+async fn release(key: &AcquiredKey) {
+    if let Err(e) = actions::release(&key.db, &key.principal, &key.key, &key.lease).await {
+        tracing::error!("Failed to release the idempotency key: {e}");
+    }
+}
+
+fn replay(stored: StoredResponse) -> HttpResponse {
+    let status = StatusCode::from_u16(stored.status).unwrap_or(StatusCode::OK);
+    let mut response = HttpResponse::build(status);
+    for (name, value) in &stored.headers {
+        response.append_header((name.as_str(), value.as_str()));
+    }
+    response.insert_header((IDEMPOTENT_REPLAYED, "true"));
+    response.body(stored.body)
+}
+
+impl ResponseError for IdempotencyError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            IdempotencyError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            IdempotencyError::InvalidKey(_) => StatusCode::BAD_REQUEST,
+            IdempotencyError::PayloadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            IdempotencyError::KeyReused(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            IdempotencyError::InProgress(_) => StatusCode::CONFLICT,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&IdempotencyError> for ErrorResponse {
+    fn from(value: &IdempotencyError) -> Self {
+        let problem = match value {
+            IdempotencyError::DatabaseError(_) => return Self::default(),
+            IdempotencyError::InvalidKey(_) => Self::new("invalid_idempotency_key"),
+            IdempotencyError::PayloadTooLarge(_) => Self::new("payload_too_large"),
+            IdempotencyError::KeyReused(_) => Self::new("idempotency_key_reused"),
+            IdempotencyError::InProgress(_) => Self::new("idempotency_key_in_progress"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -1,3 +1,9 @@
 //! Contains all middleware for the application.
 
 pub mod auth;
+pub mod deprecation;
+pub mod idempotency;
+pub mod negotiation;
+pub mod organization;
+pub mod problem;
+pub mod tenant;
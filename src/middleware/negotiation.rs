@@ -0,0 +1,53 @@
+//! Middleware which encodes response bodies in the format the client accepts.
+//! Handlers respond with [negotiation::respond], which leaves the body in the
+//! extensions of the response since only the middleware has access to the
+//! request. Problem documents are encoded by the [problem](super::problem)
+//! middleware instead.
+
+use crate::negotiation::{self, Format, Negotiated};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::Error;
+use std::future::Future;
+
+/// Wraps every service of the application, see [actix_web::App::wrap_fn].
+pub fn encode<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<BoxBody>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    let format = Format::accepted(req.request());
+    let response = srv.call(req);
+
+    async move {
+        let mut response = response.await?.map_into_boxed_body();
+        let Some(Negotiated(body)) = response.response_mut().extensions_mut().remove() else {
+            return Ok(response);
+        };
+
+        negotiation::vary(response.response_mut());
+        if format == Format::Json {
+            return Ok(response);
+        }
+
+        let body = match format.encode(&body) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!("Failed to encode the response as {format:?}: {e}");
+                return Ok(response);
+            }
+        };
+        let (request, response) = response.into_parts();
+        let mut response = response.set_body(BoxBody::new(body));
+        response.headers_mut().insert(
+            header::CONTENT_TYPE,
+            HeaderValue::from_static(format.media_type()),
+        );
+
+        Ok(ServiceResponse::new(request, response))
+    }
+}
@@ -0,0 +1,82 @@
+//! Authorization for endpoints which act on the active organization. It builds on
+//! the claims which [validator](super::auth::validator) stores on the request, and
+//! checks the membership in the [RequestTransaction] of the request.
+
+use super::tenant::RequestTransaction;
+use crate::auth::TokenClaims;
+use crate::database::TenantScope;
+use crate::domain::organization::{actions, actions::OrganizationError, Membership};
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpMessage, HttpRequest};
+use futures_util::future::{ready, LocalBoxFuture, Ready};
+use std::ops::Deref;
+
+/// The membership of the requesting user in the organization carried by their
+/// token. Extracting it fails unless the token names an organization which the
+/// user is still a member of.
+#[derive(Debug)]
+pub struct ActiveMembership(Membership);
+
+impl Deref for ActiveMembership {
+    type Target = Membership;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromRequest for ActiveMembership {
+    type Error = OrganizationError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let claims = req.extensions().get::<TokenClaims>().cloned();
+        let transaction = RequestTransaction::from_request(req, payload);
+
+        Box::pin(async move {
+            let scope = claims
+                .as_ref()
+                .ok_or(OrganizationError::NotMember)
+                .and_then(scope_of)?;
+            let organization_id = scope
+                .tenant_id
+                .ok_or(OrganizationError::NoActiveOrganization)?;
+            let transaction = transaction.await?;
+
+            tracing::debug!("Checking membership of organization {organization_id}");
+            let membership = actions::get_membership(&mut *transaction.lock().await, &scope)
+                .await?
+                .ok_or(OrganizationError::NotMember)?;
+
+            Ok(Self(membership))
+        })
+    }
+}
+
+/// The scope carried by the token of the requesting user. Queries which touch
+/// tenant data run in the [RequestTransaction] started with it.
+impl FromRequest for TenantScope {
+    type Error = OrganizationError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let scope = req
+            .extensions()
+            .get::<TokenClaims>()
+            .ok_or(OrganizationError::NotMember)
+            .and_then(scope_of);
+        ready(scope)
+    }
+}
+
+fn scope_of(claims: &TokenClaims) -> Result<TenantScope, OrganizationError> {
+    let user_id = claims
+        .sub
+        .parse()
+        .map_err(|_| OrganizationError::NotMember)?;
+
+    Ok(TenantScope {
+        tenant_id: claims.org,
+        user_id,
+    })
+}
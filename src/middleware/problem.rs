@@ -0,0 +1,111 @@
+//! Middleware which completes problem documents. Errors are turned into responses
+//! without access to the request, so [ErrorResponse::into_response] leaves the
+//! problem in the extensions of the response and it is rewritten here with the path
+//! of the request as its `instance` and its text in the language of the client.
+//!
+//! The language is the one the signed-in user saved as their `locale`, if it is
+//! supported, and is otherwise negotiated from the `Accept-Language` header. The
+//! problem is encoded in the [format](crate::negotiation) the client accepts.
+
+use crate::database::Database;
+use crate::domain::user::{cache::UserCache, BasicId};
+use crate::error::ErrorResponse;
+use crate::i18n;
+use crate::negotiation::{self, Format};
+use actix_web::body::{BoxBody, MessageBody};
+use actix_web::dev::{Service, ServiceRequest, ServiceResponse};
+use actix_web::error::InternalError;
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::{web, Error, HttpMessage, HttpRequest, HttpResponse};
+use std::future::Future;
+use unic_langid::LanguageIdentifier;
+
+/// Wraps every service of the application, see [actix_web::App::wrap_fn].
+pub fn instance<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<BoxBody>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    let request = req.request().clone();
+    let response = srv.call(req);
+
+    async move {
+        match response.await {
+            Ok(response) => {
+                let (request, response) = response.map_into_boxed_body().into_parts();
+                let response = complete(response, &request).await;
+                Ok(ServiceResponse::new(request, response))
+            }
+            // Errors returned by middleware, such as failed authentication, are
+            // only turned into responses once they reach the server.
+            Err(e) => {
+                let response = complete(e.error_response(), &request).await;
+                Err(InternalError::from_response(e, response).into())
+            }
+        }
+    }
+}
+
+/// Complete the problem carried by `response`, if it carries one, for `request`.
+pub async fn complete(mut response: HttpResponse, request: &HttpRequest) -> HttpResponse {
+    let Some(mut problem) = response.extensions_mut().remove::<ErrorResponse>() else {
+        return response;
+    };
+
+    let language = language(request).await;
+    problem.instance = Some(request.path().to_owned());
+    problem.localize(language);
+
+    let headers = response.headers_mut();
+    if let Ok(value) = HeaderValue::from_str(&language.to_string()) {
+        headers.insert(header::CONTENT_LANGUAGE, value);
+    }
+    headers.append(header::VARY, HeaderValue::from_static("accept-language"));
+
+    let format = Format::accepted(request);
+    let body = format.encode(&problem).unwrap_or_default();
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static(format.problem_media_type()),
+    );
+    negotiation::vary(&mut response);
+    response.set_body(BoxBody::new(body))
+}
+
+/// The language of the text answered to `request`.
+pub async fn language(request: &HttpRequest) -> &'static LanguageIdentifier {
+    let saved = saved_locale(request).await;
+    let accepted = request
+        .headers()
+        .get(header::ACCEPT_LANGUAGE)
+        .and_then(|value| value.to_str().ok())
+        .map(i18n::accepted_languages)
+        .unwrap_or_default();
+
+    i18n::negotiate(saved.as_deref().into_iter().chain(accepted))
+}
+
+/// The locale saved by the user who made the request, if they are signed in.
+async fn saved_locale(request: &HttpRequest) -> Option<String> {
+    let db = request.app_data::<web::Data<Database>>()?;
+    let cache = request.app_data::<web::Data<UserCache>>()?;
+    let id = request.extensions().get::<uuid::Uuid>().copied();
+    let user_id = request.extensions().get::<BasicId>().map(String::from);
+
+    let user = match (id, user_id) {
+        (Some(id), _) => cache.find_by_id(db, &id).await,
+        (None, Some(user_id)) => cache.find(db, &user_id).await,
+        (None, None) => return None,
+    };
+
+    match user {
+        Ok(user) => user?.locale,
+        Err(e) => {
+            tracing::warn!("Failed to load the locale of the user: {e}");
+            None
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! One [ScopedTransaction] per request, started with the [TenantScope] of the
+//! requesting user the first time an extractor asks for it. Every query of the
+//! request runs in it, so row level security is enforced for all of them, and
+//! the [commit] middleware commits it once the handler succeeded. Failed
+//! requests are rolled back.
+
+use crate::database::{Database, ScopedTransaction, TenantScope};
+use crate::domain::organization::actions::OrganizationError;
+use actix_web::body::MessageBody;
+use actix_web::dev::{Payload, Service, ServiceRequest, ServiceResponse};
+use actix_web::{web, Error, FromRequest, HttpMessage, HttpRequest};
+use futures_util::future::LocalBoxFuture;
+use std::future::Future;
+use std::rc::Rc;
+use tokio::sync::{Mutex, MutexGuard, OnceCell};
+
+/// The transaction of the current request. Extracting it begins the transaction
+/// unless an earlier extractor of the same request already did.
+#[derive(Debug, Clone, Default)]
+pub struct RequestTransaction(Rc<OnceCell<Mutex<ScopedTransaction>>>);
+
+impl RequestTransaction {
+    /// Wait until nothing else of the request uses the transaction.
+    pub async fn lock(&self) -> MutexGuard<'_, ScopedTransaction> {
+        self.0
+            .get()
+            .expect("The transaction begins when it is extracted")
+            .lock()
+            .await
+    }
+}
+
+impl FromRequest for RequestTransaction {
+    type Error = OrganizationError;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let scope = TenantScope::from_request(req, payload).into_inner();
+        let db = req.app_data::<web::Data<Database>>().cloned();
+        // Every extractor of the request shares the same slot, even when they are
+        // polled together.
+        let existing = req.extensions().get::<RequestTransaction>().cloned();
+        let transaction = existing.unwrap_or_else(|| {
+            let transaction = RequestTransaction::default();
+            req.extensions_mut().insert(transaction.clone());
+            transaction
+        });
+
+        Box::pin(async move {
+            let scope = scope?;
+            let db = db.expect("The database is registered as app data");
+            transaction
+                .0
+                .get_or_try_init(|| async { db.scoped(&scope).await.map(Mutex::new) })
+                .await?;
+
+            Ok(transaction)
+        })
+    }
+}
+
+/// Wraps the scopes whose handlers use a [RequestTransaction], see
+/// [actix_web::Scope::wrap_fn]. The transaction is committed when the response is
+/// successful and rolled back otherwise.
+pub fn commit<S, B>(
+    req: ServiceRequest,
+    srv: &S,
+) -> impl Future<Output = Result<ServiceResponse<B>, Error>>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error>,
+    B: MessageBody + 'static,
+{
+    let request = req.request().clone();
+    let response = srv.call(req);
+
+    async move {
+        let response = response.await;
+        let Some(RequestTransaction(transaction)) = request.extensions_mut().remove() else {
+            return response;
+        };
+
+        let response = response?;
+        if !response.status().is_success() {
+            return Ok(response);
+        }
+
+        let Some(transaction) = Rc::into_inner(transaction).and_then(OnceCell::into_inner) else {
+            tracing::error!("The transaction of the request was still in use");
+            return Err(OrganizationError::TransactionInUse.into());
+        };
+
+        transaction
+            .into_inner()
+            .commit()
+            .await
+            .map_err(OrganizationError::from)?;
+
+        Ok(response)
+    }
+}
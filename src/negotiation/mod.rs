@@ -0,0 +1,151 @@
+//! Content negotiation of bodies. Clients may send and receive JSON, MessagePack
+//! or CBOR: request bodies are decoded by [Payload] according to their
+//! `Content-Type`, and response bodies are encoded according to the `Accept`
+//! header once they reach the [middleware](crate::middleware::negotiation).
+//! JSON is the default, and answers clients which accept none of the formats.
+
+mod payload;
+
+pub use payload::{rejected, Payload};
+
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::{HttpRequest, HttpResponse, HttpResponseBuilder};
+use serde::{de::DeserializeOwned, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+impl Format {
+    /// The media type of bodies in this format.
+    pub fn media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::MessagePack => "application/msgpack",
+            Self::Cbor => "application/cbor",
+        }
+    }
+
+    /// The media type of [problem documents](crate::error::ErrorResponse) in this
+    /// format.
+    pub fn problem_media_type(self) -> &'static str {
+        match self {
+            Self::Json => "application/problem+json",
+            Self::MessagePack => "application/problem+msgpack",
+            Self::Cbor => "application/problem+cbor",
+        }
+    }
+
+    /// The format of a media type, ignoring its parameters. Structured syntax
+    /// suffixes such as `+json` are recognized as well.
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next()?.trim().to_ascii_lowercase();
+
+        match essence.as_str() {
+            "application/json" => Some(Self::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                Some(Self::MessagePack)
+            }
+            "application/cbor" => Some(Self::Cbor),
+            _ if essence.ends_with("+json") => Some(Self::Json),
+            _ if essence.ends_with("+msgpack") => Some(Self::MessagePack),
+            _ if essence.ends_with("+cbor") => Some(Self::Cbor),
+            _ => None,
+        }
+    }
+
+    /// The format of the body of `req`. Bodies without a supported `Content-Type`
+    /// are treated as JSON, which rejects them as it always did.
+    pub fn of_content(req: &HttpRequest) -> Self {
+        req.headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Self::from_media_type)
+            .unwrap_or(Self::Json)
+    }
+
+    /// The format the client who sent `req` prefers among those it accepts.
+    pub fn accepted(req: &HttpRequest) -> Self {
+        let Some(accept) = req
+            .headers()
+            .get(header::ACCEPT)
+            .and_then(|value| value.to_str().ok())
+        else {
+            return Self::Json;
+        };
+
+        preferences(accept)
+            .into_iter()
+            .find_map(|range| match range {
+                "*/*" | "application/*" => Some(Self::Json),
+                range => Self::from_media_type(range),
+            })
+            .unwrap_or(Self::Json)
+    }
+
+    pub fn encode(self, value: &impl Serialize) -> Result<Vec<u8>, String> {
+        match self {
+            Self::Json => serde_json::to_vec(value).map_err(|e| e.to_string()),
+            // Structs are encoded as maps so that clients can rely on field names
+            // rather than on their order.
+            Self::MessagePack => rmp_serde::to_vec_named(value).map_err(|e| e.to_string()),
+            Self::Cbor => {
+                let mut body = Vec::new();
+                ciborium::into_writer(value, &mut body).map_err(|e| e.to_string())?;
+                Ok(body)
+            }
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(self, body: &[u8]) -> Result<T, String> {
+        match self {
+            Self::Json => serde_json::from_slice(body).map_err(|e| e.to_string()),
+            Self::MessagePack => rmp_serde::from_slice(body).map_err(|e| e.to_string()),
+            Self::Cbor => ciborium::from_reader(body).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+/// A response body which is encoded in the format the client accepts. It is left
+/// in the extensions of the response by [respond].
+pub struct Negotiated(pub serde_json::Value);
+
+/// Respond with `body`, built from `builder`. The response is JSON until the
+/// middleware encodes it in the format the client accepts.
+pub fn respond(mut builder: HttpResponseBuilder, body: &impl Serialize) -> HttpResponse {
+    let body = serde_json::to_value(body).expect("Response bodies can be serialized");
+    let mut response = builder.json(&body);
+    response.extensions_mut().insert(Negotiated(body));
+    response
+}
+
+/// Add `Accept` to the `Vary` header of a response which was negotiated.
+pub fn vary(response: &mut HttpResponse) {
+    response
+        .headers_mut()
+        .append(header::VARY, HeaderValue::from_static("accept"));
+}
+
+/// The values of a header such as `Accept` or `Accept-Language`, most preferred
+/// first. Values with a quality of zero are left out.
+pub fn preferences(header: &str) -> Vec<&str> {
+    let mut values: Vec<(&str, f32)> = header
+        .split(',')
+        .filter_map(|range| {
+            let mut params = range.split(';');
+            let value = params.next()?.trim();
+            let quality = params
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.trim().parse().ok())?;
+
+            (!value.is_empty() && quality > 0.0).then_some((value, quality))
+        })
+        .collect();
+
+    // The sort is stable, so values of equal quality keep the order of the header.
+    values.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+    values.into_iter().map(|(value, _)| value).collect()
+}
@@ -0,0 +1,97 @@
+use super::Format;
+use crate::domain::user::actions::SignupError;
+use crate::error::ErrorResponse;
+use actix_web::dev;
+use actix_web::error::{Error, InternalError, JsonPayloadError};
+use actix_web::web::{self, BytesMut};
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use futures_util::future::LocalBoxFuture;
+use futures_util::StreamExt;
+use serde::de::{DeserializeOwned, Error as _};
+use std::ops::{Deref, DerefMut};
+
+/// The largest body which is decoded, the same as for JSON bodies.
+const LIMIT: usize = 2_097_152;
+
+/// A request body in any of the supported [formats](Format), the negotiated
+/// counterpart of [web::Json]. JSON bodies are still extracted by [web::Json], so
+/// they are configured by [web::JsonConfig].
+#[derive(Debug)]
+pub struct Payload<T>(pub T);
+
+impl<T> Payload<T> {
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for Payload<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Payload<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: DeserializeOwned + 'static> FromRequest for Payload<T> {
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut dev::Payload) -> Self::Future {
+        match Format::of_content(req) {
+            Format::Json => {
+                let json = web::Json::<T>::from_request(req, payload);
+                Box::pin(async move { Ok(Self(json.await?.into_inner())) })
+            }
+            format => {
+                let req = req.clone();
+                let payload = payload.take();
+                Box::pin(async move {
+                    read(payload)
+                        .await
+                        .and_then(|body| {
+                            format.decode(&body).map_err(|e| {
+                                JsonPayloadError::Deserialize(serde_json::Error::custom(e))
+                            })
+                        })
+                        .map(Self)
+                        .map_err(|e| rejected(e, &req))
+                })
+            }
+        }
+    }
+}
+
+async fn read(mut payload: dev::Payload) -> Result<BytesMut, JsonPayloadError> {
+    let mut body = BytesMut::new();
+    while let Some(chunk) = payload.next().await {
+        let chunk = chunk?;
+        if body.len() + chunk.len() > LIMIT {
+            return Err(JsonPayloadError::Overflow { limit: LIMIT });
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(body)
+}
+
+/// Answer a body which cannot be extracted with a problem document. Bodies in
+/// other formats are rejected for the same reasons as JSON bodies, so they are
+/// described by the same errors.
+pub fn rejected(err: JsonPayloadError, req: &HttpRequest) -> Error {
+    let problem = match err {
+        JsonPayloadError::Deserialize(_) if req.path().ends_with("/signup") => {
+            ErrorResponse::from(&SignupError::InvalidPayload)
+        }
+        _ => ErrorResponse::from(&err),
+    };
+    let response = problem.into_response(HttpResponse::build(err.status_code()));
+
+    InternalError::from_response(err, response).into()
+}
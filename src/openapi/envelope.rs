@@ -0,0 +1,115 @@
+//! The shapes of response bodies which wrap a DTO along with a message. The
+//! handlers build these bodies with `serde_json::json!`, so these types only
+//! exist to describe them. Only the first version of the API wraps its responses.
+
+use crate::domain::{
+    organization::dto::{InvitationResponse, OrganizationResponse},
+    user::dto::{FollowResponse, GetUserResponse, PrivacyResponse, SignupResponse},
+    webhook::dto::{DeliveryResponse, SubscriptionResponse},
+};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// The envelopes along with the schema of the DTO they wrap, or `None` for a bare
+/// [Message]. Later versions of the API respond with the DTO alone, and with no
+/// content instead of a bare message.
+pub const UNWRAPPED: [(&str, Option<&str>); 10] = [
+    ("Message", None),
+    ("SignupEnvelope", Some("SignupResponse")),
+    ("UserEnvelope", Some("GetUserResponse")),
+    ("UpdatedUserEnvelope", Some("GetUserResponse")),
+    ("FollowEnvelope", Some("FollowResponse")),
+    ("PrivacyEnvelope", Some("PrivacyResponse")),
+    ("OrganizationEnvelope", Some("OrganizationResponse")),
+    ("InvitationEnvelope", Some("InvitationResponse")),
+    ("SubscriptionEnvelope", Some("SubscriptionResponse")),
+    ("DeliveryEnvelope", Some("DeliveryResponse")),
+];
+
+#[derive(Serialize, ToSchema)]
+pub struct Message {
+    pub message: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct Token {
+    pub token: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SignupEnvelope {
+    pub message: String,
+    pub user: SignupResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UserEnvelope {
+    pub message: String,
+    pub user: GetUserResponse,
+}
+
+/// The updated user is returned in a list named `recipe`.
+#[derive(Serialize, ToSchema)]
+pub struct UpdatedUserEnvelope {
+    pub message: String,
+    pub recipe: Vec<GetUserResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FollowEnvelope {
+    pub message: String,
+    pub follow: FollowResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PrivacyEnvelope {
+    pub message: String,
+    pub privacy: PrivacyResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationEnvelope {
+    pub message: String,
+    pub organization: OrganizationResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct OrganizationList {
+    pub organizations: Vec<OrganizationResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct InvitationEnvelope {
+    pub message: String,
+    pub invitation: InvitationResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SubscriptionEnvelope {
+    pub message: String,
+    pub subscription: SubscriptionResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SubscriptionList {
+    pub subscriptions: Vec<SubscriptionResponse>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeliveryEnvelope {
+    pub message: String,
+    pub delivery: DeliveryResponse,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeliveryList {
+    pub deliveries: Vec<DeliveryResponse>,
+}
+
+/// The multipart form which carries a new avatar.
+#[derive(ToSchema)]
+pub struct AvatarUpload {
+    /// A GIF, JPEG, PNG or WebP image.
+    #[schema(value_type = String, format = Binary)]
+    pub avatar: Vec<u8>,
+}
@@ -0,0 +1,239 @@
+//! The OpenAPI document of the application. Each scope of routes describes its
+//! own handlers, and they are merged here along with the ways to authenticate.
+//! The routes of the API are described once per version, under its prefix, and
+//! the operations of deprecated versions are marked as such. Every JSON body is
+//! also described in the other [formats](crate::negotiation) it may be exchanged in,
+//! and every POST operation takes an [idempotency key](crate::middleware::idempotency).
+//! The document is served at `/openapi.json`.
+
+pub mod envelope;
+
+use crate::middleware::idempotency::MAX_KEY_LENGTH;
+use crate::negotiation::Format;
+use crate::routes::{
+    private::{OrganizationApi, PrivateApi, WebhookApi},
+    public::{OperationalApi, PublicApi},
+    version::ApiVersion,
+};
+use utoipa::{
+    openapi::{
+        content::{Content, ContentBuilder},
+        path::{Operation, ParameterBuilder, ParameterIn, PathItem},
+        schema::{ObjectBuilder, Type},
+        security::{Http, HttpAuthScheme, SecurityScheme},
+        Deprecated, OpenApi as Document, Ref, RefOr, Required, ResponseBuilder,
+    },
+    Modify, OpenApi,
+};
+
+/// The name of the security scheme for routes which take a `user_id` and
+/// password with HTTP basic authentication.
+pub const BASIC: &str = "basic";
+/// The name of the security scheme for routes which take the JWT issued at
+/// signin.
+pub const BEARER: &str = "bearer";
+
+#[derive(OpenApi)]
+#[openapi(
+    info(title = "Track API", description = "Accounts, profiles and organizations"),
+    modifiers(&SecuritySchemes)
+)]
+struct ApiDoc;
+
+struct SecuritySchemes;
+
+impl Modify for SecuritySchemes {
+    fn modify(&self, openapi: &mut Document) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            BASIC,
+            SecurityScheme::Http(Http::new(HttpAuthScheme::Basic)),
+        );
+        components.add_security_scheme(
+            BEARER,
+            SecurityScheme::Http(
+                Http::builder()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
+
+/// Build the OpenAPI document.
+pub fn document() -> Document {
+    let mut document = ApiDoc::openapi();
+    // The crate has no license to advertise.
+    document.info.license = None;
+    document.merge(OperationalApi::openapi());
+    for version in ApiVersion::ALL {
+        document.merge(versioned(version));
+    }
+    for item in document.paths.paths.values_mut() {
+        if let Some(operation) = &mut item.post {
+            idempotent(operation);
+        }
+        operations(item).for_each(negotiated);
+    }
+    document
+}
+
+/// The routes of the API as they are served by `version`.
+fn versioned(version: ApiVersion) -> Document {
+    let mut document = PublicApi::openapi();
+    document.merge(PrivateApi::openapi());
+    document.merge(OrganizationApi::openapi());
+    document.merge(WebhookApi::openapi());
+
+    let paths = std::mem::take(&mut document.paths.paths);
+    for (path, mut item) in paths {
+        for operation in operations(&mut item) {
+            // Operation ids must be unique across the whole document.
+            operation.operation_id = operation
+                .operation_id
+                .take()
+                .map(|id| format!("{}_{id}", version.prefix().trim_start_matches('/')));
+            if version.successor().is_some() {
+                operation.deprecated = Some(Deprecated::True);
+            }
+            if version != ApiVersion::V1 {
+                unwrap_envelopes(operation);
+            }
+        }
+        document
+            .paths
+            .paths
+            .insert(format!("{}{path}", version.prefix()), item);
+    }
+    document
+}
+
+/// Describe the `Idempotency-Key` header of a POST operation, along with the
+/// problems it may be answered with.
+fn idempotent(operation: &mut Operation) {
+    let parameter = ParameterBuilder::new()
+        .name("Idempotency-Key")
+        .parameter_in(ParameterIn::Header)
+        .required(Required::False)
+        .description(Some(
+            "Replays the response to the first request with the same key, which \
+             is answered with an `Idempotent-Replayed` header",
+        ))
+        .schema(Some(
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .min_length(Some(1))
+                .max_length(Some(MAX_KEY_LENGTH)),
+        ))
+        .build();
+    operation
+        .parameters
+        .get_or_insert_with(Vec::new)
+        .push(parameter);
+
+    for (status, description) in [
+        (
+            "409",
+            "A request with the same idempotency key is in progress",
+        ),
+        (
+            "422",
+            "The idempotency key was used for a different request",
+        ),
+    ] {
+        let problem = ContentBuilder::new()
+            .schema(Some(Ref::from_schema_name("ErrorResponse")))
+            .build();
+        let response = ResponseBuilder::new()
+            .description(description)
+            .content(Format::Json.problem_media_type(), problem)
+            .build();
+        operation
+            .responses
+            .responses
+            .entry(status.to_owned())
+            .or_insert(RefOr::T(response));
+    }
+}
+
+/// Describe the bodies of `operation` in every format, with the same schema as
+/// their JSON counterpart.
+fn negotiated(operation: &mut Operation) {
+    if let Some(body) = &mut operation.request_body {
+        for (media_type, content) in alternatives(&body.content) {
+            body.content.entry(media_type).or_insert(content);
+        }
+    }
+    for response in operation.responses.responses.values_mut() {
+        if let RefOr::T(response) = response {
+            for (media_type, content) in alternatives(&response.content) {
+                response.content.entry(media_type).or_insert(content);
+            }
+        }
+    }
+}
+
+/// The content in other formats for each JSON content among `contents`.
+fn alternatives<'a>(
+    contents: impl IntoIterator<Item = (&'a String, &'a Content)>,
+) -> Vec<(String, Content)> {
+    let mut alternatives = Vec::new();
+    for (media_type, content) in contents {
+        let media_type = match media_type.as_str() {
+            json if json == Format::Json.media_type() => Format::media_type,
+            json if json == Format::Json.problem_media_type() => Format::problem_media_type,
+            _ => continue,
+        };
+        for format in [Format::MessagePack, Format::Cbor] {
+            alternatives.push((media_type(format).to_owned(), content.clone()));
+        }
+    }
+    alternatives
+}
+
+fn operations(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [
+        &mut item.get,
+        &mut item.put,
+        &mut item.post,
+        &mut item.delete,
+        &mut item.options,
+        &mut item.head,
+        &mut item.patch,
+        &mut item.trace,
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// Describe the bare resources which replace the envelopes of version 1, see
+/// [envelope::UNWRAPPED].
+fn unwrap_envelopes(operation: &mut Operation) {
+    let responses = std::mem::take(&mut operation.responses.responses);
+    for (status, mut response) in responses {
+        let mut status = status;
+        if let RefOr::T(response) = &mut response {
+            for content in response.content.values_mut() {
+                let Some(RefOr::Ref(schema)) = &content.schema else {
+                    continue;
+                };
+                let name = schema.ref_location.rsplit('/').next().unwrap_or_default();
+                match envelope::UNWRAPPED
+                    .iter()
+                    .find(|(envelope, _)| *envelope == name)
+                {
+                    Some((_, Some(resource))) => {
+                        content.schema = Some(RefOr::Ref(Ref::from_schema_name(*resource)));
+                    }
+                    Some((_, None)) => status = "204".to_owned(),
+                    None => {}
+                }
+            }
+            if status == "204" {
+                response.content.clear();
+            }
+        }
+        operation.responses.responses.insert(status, response);
+    }
+}
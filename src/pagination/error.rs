@@ -0,0 +1,44 @@
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use actix_web::{http::StatusCode, HttpResponse, ResponseError};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PaginationError {
+    #[error("The limit must be between 1 and {max}")]
+    InvalidLimit { max: i64 },
+    #[error("The cursor is invalid")]
+    InvalidCursor,
+    #[error("Sorting by '{0}' is not supported")]
+    UnknownSort(String),
+    #[error("The parameter '{0}' is not supported")]
+    UnknownParameter(String),
+    #[error("Value for filter '{field}' is invalid: '{reason}'")]
+    InvalidFilter { field: String, reason: Message },
+}
+
+impl ResponseError for PaginationError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&PaginationError> for ErrorResponse {
+    fn from(value: &PaginationError) -> Self {
+        let problem = match value {
+            PaginationError::InvalidLimit { .. } => Self::new("invalid_limit"),
+            PaginationError::InvalidCursor => Self::new("invalid_cursor"),
+            PaginationError::UnknownSort(_) => Self::new("unknown_sort"),
+            PaginationError::UnknownParameter(_) => Self::new("unknown_parameter"),
+            PaginationError::InvalidFilter { field, reason } => {
+                return Self::new("invalid_filter").with_field(field, reason)
+            }
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -0,0 +1,84 @@
+//! Cursor pagination, sorting and filtering for list endpoints. An endpoint
+//! describes what may be sorted and filtered by implementing [Listing]. The
+//! [ListQuery] extractor validates the query string against it, extends a
+//! [sqlx::QueryBuilder] with the matching conditions, and turns the rows which
+//! were fetched into a [Page].
+//!
+//! The query string accepts `limit`, `cursor`, `sort` and one parameter per
+//! filter. `sort` names a field, prefixed with `-` to sort in descending order.
+//! Cursors are signed, so clients cannot forge them, and only work with the
+//! sort and filters they were issued for.
+
+mod cursor;
+mod error;
+mod page;
+mod query;
+
+use chrono::NaiveDateTime;
+use uuid::Uuid;
+
+pub use cursor::CursorSigner;
+pub use error::PaginationError;
+pub use page::Page;
+pub use query::ListQuery;
+
+/// The type of a field, which decides how values for it are parsed and bound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    Text,
+    Integer,
+    Real,
+    Uuid,
+    /// Written as RFC 3339 in query strings.
+    Timestamp,
+    Boolean,
+}
+
+/// A field which can be sorted or filtered by.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Field {
+    /// The name used in the query string.
+    pub name: &'static str,
+    /// The SQL expression the field refers to. It is pasted into queries as is,
+    /// so it must never come from user input.
+    pub column: &'static str,
+    pub kind: FieldKind,
+}
+
+/// A value of a [Field].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Text(String),
+    Integer(i64),
+    Real(f32),
+    Uuid(Uuid),
+    Timestamp(NaiveDateTime),
+    Boolean(bool),
+}
+
+/// Describes a list endpoint: the rows it returns and how they may be sorted
+/// and filtered.
+pub trait Listing {
+    type Row;
+
+    /// The fields which may be sorted by.
+    const SORTS: &'static [Field];
+    /// The fields which may be filtered by, each compared for equality.
+    const FILTERS: &'static [Field];
+    /// The sort used when none is requested, in the same format as the `sort`
+    /// parameter.
+    const DEFAULT_SORT: &'static str;
+    /// A unique column which orders rows with equal sort values.
+    const TIE_BREAKER: &'static str;
+    /// Further parameters which the endpoint reads itself, such as a search
+    /// term. Cursors only work with the values they were issued for.
+    const PARAMETERS: &'static [&'static str] = &[];
+    const DEFAULT_LIMIT: i64 = 20;
+    const MAX_LIMIT: i64 = 100;
+
+    /// The value of the given sort field for a row.
+    fn sort_value(row: &Self::Row, field: &Field) -> Value;
+
+    /// The value of the [Listing::TIE_BREAKER] column for a row.
+    fn tie_breaker(row: &Self::Row) -> Uuid;
+}
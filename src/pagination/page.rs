@@ -0,0 +1,49 @@
+use crate::negotiation;
+use actix_web::{http::header, HttpRequest, HttpResponse};
+use serde::Serialize;
+use utoipa::ToSchema;
+
+/// A page of a list. `next_cursor` is absent on the last page.
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+}
+
+impl<T> Page<T> {
+    pub fn new(items: Vec<T>, next_cursor: Option<String>) -> Self {
+        Self { items, next_cursor }
+    }
+
+    pub fn map<U>(self, f: impl FnMut(T) -> U) -> Page<U> {
+        Page {
+            items: self.items.into_iter().map(f).collect(),
+            next_cursor: self.next_cursor,
+        }
+    }
+}
+
+impl<T: Serialize> Page<T> {
+    /// Respond with the page, linking to the next one in the `Link` header. The
+    /// link repeats the query string of the request with the cursor replaced.
+    pub fn respond(&self, req: &HttpRequest) -> HttpResponse {
+        let mut builder = HttpResponse::Ok();
+
+        if let Some(cursor) = &self.next_cursor {
+            let query = url::form_urlencoded::Serializer::new(String::new())
+                .extend_pairs(
+                    url::form_urlencoded::parse(req.query_string().as_bytes())
+                        .filter(|(key, _)| key != "cursor"),
+                )
+                .append_pair("cursor", cursor)
+                .finish();
+            builder.insert_header((
+                header::LINK,
+                format!("<{}?{query}>; rel=\"next\"", req.path()),
+            ));
+        }
+
+        negotiation::respond(builder, self)
+    }
+}
@@ -0,0 +1,238 @@
+use super::{
+    cursor::{Cursor, CursorSigner},
+    Field, Listing, Page, PaginationError, Value,
+};
+use crate::configuration::auth::AuthSettings;
+use actix_web::{dev::Payload, web, FromRequest, HttpRequest};
+use futures_util::future::{ready, Ready};
+use sqlx::{Postgres, QueryBuilder};
+use std::{fmt::Debug, marker::PhantomData};
+use uuid::Uuid;
+
+const LIMIT: &str = "limit";
+const CURSOR: &str = "cursor";
+const SORT: &str = "sort";
+
+/// The validated query string of a list endpoint described by `L`.
+pub struct ListQuery<L: Listing> {
+    limit: i64,
+    sort: &'static Field,
+    descending: bool,
+    filters: Vec<(&'static Field, Value)>,
+    parameters: Vec<(String, String)>,
+    after: Option<(Value, Uuid)>,
+    signer: CursorSigner,
+    listing: PhantomData<L>,
+}
+
+impl<L: Listing> Debug for ListQuery<L> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ListQuery")
+            .field("limit", &self.limit)
+            .field("sort", &self.sort_param())
+            .field("filters", &self.filters)
+            .field("parameters", &self.parameters)
+            .field("after", &self.after)
+            .finish()
+    }
+}
+
+impl<L: Listing> ListQuery<L> {
+    /// Parse and validate a query string.
+    pub fn parse(query: &str, signer: CursorSigner) -> Result<Self, PaginationError> {
+        let mut limit = L::DEFAULT_LIMIT;
+        let mut cursor = None;
+        let mut sort = L::DEFAULT_SORT.to_owned();
+        let mut filters = Vec::new();
+        let mut parameters = Vec::new();
+
+        for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+            match key.as_ref() {
+                LIMIT => {
+                    limit = value
+                        .parse()
+                        .ok()
+                        .filter(|limit| (1..=L::MAX_LIMIT).contains(limit))
+                        .ok_or(PaginationError::InvalidLimit { max: L::MAX_LIMIT })?;
+                }
+                CURSOR => cursor = Some(value.into_owned()),
+                SORT => sort = value.into_owned(),
+                key if L::PARAMETERS.contains(&key) => {
+                    parameters.retain(|(name, _)| *name != key);
+                    parameters.push((key.to_owned(), value.into_owned()));
+                }
+                key => {
+                    let field = find(L::FILTERS, key)
+                        .ok_or_else(|| PaginationError::UnknownParameter(key.to_owned()))?;
+                    let value = Value::parse(field.kind, &value).map_err(|reason| {
+                        PaginationError::InvalidFilter {
+                            field: field.name.to_owned(),
+                            reason,
+                        }
+                    })?;
+                    filters.retain(|(filtered, _)| *filtered != field);
+                    filters.push((field, value));
+                }
+            }
+        }
+        filters.sort_by_key(|(field, _)| field.name);
+        parameters.sort();
+
+        let (name, descending) = match sort.strip_prefix('-') {
+            Some(name) => (name, true),
+            None => (sort.as_str(), false),
+        };
+        let sort_field =
+            find(L::SORTS, name).ok_or_else(|| PaginationError::UnknownSort(name.to_owned()))?;
+
+        let mut list = Self {
+            limit,
+            sort: sort_field,
+            descending,
+            filters,
+            parameters,
+            after: None,
+            signer,
+            listing: PhantomData,
+        };
+
+        if let Some(cursor) = cursor {
+            let cursor = list
+                .signer
+                .decode(&cursor, &list.filter_param())
+                .filter(|cursor| cursor.sort == list.sort_param())
+                .ok_or(PaginationError::InvalidCursor)?;
+            let value = Value::parse(list.sort.kind, &cursor.value)
+                .map_err(|_| PaginationError::InvalidCursor)?;
+            list.after = Some((value, cursor.id));
+        }
+
+        Ok(list)
+    }
+
+    /// Add the filters, the position of the cursor, the order and the limit to a
+    /// query. The query must end in a `WHERE` clause, to which the conditions
+    /// are added with `AND`.
+    pub fn push_to(&self, query: &mut QueryBuilder<'static, Postgres>) {
+        for (field, value) in &self.filters {
+            query.push(" AND ").push(field.column).push(" = ");
+            push_value(query, value);
+        }
+
+        let comparison = match self.descending {
+            true => "<",
+            false => ">",
+        };
+
+        if let Some((value, id)) = &self.after {
+            query
+                .push(" AND (")
+                .push(self.sort.column)
+                .push(", ")
+                .push(L::TIE_BREAKER)
+                .push(") ")
+                .push(comparison)
+                .push(" (");
+            push_value(query, value);
+            query.push(", ").push_bind(*id).push(")");
+        }
+
+        // Fetch one extra row to find out whether there is another page.
+        query.push(" ORDER BY ");
+        self.push_order(query);
+        query.push(" LIMIT ").push_bind(self.limit + 1);
+    }
+
+    /// Add the order of the rows to a query, without the `ORDER BY` keywords, so
+    /// that it can also be used in a window function.
+    pub fn push_order(&self, query: &mut QueryBuilder<'static, Postgres>) {
+        let direction = match self.descending {
+            true => "DESC",
+            false => "ASC",
+        };
+
+        query
+            .push(self.sort.column)
+            .push(" ")
+            .push(direction)
+            .push(", ")
+            .push(L::TIE_BREAKER)
+            .push(" ")
+            .push(direction);
+    }
+
+    /// Turn the rows fetched with a query built by [ListQuery::push_to] into a page.
+    pub fn page(&self, mut rows: Vec<L::Row>) -> Page<L::Row> {
+        let next_cursor = if rows.len() as i64 > self.limit {
+            rows.truncate(self.limit as usize);
+            rows.last().map(|row| {
+                let cursor = Cursor {
+                    sort: self.sort_param(),
+                    value: L::sort_value(row, self.sort).format(),
+                    id: L::tie_breaker(row),
+                };
+                self.signer.encode(&cursor, &self.filter_param())
+            })
+        } else {
+            None
+        };
+
+        Page::new(rows, next_cursor)
+    }
+
+    fn sort_param(&self) -> String {
+        match self.descending {
+            true => format!("-{}", self.sort.name),
+            false => self.sort.name.to_owned(),
+        }
+    }
+
+    /// The value of one of the [Listing::PARAMETERS], if it was given.
+    pub fn parameter(&self, name: &str) -> Option<&str> {
+        self.parameters
+            .iter()
+            .find(|(parameter, _)| *parameter == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The filters and parameters, which cursors are bound to.
+    fn filter_param(&self) -> String {
+        url::form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(
+                self.filters
+                    .iter()
+                    .map(|(field, value)| (field.name, value.format())),
+            )
+            .extend_pairs(&self.parameters)
+            .finish()
+    }
+}
+
+impl<L: Listing> FromRequest for ListQuery<L> {
+    type Error = PaginationError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let settings = req
+            .app_data::<web::Data<AuthSettings>>()
+            .expect("The auth settings are registered as app data");
+        let signer = CursorSigner::new(settings.cursorsecret.clone());
+
+        ready(Self::parse(req.query_string(), signer))
+    }
+}
+
+fn find(fields: &'static [Field], name: &str) -> Option<&'static Field> {
+    fields.iter().find(|field| field.name == name)
+}
+
+fn push_value(query: &mut QueryBuilder<'static, Postgres>, value: &Value) {
+    match value.clone() {
+        Value::Text(value) => query.push_bind(value),
+        Value::Integer(value) => query.push_bind(value),
+        Value::Real(value) => query.push_bind(value),
+        Value::Uuid(value) => query.push_bind(value),
+        Value::Timestamp(value) => query.push_bind(value),
+        Value::Boolean(value) => query.push_bind(value),
+    };
+}
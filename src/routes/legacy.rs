@@ -0,0 +1,61 @@
+//! Compatibility with clients written before the API was versioned, when every
+//! endpoint was served without a prefix, or even earlier under `/public` and
+//! `/private`. Their requests are redirected to the same path in version 1 with
+//! `308 Permanent Redirect`, which keeps the method and body of the request.
+
+use super::version::ApiVersion;
+use crate::error::ErrorResponse;
+use actix_web::http::header;
+use actix_web::{HttpRequest, HttpResponse};
+
+/// The first segments of the paths which used to be served without a prefix.
+const UNPREFIXED: [&str; 6] = [
+    "signup",
+    "signin",
+    "users",
+    "organizations",
+    "invitations",
+    "close",
+];
+
+/// The scopes which the routes were split into before they were served without a
+/// prefix.
+const SCOPES: [&str; 2] = ["/public", "/private"];
+
+/// The default service of the application. Requests which match no route are
+/// redirected if they used to be served, and are otherwise not found.
+#[tracing::instrument]
+pub async fn redirect(req: HttpRequest) -> HttpResponse {
+    let path = req.path();
+    let Some(unprefixed) = unprefixed(path) else {
+        return ErrorResponse::new("not_found").into_response(HttpResponse::NotFound());
+    };
+
+    let mut location = format!("{}{unprefixed}", ApiVersion::V1.prefix());
+    if !req.query_string().is_empty() {
+        location = format!("{location}?{}", req.query_string());
+    }
+    tracing::info!("Redirecting legacy path {path} to {location}");
+
+    HttpResponse::PermanentRedirect()
+        .insert_header((header::LOCATION, location))
+        .finish()
+}
+
+/// The path which `path` is served at without a prefix, if it used to be served.
+fn unprefixed(path: &str) -> Option<String> {
+    let path = SCOPES
+        .iter()
+        .find_map(|scope| {
+            path.strip_prefix(scope)
+                .filter(|rest| rest.starts_with('/'))
+        })
+        .unwrap_or(path);
+    // The user of the request used to be found at `/private/my_user`.
+    if path == "/my_user" {
+        return Some("/users/my_user".to_owned());
+    }
+
+    let segment = path.trim_start_matches('/').split('/').next()?;
+    UNPREFIXED.contains(&segment).then(|| path.to_owned())
+}
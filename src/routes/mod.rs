@@ -1,5 +1,27 @@
 //! The routing module is the meat of the application. It handles defining routes and
 //! directing requests to the proper handler functions in the `crate::domain` module.
+//!
+//! The API is served in every [version](version::ApiVersion) side by side, each
+//! under its own prefix. Operational endpoints such as the health check are not
+//! versioned.
 
+pub mod legacy;
 pub mod private;
 pub mod public;
+pub mod version;
+
+use crate::middleware::deprecation;
+use actix_web::web;
+use version::ApiVersion;
+
+pub fn api_services(cfg: &mut web::ServiceConfig) {
+    for version in ApiVersion::ALL {
+        cfg.service(
+            web::scope(version.prefix())
+                .app_data(version)
+                .wrap_fn(deprecation::announce)
+                .configure(private::private_services)
+                .configure(public::public_services),
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use crate::domain::user::events::{AccountEvent, EventError, EventHub};
+use crate::domain::user::privacy::Viewer;
+use crate::error::{self, ErrorResponse};
+use crate::middleware::auth::AuthError;
+use actix_web::http::{header, StatusCode};
+use actix_web::web::{self, Bytes};
+use actix_web::{HttpResponse, ResponseError};
+use std::convert::Infallible;
+use std::time::Duration;
+use tokio_stream::wrappers::IntervalStream;
+use tokio_stream::StreamExt;
+
+/// How often a comment is sent while there are no events, so that proxies keep
+/// the connection open.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+#[utoipa::path(
+    get,
+    path = "/users/my_user/events",
+    tag = "users",
+    summary = "Stream the events of the signed in user's account",
+    description = "A stream of server-sent events, one for each change to the account made from any device. The `event` field is the type of the event and its `data` is the event as JSON.",
+    responses(
+        (status = 200, description = "The events of the account, as they happen", body = String, content_type = "text/event-stream"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 503, description = "Events cannot be streamed for now", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument(skip(hub))]
+pub async fn account_events(
+    hub: web::Data<EventHub>,
+    viewer: web::ReqData<Viewer>,
+) -> Result<HttpResponse, EventError> {
+    let Some(id) = viewer.id() else {
+        return Ok(error::respond(&AuthError::InvalidCredentials));
+    };
+    tracing::info!("Streaming account events");
+
+    let events = match hub.subscribe(id).await {
+        Ok(events) => events.map(|event| Ok(frame(&event))),
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    };
+    let keep_alive = IntervalStream::new(actix_web::rt::time::interval(KEEP_ALIVE))
+        .map(|_| Ok::<_, Infallible>(Bytes::from_static(b": keep-alive\n\n")));
+
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        .streaming(events.merge(keep_alive)))
+}
+
+/// The server-sent event of `event`.
+fn frame(event: &AccountEvent) -> Bytes {
+    let data = serde_json::to_string(event).expect("Account events can be serialized");
+    Bytes::from(format!("event: {}\ndata: {data}\n\n", event.name()))
+}
+
+impl ResponseError for EventError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            EventError::NotListening => StatusCode::SERVICE_UNAVAILABLE,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&EventError> for ErrorResponse {
+    fn from(value: &EventError) -> Self {
+        match value {
+            EventError::NotListening => {
+                Self::new("events_unavailable").with_detail(value.to_string())
+            }
+        }
+    }
+}
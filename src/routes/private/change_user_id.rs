@@ -0,0 +1,114 @@
+use crate::configuration::user::UserSettings;
+use crate::domain::user::actions::ChangeUserIdError;
+use crate::domain::user::cache::UserCache;
+use crate::domain::user::{self, handle, BasicId};
+use crate::error::ErrorResponse;
+use crate::i18n::Message;
+use crate::negotiation::Payload;
+use crate::openapi::envelope::UserEnvelope;
+use crate::routes::version::ApiVersion;
+use crate::{database::Database, domain::user::dto::ChangeUserId};
+use actix_web::http::{header, StatusCode};
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/user_id",
+    tag = "users",
+    summary = "Change the user_id of the signed in user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    request_body = ChangeUserId,
+    responses(
+        (status = 200, description = "The user_id was changed", body = UserEnvelope),
+        (status = 400, description = "The new user_id is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Users may only change their own user_id", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 409, description = "The new user_id is taken", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 429, description = "The user_id was changed too recently", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn change_user_id(
+    db: web::Data<Database>,
+    cache: web::Data<UserCache>,
+    settings: web::Data<UserSettings>,
+    user_id: web::Path<String>,
+    change: Payload<ChangeUserId>,
+    requester_id: web::ReqData<BasicId>,
+    version: ApiVersion,
+) -> Result<HttpResponse, ChangeUserIdError> {
+    tracing::info!("Request to change user_id {:?}", &change);
+
+    if !handle::matches(requester_id.as_str(), user_id.as_str()) {
+        Err(ChangeUserIdError::Forbidden {
+            requester: requester_id.as_str().into(),
+            requested: user_id.as_str().to_owned(),
+        })?
+    }
+
+    match user::actions::change_user_id(&db, &cache, &settings, &requester_id, &change).await {
+        Ok(user) => {
+            tracing::info!("Request success: {user:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "User id successfully changed",
+                "user",
+                &user,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for ChangeUserIdError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ChangeUserIdError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ChangeUserIdError::NotFound(_) => StatusCode::NOT_FOUND,
+            ChangeUserIdError::Forbidden { .. } => StatusCode::FORBIDDEN,
+            ChangeUserIdError::Validation { .. } => StatusCode::BAD_REQUEST,
+            ChangeUserIdError::AlreadyExists(_) => StatusCode::CONFLICT,
+            ChangeUserIdError::TooSoon { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut builder = HttpResponse::build(self.status_code());
+
+        if let ChangeUserIdError::TooSoon { retry_after } = self {
+            builder.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+
+        ErrorResponse::from(self).into_response(builder)
+    }
+}
+
+impl From<&ChangeUserIdError> for ErrorResponse
+where
+    ChangeUserIdError: ResponseError,
+{
+    fn from(value: &ChangeUserIdError) -> Self {
+        match value {
+            ChangeUserIdError::DatabaseError(_) => Self::default(),
+            ChangeUserIdError::NotFound(_) => {
+                Self::new("user_not_found").with_detail(Message::new("detail-user-not-found"))
+            }
+            ChangeUserIdError::Forbidden { .. } => {
+                Self::new("forbidden").with_detail(value.to_string())
+            }
+            ChangeUserIdError::Validation { field, reason } => {
+                Self::new("validation_failed").with_field(field, reason)
+            }
+            ChangeUserIdError::AlreadyExists(..) => {
+                Self::new("user_id_taken").with_detail(Message::new("detail-user-id-taken"))
+            }
+            ChangeUserIdError::TooSoon { .. } => {
+                Self::new("user_id_changed_recently").with_detail(value.to_string())
+            }
+        }
+    }
+}
@@ -1,21 +1,37 @@
 use crate::database::Database;
+use crate::domain::user::cache::UserCache;
 use crate::domain::user::{self, BasicId};
-use crate::error::ErrorResponse;
+use crate::error::{self, ErrorResponse};
+use crate::openapi::envelope::Message;
+use crate::routes::version::ApiVersion;
 use actix_web::http::StatusCode;
 use actix_web::{web, HttpResponse, ResponseError};
 
+#[utoipa::path(
+    post,
+    path = "/close",
+    tag = "auth",
+    summary = "Delete the account of the signed in user",
+    responses(
+        (status = 200, description = "The account was deleted", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "The account was already deleted", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
 #[tracing::instrument]
 pub async fn close_account(
     db: web::Data<Database>,
+    cache: web::Data<UserCache>,
     requester_id: web::ReqData<BasicId>,
+    version: ApiVersion,
 ) -> Result<HttpResponse, user::actions::DeleteError> {
     tracing::info!("Requested to delete user {}", requester_id.as_str());
-    match user::actions::delete(&db, &requester_id).await {
+    match user::actions::delete(&db, &cache, &requester_id).await {
         Ok(user) => {
             tracing::info!("Request success: {user:?} deleted");
 
-            Ok(HttpResponse::Ok()
-                .json(serde_json::json!({"message": "Account and user successfully removed"})))
+            Ok(version.done("Account and user successfully removed"))
         }
         Err(e) => {
             tracing::error!("Request failure: {e}");
@@ -28,15 +44,12 @@ impl ResponseError for user::actions::DeleteError {
     fn status_code(&self) -> StatusCode {
         match self {
             user::actions::DeleteError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            user::actions::DeleteError::NotFound(_) => todo!(),
+            user::actions::DeleteError::NotFound(_) => StatusCode::NOT_FOUND,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        error::respond(self)
     }
 }
 
@@ -45,14 +58,9 @@ where
     user::actions::DeleteError: ResponseError,
 {
     fn from(value: &user::actions::DeleteError) -> Self {
-        let cause = match value {
-            user::actions::DeleteError::DatabaseError(_) => Some(ErrorResponse::default().message),
-            user::actions::DeleteError::NotFound(_) => todo!(),
-        };
-
-        Self {
-            cause,
-            message: "Failed to delete the account".into(),
+        match value {
+            user::actions::DeleteError::DatabaseError(_) => Self::default(),
+            user::actions::DeleteError::NotFound(_) => Self::new("user_not_found"),
         }
     }
 }
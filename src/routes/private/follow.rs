@@ -0,0 +1,82 @@
+use crate::database::Database;
+use crate::domain::user::actions::FollowError;
+use crate::domain::user::{self, privacy::Viewer};
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use crate::openapi::envelope::FollowEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/follow",
+    tag = "follows",
+    summary = "Follow a user, or request to follow them",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user was followed, or a request was sent", body = FollowEnvelope),
+        (status = 400, description = "Users cannot follow themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn follow(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, FollowError> {
+    tracing::info!("Request to follow user {:?}", user_id.as_str());
+
+    match user::actions::follow(&db, &viewer, &user_id).await {
+        Ok(follow) => {
+            tracing::info!("Request success: {follow:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "User successfully followed",
+                "follow",
+                &follow,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for FollowError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            FollowError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            FollowError::NotFound(_) => StatusCode::NOT_FOUND,
+            FollowError::SelfFollow => StatusCode::BAD_REQUEST,
+            FollowError::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&FollowError> for ErrorResponse
+where
+    FollowError: ResponseError,
+{
+    fn from(value: &FollowError) -> Self {
+        match value {
+            FollowError::DatabaseError(_) => Self::default(),
+            FollowError::NotFound(_) => {
+                Self::new("user_not_found").with_detail(Message::new("detail-user-not-found"))
+            }
+            FollowError::SelfFollow => Self::new("self_follow"),
+            FollowError::Unauthenticated => {
+                Self::new("unauthenticated").with_detail(value.to_string())
+            }
+        }
+    }
+}
@@ -1,19 +1,51 @@
 use crate::database::Database;
-use crate::domain::user;
-use actix_web::{web, HttpResponse};
+use crate::domain::user::{self, cache::UserCache, dto::GetUserResponse, privacy::Viewer};
+use crate::error::ErrorResponse;
+use crate::negotiation::{self, Format};
+use crate::openapi::envelope::UserEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::http::header::{
+    CacheControl, CacheDirective, ETag, EntityTag, HttpDate, IfModifiedSince, IfNoneMatch,
+    LastModified,
+};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::time::{Duration, SystemTime};
 
-#[tracing::instrument]
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}",
+    tag = "users",
+    summary = "Get a user as seen by the signed in user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user, leaving out fields the viewer may not see", body = UserEnvelope),
+        (status = 304, description = "The user has not changed since it was last read"),
+        (status = 308, description = "The user has changed their user_id", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument(skip(req))]
 pub async fn get_user(
+    req: HttpRequest,
     db: web::Data<Database>,
+    cache: web::Data<UserCache>,
     user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
 ) -> Result<HttpResponse, user::actions::GetOneError> {
     tracing::info!("User info requested for user: {:?}", &user_id.as_ref());
 
-    match user::actions::get_one_by_str_id(&db, &user_id).await {
+    match user::actions::get_one_by_str_id(&db, &cache, &user_id, &viewer).await {
         Ok(user) => {
             tracing::info!("Request success: {user:?}");
-            Ok(HttpResponse::Ok()
-                .json(serde_json::json!({"message": "User details by user_id", "user": user})))
+            let body = version.body("User details by user_id", "user", &user);
+            Ok(respond_conditionally(&req, &viewer, &user, &body))
         }
         Err(e) => {
             tracing::error!("Request failure: {e}");
@@ -22,6 +54,69 @@ pub async fn get_user(
     }
 }
 
+/// Respond with the user in the format the client accepts, or with `304 Not
+/// Modified` when the client already has the same representation. `If-None-Match` takes precedence over
+/// `If-Modified-Since`, which only covers changes to the user itself and not
+/// their follower counts. Caches must always revalidate, since the response
+/// depends on who is asking. Shared caches may only store responses which are
+/// the same for everyone and were not sent to a signed in viewer, since a
+/// shared cache would hand them to requests without their credentials.
+pub fn respond_conditionally(
+    req: &HttpRequest,
+    viewer: &Viewer,
+    user: &GetUserResponse,
+    body: &impl Serialize,
+) -> HttpResponse {
+    let format = Format::accepted(req);
+    let body = format.encode(body).expect("The user can be serialized");
+    let etag = representation_tag(user.version, &body);
+    let last_modified = http_date(user.updated_at);
+    let visibility = match user.shared && viewer.id().is_none() {
+        true => CacheDirective::Public,
+        false => CacheDirective::Private,
+    };
+
+    let not_modified = match req.get_header::<IfNoneMatch>() {
+        Some(IfNoneMatch::Any) => true,
+        Some(IfNoneMatch::Items(tags)) => tags.iter().any(|tag| tag.weak_eq(&etag)),
+        None => req
+            .get_header::<IfModifiedSince>()
+            .is_some_and(|since| SystemTime::from(last_modified) <= SystemTime::from(since.0)),
+    };
+
+    let mut builder = match not_modified {
+        true => HttpResponse::NotModified(),
+        false => HttpResponse::Ok(),
+    };
+    builder
+        .insert_header(ETag(etag))
+        .insert_header(LastModified(last_modified))
+        .insert_header(CacheControl(vec![visibility, CacheDirective::NoCache]));
+
+    let mut response = match not_modified {
+        true => builder.finish(),
+        false => builder.content_type(format.media_type()).body(body),
+    };
+    negotiation::vary(&mut response);
+    response
+}
+
+/// A strong tag for one representation of a user. It starts with the version of
+/// the user, so it can also be used in `If-Match` when updating them.
+fn representation_tag(version: i64, body: &[u8]) -> EntityTag {
+    let digest = Sha256::digest(body);
+    EntityTag::new_strong(format!(
+        "{version}-{}",
+        URL_SAFE_NO_PAD.encode(&digest[..12])
+    ))
+}
+
+/// HTTP dates only have a precision of seconds.
+fn http_date(value: NaiveDateTime) -> HttpDate {
+    let seconds = value.timestamp().max(0) as u64;
+    HttpDate::from(SystemTime::UNIX_EPOCH + Duration::from_secs(seconds))
+}
+
 // impl ResponseError for user::actions::GetOneError {
 //     fn status_code(&self) -> StatusCode {
 //         match self {
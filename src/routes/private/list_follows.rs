@@ -0,0 +1,153 @@
+use crate::database::Database;
+use crate::domain::user::actions::ListFollowsError;
+use crate::domain::user::dto::GetUserResponse;
+use crate::domain::user::follow::{FollowList, FollowListing};
+use crate::domain::user::{self, privacy::Viewer, BasicId};
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use crate::pagination::{ListQuery, Page};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/followers",
+    tag = "follows",
+    summary = "List the followers of a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user"),
+        ("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "A page of followers, linked to the next one in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The followers of this user are private", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_followers(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    query: ListQuery<FollowListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ListFollowsError> {
+    list(&db, &user_id, FollowList::Followers, &query, &viewer, &req).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/{user_id}/following",
+    tag = "follows",
+    summary = "List the users a user follows",
+    params(("user_id" = String, Path, description = "The `user_id` of the user"),
+        ("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "A page of followed users, linked to the next one in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The users this user follows are private", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_following(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    query: ListQuery<FollowListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ListFollowsError> {
+    list(&db, &user_id, FollowList::Following, &query, &viewer, &req).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/my_user/follow_requests",
+    tag = "follows",
+    summary = "List the pending follow requests of the signed in user",
+    params(("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "A page of users who asked to follow, linked to the next one in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_follow_requests(
+    db: web::Data<Database>,
+    user_id: web::ReqData<BasicId>,
+    query: ListQuery<FollowListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ListFollowsError> {
+    list(
+        &db,
+        user_id.as_str(),
+        FollowList::Requests,
+        &query,
+        &viewer,
+        &req,
+    )
+    .await
+}
+
+async fn list(
+    db: &Database,
+    user_id: &str,
+    list: FollowList,
+    query: &ListQuery<FollowListing>,
+    viewer: &Viewer,
+    req: &HttpRequest,
+) -> Result<HttpResponse, ListFollowsError> {
+    tracing::info!("{list:?} requested for user: {user_id:?}");
+
+    match user::actions::list_follows(db, viewer, user_id, list, query).await {
+        Ok(page) => {
+            tracing::info!("Request success: {page:?}");
+            Ok(page.respond(req))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            Err(e)
+        }
+    }
+}
+
+impl ResponseError for ListFollowsError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ListFollowsError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ListFollowsError::NotFound(_) => StatusCode::NOT_FOUND,
+            ListFollowsError::Forbidden => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&ListFollowsError> for ErrorResponse
+where
+    ListFollowsError: ResponseError,
+{
+    fn from(value: &ListFollowsError) -> Self {
+        let problem = match value {
+            ListFollowsError::DatabaseError(_) => return Self::default(),
+            ListFollowsError::NotFound(_) => {
+                return Self::new("user_not_found")
+                    .with_detail(Message::new("detail-user-not-found"))
+            }
+            ListFollowsError::Forbidden => Self::new("forbidden"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -0,0 +1,107 @@
+use crate::database::Database;
+use crate::domain::user::actions::ManageFollowerError;
+use crate::domain::user::{self, privacy::Viewer};
+use crate::error::{self, ErrorResponse};
+use crate::openapi::envelope::Message;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    put,
+    path = "/users/my_user/followers/{follower_id}",
+    tag = "follows",
+    summary = "Approve a follow request",
+    params(("follower_id" = String, Path, description = "The `user_id` of the follower")),
+    responses(
+        (status = 200, description = "The follower was approved", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No follow request from this user exists", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn approve_follower(
+    db: web::Data<Database>,
+    follower_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, ManageFollowerError> {
+    tracing::info!("Request to approve follower {:?}", follower_id.as_str());
+
+    match user::actions::approve_follower(&db, &viewer, &follower_id).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done("Follower successfully approved"))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/my_user/followers/{follower_id}",
+    tag = "follows",
+    summary = "Remove a follower or decline their request",
+    params(("follower_id" = String, Path, description = "The `user_id` of the follower")),
+    responses(
+        (status = 200, description = "The follower was removed", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "This user does not follow the signed in user", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn remove_follower(
+    db: web::Data<Database>,
+    follower_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, ManageFollowerError> {
+    tracing::info!("Request to remove follower {:?}", follower_id.as_str());
+
+    match user::actions::remove_follower(&db, &viewer, &follower_id).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done("Follower successfully removed"))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for ManageFollowerError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            ManageFollowerError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            ManageFollowerError::NotFound(_) => StatusCode::NOT_FOUND,
+            ManageFollowerError::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&ManageFollowerError> for ErrorResponse
+where
+    ManageFollowerError: ResponseError,
+{
+    fn from(value: &ManageFollowerError) -> Self {
+        match value {
+            ManageFollowerError::DatabaseError(_) => Self::default(),
+            ManageFollowerError::NotFound(_) => {
+                Self::new("follower_not_found").with_detail(value.to_string())
+            }
+            ManageFollowerError::Unauthenticated => {
+                Self::new("unauthenticated").with_detail(value.to_string())
+            }
+        }
+    }
+}
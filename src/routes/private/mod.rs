@@ -1,23 +1,153 @@
 //! Responsible for all endpoints that require authentication.
 
-use crate::middleware::auth::process_basic;
+use crate::middleware::auth::{process_basic, validator};
+use crate::middleware::tenant;
 use actix_web::web::{self};
 
 use actix_web_httpauth::middleware::HttpAuthentication;
+use utoipa::OpenApi;
 
+mod account_events;
+mod change_user_id;
 mod close_account;
+mod follow;
 mod get_user;
+mod list_follows;
+mod manage_follower;
 mod my_user;
+mod organization;
 mod patch_user;
+mod restrict;
+mod search_users;
+mod unfollow;
+mod update_privacy;
+mod upload_avatar;
+mod webhook;
+
+pub use organization::OrganizationApi;
+pub use webhook::WebhookApi;
+
+/// The OpenAPI description of the endpoints which require authentication.
+#[derive(OpenApi)]
+#[openapi(paths(
+    search_users::search_users,
+    my_user::my_user,
+    account_events::account_events,
+    upload_avatar::upload_avatar,
+    list_follows::list_follow_requests,
+    restrict::list_blocks,
+    restrict::list_mutes,
+    manage_follower::approve_follower,
+    manage_follower::remove_follower,
+    get_user::get_user,
+    patch_user::patch_user,
+    change_user_id::change_user_id,
+    update_privacy::update_privacy,
+    follow::follow,
+    unfollow::unfollow,
+    restrict::block,
+    restrict::unblock,
+    restrict::mute,
+    restrict::unmute,
+    list_follows::list_followers,
+    list_follows::list_following,
+    close_account::close_account,
+))]
+pub struct PrivateApi;
 
 pub fn private_services(cfg: &mut web::ServiceConfig) {
     cfg.service(
         web::scope("/users")
             // .wrap(HttpAuthentication::bearer(validator))
             .wrap(HttpAuthentication::with_fn(process_basic))
+            .route("", web::get().to(search_users::search_users))
+            .route("/my_user", web::get().to(my_user::my_user))
+            .route(
+                "/my_user/events",
+                web::get().to(account_events::account_events),
+            )
+            .route(
+                "/my_user/avatar",
+                web::put().to(upload_avatar::upload_avatar),
+            )
+            .route(
+                "/my_user/follow_requests",
+                web::get().to(list_follows::list_follow_requests),
+            )
+            .route("/my_user/blocks", web::get().to(restrict::list_blocks))
+            .route("/my_user/mutes", web::get().to(restrict::list_mutes))
+            .route(
+                "/my_user/followers/{follower_id}",
+                web::put().to(manage_follower::approve_follower),
+            )
+            .route(
+                "/my_user/followers/{follower_id}",
+                web::delete().to(manage_follower::remove_follower),
+            )
             .route("/{user_id}", web::get().to(get_user::get_user))
             .route("/{user_id}", web::patch().to(patch_user::patch_user))
-            .route("/my_user", web::get().to(my_user::my_user)),
+            .route(
+                "/{user_id}/user_id",
+                web::put().to(change_user_id::change_user_id),
+            )
+            .route(
+                "/{user_id}/privacy",
+                web::put().to(update_privacy::update_privacy),
+            )
+            .route("/{user_id}/follow", web::put().to(follow::follow))
+            .route("/{user_id}/follow", web::delete().to(unfollow::unfollow))
+            .route("/{user_id}/block", web::put().to(restrict::block))
+            .route("/{user_id}/block", web::delete().to(restrict::unblock))
+            .route("/{user_id}/mute", web::put().to(restrict::mute))
+            .route("/{user_id}/mute", web::delete().to(restrict::unmute))
+            .route(
+                "/{user_id}/followers",
+                web::get().to(list_follows::list_followers),
+            )
+            .route(
+                "/{user_id}/following",
+                web::get().to(list_follows::list_following),
+            ),
+    )
+    .service(
+        web::scope("/organizations")
+            .wrap_fn(tenant::commit)
+            .wrap(HttpAuthentication::bearer(validator))
+            .route("", web::post().to(organization::create_organization))
+            .route("", web::get().to(organization::list_organizations))
+            .route("/active", web::put().to(organization::switch_organization))
+            .route("/active/members", web::get().to(organization::list_members))
+            .route(
+                "/active/members/{user_id}",
+                web::delete().to(organization::remove_member),
+            )
+            .route(
+                "/active/invitations",
+                web::post().to(organization::create_invitation),
+            ),
+    )
+    .service(
+        web::scope("/webhooks")
+            .wrap(HttpAuthentication::with_fn(process_basic))
+            .route("", web::post().to(webhook::create_subscription))
+            .route("", web::get().to(webhook::list_subscriptions))
+            .route("/deliveries", web::get().to(webhook::list_deliveries))
+            .route(
+                "/deliveries/{delivery_id}/replay",
+                web::post().to(webhook::replay_delivery),
+            )
+            .route(
+                "/{subscription_id}",
+                web::delete().to(webhook::delete_subscription),
+            ),
+    )
+    .service(
+        web::scope("/invitations")
+            .wrap(HttpAuthentication::bearer(validator))
+            .route(
+                "/{token}/accept",
+                web::post().to(organization::accept_invitation),
+            ),
     )
     .service(
         web::scope("/close").route(
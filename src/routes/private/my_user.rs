@@ -1,21 +1,37 @@
+use super::get_user::respond_conditionally;
 use crate::database::Database;
-use crate::domain::user;
+use crate::domain::user::{self, cache::UserCache, privacy::Viewer, BasicId};
 use crate::error::ErrorResponse;
+use actix_web::http::header;
 use actix_web::http::StatusCode;
-use actix_web::{web, HttpResponse, ResponseError};
-use uuid::Uuid;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
 
-#[tracing::instrument]
+#[utoipa::path(
+    get,
+    path = "/users/my_user",
+    tag = "users",
+    summary = "Get the signed in user",
+    responses(
+        (status = 200, description = "The signed in user", body = user::dto::GetUserResponse),
+        (status = 304, description = "The user has not changed since it was last read"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument(skip(req))]
 pub async fn my_user(
+    req: HttpRequest,
     db: web::Data<Database>,
-    user_id: web::ReqData<Uuid>,
+    cache: web::Data<UserCache>,
+    user_id: web::ReqData<BasicId>,
+    viewer: web::ReqData<Viewer>,
 ) -> Result<HttpResponse, user::actions::GetOneError> {
-    tracing::info!("User info requested for user: {:?}", &user_id.as_ref());
+    tracing::info!("User info requested for user: {:?}", user_id.as_str());
 
-    match user::actions::get_one(&db, &user_id.into_inner()).await {
+    match user::actions::get_one_by_str_id(&db, &cache, user_id.as_str(), &viewer).await {
         Ok(user) => {
             tracing::info!("Request success: {user:?}");
-            Ok(HttpResponse::Ok().json(user))
+            Ok(respond_conditionally(&req, &viewer, &user, &user))
         }
         Err(e) => {
             tracing::error!("Request failure: {e}");
@@ -28,15 +44,21 @@ impl ResponseError for user::actions::GetOneError {
     fn status_code(&self) -> StatusCode {
         match self {
             user::actions::GetOneError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
-            user::actions::GetOneError::NotFound(_) => StatusCode::BAD_REQUEST,
+            user::actions::GetOneError::NotFound(_) => StatusCode::NOT_FOUND,
+            user::actions::GetOneError::Moved(_) => StatusCode::PERMANENT_REDIRECT,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        let mut builder = HttpResponse::build(self.status_code());
+
+        if let user::actions::GetOneError::Moved(user_id) = self {
+            // Relative to the path which was requested, so that the client stays
+            // in the same version of the API.
+            builder.insert_header((header::LOCATION, format!("./{user_id}")));
+        }
+
+        ErrorResponse::from(self).into_response(builder)
     }
 }
 
@@ -45,14 +67,12 @@ where
     user::actions::GetOneError: ResponseError,
 {
     fn from(value: &user::actions::GetOneError) -> Self {
-        let cause = match value {
-            user::actions::GetOneError::DatabaseError(_) => ErrorResponse::default().cause,
-            user::actions::GetOneError::NotFound(_) => None,
-        };
-
-        Self {
-            cause,
-            message: "No User found".into(),
+        match value {
+            user::actions::GetOneError::DatabaseError(_) => Self::default(),
+            user::actions::GetOneError::NotFound(_) => Self::new("user_not_found"),
+            user::actions::GetOneError::Moved(_) => {
+                Self::new("user_moved").with_detail(value.to_string())
+            }
         }
     }
 }
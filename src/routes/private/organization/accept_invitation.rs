@@ -0,0 +1,48 @@
+use crate::database::Database;
+use crate::domain::organization::{self, actions::InvitationError};
+use crate::error::ErrorResponse;
+use crate::openapi::envelope::OrganizationEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    post,
+    path = "/invitations/{token}/accept",
+    tag = "organizations",
+    summary = "Join an organization with an invitation",
+    description = "The token is a bearer credential: accounts have no email address to compare with the one an invitation was addressed to, so whoever presents the token first joins. An invitation with an email can be accepted once, and a link invitation by anyone until it expires.",
+    params(("token" = String, Path, description = "The token of the invitation")),
+    responses(
+        (status = 200, description = "The user joined the organization", body = OrganizationEnvelope),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "The invitation does not exist", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 410, description = "The invitation has expired", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument(skip(token))]
+pub async fn accept_invitation(
+    db: web::Data<Database>,
+    user_id: web::ReqData<Uuid>,
+    token: web::Path<String>,
+    version: ApiVersion,
+) -> Result<HttpResponse, InvitationError> {
+    tracing::info!("Request to accept invitation");
+
+    match organization::actions::accept_invitation(&db, &user_id, &token).await {
+        Ok(organization) => {
+            tracing::info!("Request success: {organization:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "Invitation successfully accepted",
+                "organization",
+                &organization,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
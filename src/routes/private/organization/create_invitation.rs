@@ -0,0 +1,95 @@
+use crate::configuration::organization::OrganizationSettings;
+use crate::domain::organization::{self, actions::InvitationError, dto::CreateInvitation};
+use crate::error::{self, ErrorResponse};
+use crate::middleware::organization::ActiveMembership;
+use crate::middleware::tenant::RequestTransaction;
+use crate::negotiation::Payload;
+use crate::openapi::envelope::InvitationEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    post,
+    path = "/organizations/active/invitations",
+    tag = "organizations",
+    summary = "Invite someone to the active organization",
+    request_body = CreateInvitation,
+    responses(
+        (status = 201, description = "The invitation was created", body = InvitationEnvelope),
+        (status = 400, description = "The invitation is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user may not invite members", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn create_invitation(
+    tx: RequestTransaction,
+    settings: web::Data<OrganizationSettings>,
+    membership: ActiveMembership,
+    invitation: Payload<CreateInvitation>,
+    version: ApiVersion,
+) -> Result<HttpResponse, InvitationError> {
+    tracing::info!("Request to create invitation {:?}", &invitation);
+
+    match organization::actions::create_invitation(
+        &mut *tx.lock().await,
+        &settings,
+        &membership,
+        &invitation,
+    )
+    .await
+    {
+        Ok(invitation) => {
+            tracing::info!("Request success: {:?}", invitation.id);
+            Ok(version.resource(
+                HttpResponse::Created(),
+                "Invitation successfully created",
+                "invitation",
+                &invitation,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for InvitationError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            InvitationError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            InvitationError::NotFound => StatusCode::NOT_FOUND,
+            InvitationError::Expired => StatusCode::GONE,
+            InvitationError::AlreadyAccepted => StatusCode::GONE,
+            InvitationError::Forbidden => StatusCode::FORBIDDEN,
+            InvitationError::Validation { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&InvitationError> for ErrorResponse
+where
+    InvitationError: ResponseError,
+{
+    fn from(value: &InvitationError) -> Self {
+        let problem = match value {
+            InvitationError::DatabaseError(_) => return Self::default(),
+            InvitationError::Validation { field, reason } => {
+                return Self::new("validation_failed").with_field(field, reason)
+            }
+            InvitationError::NotFound => Self::new("invitation_not_found"),
+            InvitationError::Expired => Self::new("invitation_expired"),
+            InvitationError::AlreadyAccepted => Self::new("invitation_accepted"),
+            InvitationError::Forbidden => Self::new("forbidden"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
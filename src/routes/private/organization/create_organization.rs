@@ -0,0 +1,89 @@
+use crate::database::TenantScope;
+use crate::domain::organization::{self, actions::OrganizationError, dto::CreateOrganization};
+use crate::error::{self, ErrorResponse};
+use crate::middleware::tenant::RequestTransaction;
+use crate::negotiation::Payload;
+use crate::openapi::envelope::OrganizationEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{HttpResponse, ResponseError};
+
+#[utoipa::path(
+    post,
+    path = "/organizations",
+    tag = "organizations",
+    summary = "Create an organization owned by the signed in user",
+    request_body = CreateOrganization,
+    responses(
+        (status = 201, description = "The organization was created", body = OrganizationEnvelope),
+        (status = 400, description = "The name is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn create_organization(
+    tx: RequestTransaction,
+    scope: TenantScope,
+    organization: Payload<CreateOrganization>,
+    version: ApiVersion,
+) -> Result<HttpResponse, OrganizationError> {
+    tracing::info!("Request to create organization {:?}", &organization);
+
+    match organization::actions::create(&mut *tx.lock().await, &scope, &organization).await {
+        Ok(organization) => {
+            tracing::info!("Request success: {organization:?}");
+            Ok(version.resource(
+                HttpResponse::Created(),
+                "Organization successfully created",
+                "organization",
+                &organization,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for OrganizationError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            OrganizationError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OrganizationError::NotMember => StatusCode::FORBIDDEN,
+            OrganizationError::NoActiveOrganization => StatusCode::FORBIDDEN,
+            OrganizationError::Forbidden(_) => StatusCode::FORBIDDEN,
+            OrganizationError::MemberNotFound(_) => StatusCode::NOT_FOUND,
+            OrganizationError::Validation { .. } => StatusCode::BAD_REQUEST,
+            OrganizationError::JwtError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            OrganizationError::TransactionInUse => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&OrganizationError> for ErrorResponse
+where
+    OrganizationError: ResponseError,
+{
+    fn from(value: &OrganizationError) -> Self {
+        let problem = match value {
+            OrganizationError::DatabaseError(_)
+            | OrganizationError::JwtError(_)
+            | OrganizationError::TransactionInUse => return Self::default(),
+            OrganizationError::Validation { field, reason } => {
+                return Self::new("validation_failed").with_field(field, reason)
+            }
+            OrganizationError::NotMember => Self::new("not_member"),
+            OrganizationError::NoActiveOrganization => Self::new("no_active_organization"),
+            OrganizationError::Forbidden(_) => Self::new("forbidden"),
+            OrganizationError::MemberNotFound(_) => Self::new("member_not_found"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::domain::organization::{self, actions::OrganizationError, dto::MemberListing};
+use crate::error::ErrorResponse;
+use crate::middleware::organization::ActiveMembership;
+use crate::middleware::tenant::RequestTransaction;
+use crate::pagination::{ListQuery, Page};
+use actix_web::{HttpRequest, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/organizations/active/members",
+    tag = "organizations",
+    summary = "List the members of the active organization",
+    params(("limit" = Option<i64>, Query, description = "The number of members per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts"),
+        ("sort" = Option<String>, Query, description = "`joined_at` or `user_id`, prefixed with `-` to reverse the order"),
+        ("role" = Option<organization::MemberRole>, Query, description = "Only list members with this role")),
+    responses(
+        (status = 200, description = "A page of members, linked to the next one in the Link header", body = Page<organization::dto::MemberResponse>),
+        (status = 400, description = "The query is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "No organization is active", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_members(
+    tx: RequestTransaction,
+    membership: ActiveMembership,
+    list: ListQuery<MemberListing>,
+    req: HttpRequest,
+) -> Result<HttpResponse, OrganizationError> {
+    tracing::info!(
+        "Members requested for organization: {:?}",
+        membership.organization_id
+    );
+
+    match organization::actions::list_members(&mut *tx.lock().await, &membership, &list).await {
+        Ok(page) => {
+            tracing::info!("Request success: {page:?}");
+            Ok(page.respond(&req))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use crate::database::TenantScope;
+use crate::domain::organization::{self, actions::OrganizationError};
+use crate::error::ErrorResponse;
+use crate::middleware::tenant::RequestTransaction;
+use crate::negotiation;
+use crate::openapi::envelope::OrganizationList;
+use actix_web::HttpResponse;
+
+#[utoipa::path(
+    get,
+    path = "/organizations",
+    tag = "organizations",
+    summary = "List the organizations of the signed in user",
+    responses(
+        (status = 200, description = "The organizations along with the role of the user", body = OrganizationList),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_organizations(
+    tx: RequestTransaction,
+    scope: TenantScope,
+) -> Result<HttpResponse, OrganizationError> {
+    tracing::info!("Organizations requested for user: {:?}", scope.user_id);
+
+    match organization::actions::list(&mut *tx.lock().await, &scope).await {
+        Ok(organizations) => {
+            tracing::info!("Request success: {organizations:?}");
+            Ok(negotiation::respond(
+                HttpResponse::Ok(),
+                &serde_json::json!({ "organizations": organizations }),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
@@ -0,0 +1,33 @@
+//! Endpoints for managing organizations. They authenticate with the JWT issued at
+//! signin, which also carries the organization the user is acting in.
+
+mod accept_invitation;
+mod create_invitation;
+mod create_organization;
+mod list_members;
+mod list_organizations;
+mod remove_member;
+mod switch_organization;
+
+use utoipa::OpenApi;
+
+pub use accept_invitation::accept_invitation;
+pub use create_invitation::create_invitation;
+pub use create_organization::create_organization;
+pub use list_members::list_members;
+pub use list_organizations::list_organizations;
+pub use remove_member::remove_member;
+pub use switch_organization::switch_organization;
+
+/// The OpenAPI description of the endpoints under `/organizations`.
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_organization::create_organization,
+    list_organizations::list_organizations,
+    switch_organization::switch_organization,
+    list_members::list_members,
+    create_invitation::create_invitation,
+    accept_invitation::accept_invitation,
+    remove_member::remove_member,
+))]
+pub struct OrganizationApi;
@@ -0,0 +1,42 @@
+use crate::domain::organization::{self, actions::OrganizationError};
+use crate::error::ErrorResponse;
+use crate::middleware::organization::ActiveMembership;
+use crate::middleware::tenant::RequestTransaction;
+use crate::openapi::envelope::Message;
+use crate::routes::version::ApiVersion;
+use actix_web::{web, HttpResponse};
+
+#[utoipa::path(
+    delete,
+    path = "/organizations/active/members/{user_id}",
+    tag = "organizations",
+    summary = "Remove a member from the active organization",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The member was removed", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user may not remove this member", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No member with this user_id exists", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn remove_member(
+    tx: RequestTransaction,
+    membership: ActiveMembership,
+    user_id: web::Path<String>,
+    version: ApiVersion,
+) -> Result<HttpResponse, OrganizationError> {
+    tracing::info!("Request to remove member {:?}", user_id.as_str());
+
+    match organization::actions::remove_member(&mut *tx.lock().await, &membership, &user_id).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done("Member successfully removed"))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
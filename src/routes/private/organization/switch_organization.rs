@@ -0,0 +1,53 @@
+use crate::configuration::auth::AuthSettings;
+use crate::database::TenantScope;
+use crate::domain::organization::{self, actions::OrganizationError, dto::SwitchOrganization};
+use crate::error::ErrorResponse;
+use crate::middleware::tenant::RequestTransaction;
+use crate::negotiation::{self, Payload};
+use crate::openapi::envelope::Token;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+
+#[utoipa::path(
+    put,
+    path = "/organizations/active",
+    tag = "organizations",
+    summary = "Switch the organization the signed in user acts in",
+    request_body = SwitchOrganization,
+    responses(
+        (status = 200, description = "A new JWT for the organization", body = Token),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not a member of the organization", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("bearer" = [])),
+)]
+#[tracing::instrument]
+pub async fn switch_organization(
+    tx: RequestTransaction,
+    settings: web::Data<AuthSettings>,
+    scope: TenantScope,
+    switch: Payload<SwitchOrganization>,
+) -> Result<HttpResponse, OrganizationError> {
+    tracing::info!("Request to switch organization: {:?}", &switch);
+
+    match organization::actions::switch(
+        &mut *tx.lock().await,
+        &scope,
+        switch.organization_id.as_ref(),
+        &settings.jwtsecret,
+    )
+    .await
+    {
+        Ok(jwt) => {
+            tracing::info!("Request success");
+            Ok(negotiation::respond(
+                HttpResponse::Ok(),
+                &json!({"token": jwt}),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
@@ -1,32 +1,85 @@
-use crate::domain::user::actions::UpdateError;
-use crate::domain::user::{self, BasicId};
-use crate::error::ErrorResponse;
-use crate::{database::Database, domain::user::dto::UpdateUserDto};
+use crate::configuration::user::UserSettings;
+use crate::domain::user::actions::{ExpectedVersion, UpdateError};
+use crate::domain::user::cache::UserCache;
+use crate::domain::user::{self, handle, BasicId};
+use crate::error::{self, ErrorResponse};
+use crate::negotiation::{self, Format};
+use crate::openapi::envelope::UpdatedUserEnvelope;
+use crate::routes::version::ApiVersion;
+use crate::{database::Database, domain::user::patch::ProfileChange};
+use actix_web::http::header::{ETag, EntityTag, IfMatch};
 use actix_web::http::StatusCode;
-use actix_web::{web, HttpResponse, ResponseError};
+use actix_web::{web, HttpMessage, HttpRequest, HttpResponse, ResponseError};
 
+const MERGE_PATCH: &str = "application/merge-patch+json";
+const JSON_PATCH: &str = "application/json-patch+json";
+
+#[utoipa::path(
+    patch,
+    path = "/users/{user_id}",
+    tag = "users",
+    summary = "Update the profile of the signed in user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user"), ("If-Match" = Option<String>, Header, description = "Only update the user if it still has one of these entity tags")),
+    request_body(
+        content(
+            (user::dto::UpdateUserDto = "application/json"),
+            (user::dto::UpdateUserDto = "application/merge-patch+json"),
+            (Vec<user::patch::PatchOperation> = "application/json-patch+json"),
+        )
+    ),
+    responses(
+        (status = 200, description = "The user was updated", body = UpdatedUserEnvelope),
+        (status = 400, description = "A field is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Users may only update themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 409, description = "A test operation of the JSON Patch failed", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 412, description = "The user has changed since it was last read", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 415, description = "The content type is not supported", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 428, description = "The If-Match header is required", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
 #[tracing::instrument]
 pub async fn patch_user(
     db: web::Data<Database>,
+    cache: web::Data<UserCache>,
+    settings: web::Data<UserSettings>,
     user_id: web::Path<String>,
-    update_user: web::Json<UpdateUserDto>,
+    req: HttpRequest,
+    body: web::Bytes,
     requester_id: web::ReqData<BasicId>,
 ) -> Result<HttpResponse, user::actions::UpdateError> {
-    tracing::info!("Request to update user {:?}", &update_user);
-
-    if requester_id.as_str() != user_id.as_str() {
+    let version = ApiVersion::of(&req);
+    // Other users learn nothing about the body, not even whether it is valid.
+    if !handle::matches(requester_id.as_str(), user_id.as_str()) {
         Err(UpdateError::Forbidden {
             requester: requester_id.as_str().into(),
             requested: user_id.as_str().to_owned(),
         })?
     }
 
-    match user::actions::update_user(&db, &user_id, &update_user).await {
+    let change = profile_change(&req, &body)?;
+    tracing::info!("Request to update user {:?}", &change);
+
+    let expected = req
+        .get_header::<IfMatch>()
+        .map(|if_match| expected_version(&if_match));
+    if expected.is_none() && settings.require_if_match {
+        Err(UpdateError::PreconditionRequired)?
+    }
+
+    match user::actions::update_user(&db, &cache, &user_id, &change, expected.as_ref()).await {
         Ok(user) => {
             tracing::info!("Request success: {user:?}");
-            let users = vec![user];
-            Ok(HttpResponse::Ok()
-                .json(serde_json::json!({"message": "User successfully updated", "recipe": users})))
+            let etag = ETag(version_tag(user.version));
+            let body = match version {
+                // The first version returns the user in a list named `recipe`.
+                ApiVersion::V1 => version.body("User successfully updated", "recipe", &[user]),
+                _ => version.body("User successfully updated", "user", &user),
+            };
+            let mut builder = HttpResponse::Ok();
+            builder.insert_header(etag);
+            Ok(negotiation::respond(builder, &body))
         }
         Err(e) => {
             tracing::error!("Request failure: {e}");
@@ -35,6 +88,59 @@ pub async fn patch_user(
     }
 }
 
+/// Read the body according to its content type. Plain JSON, MessagePack and CBOR
+/// are treated as merge patches.
+fn profile_change(req: &HttpRequest, body: &[u8]) -> Result<ProfileChange, UpdateError> {
+    let content_type = req
+        .mime_type()
+        .map_err(|_| UpdateError::UnsupportedMediaType("invalid".into()))?
+        .map(|mime| mime.essence_str().to_owned())
+        .unwrap_or_default();
+
+    let invalid = |e: serde_json::Error| UpdateError::InvalidPayload(e.to_string());
+    match content_type.as_str() {
+        "application/json" | MERGE_PATCH => serde_json::from_slice(body)
+            .map(ProfileChange::Merge)
+            .map_err(invalid),
+        JSON_PATCH => serde_json::from_slice(body)
+            .map(ProfileChange::Patch)
+            .map_err(invalid),
+        other => match Format::from_media_type(other) {
+            Some(format @ (Format::MessagePack | Format::Cbor)) => format
+                .decode(body)
+                .map(ProfileChange::Merge)
+                .map_err(UpdateError::InvalidPayload),
+            _ => Err(UpdateError::UnsupportedMediaType(content_type)),
+        },
+    }
+}
+
+/// The entity tag of a version of a user.
+fn version_tag(version: i64) -> EntityTag {
+    EntityTag::new_strong(version.to_string())
+}
+
+/// The versions listed in an `If-Match` header. Tags which were not issued by
+/// [version_tag], or for a representation of the user when reading it, never
+/// match.
+fn expected_version(if_match: &IfMatch) -> ExpectedVersion {
+    match if_match {
+        IfMatch::Any => ExpectedVersion::Any,
+        IfMatch::Items(tags) => ExpectedVersion::OneOf(
+            tags.iter()
+                .filter(|tag| !tag.weak)
+                .filter_map(|tag| {
+                    let tag = tag.tag();
+                    tag.split_once('-')
+                        .map_or(tag, |(version, _)| version)
+                        .parse()
+                        .ok()
+                })
+                .collect(),
+        ),
+    }
+}
+
 impl ResponseError for user::actions::UpdateError {
     fn status_code(&self) -> StatusCode {
         match self {
@@ -42,14 +148,18 @@ impl ResponseError for user::actions::UpdateError {
             user::actions::UpdateError::GetOneError(e) => e.status_code(),
             user::actions::UpdateError::Forbidden { .. } => StatusCode::FORBIDDEN,
             user::actions::UpdateError::Validation { .. } => StatusCode::BAD_REQUEST,
+            user::actions::UpdateError::PreconditionFailed => StatusCode::PRECONDITION_FAILED,
+            user::actions::UpdateError::PreconditionRequired => StatusCode::PRECONDITION_REQUIRED,
+            user::actions::UpdateError::TestFailed(_) => StatusCode::CONFLICT,
+            user::actions::UpdateError::InvalidPayload(_) => StatusCode::BAD_REQUEST,
+            user::actions::UpdateError::UnsupportedMediaType(_) => {
+                StatusCode::UNSUPPORTED_MEDIA_TYPE
+            }
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        error::respond(self)
     }
 }
 
@@ -58,20 +168,20 @@ where
     user::actions::UpdateError: ResponseError,
 {
     fn from(value: &user::actions::UpdateError) -> Self {
-        let cause = match value {
-            user::actions::UpdateError::DatabaseError(_) => Some(ErrorResponse::default().message),
-            user::actions::UpdateError::GetOneError(e) => Some(e.to_string()),
-            user::actions::UpdateError::Forbidden { .. } => Some("Unauthorized".into()),
-            user::actions::UpdateError::Validation { field, reason } => {
-                Some(format!("Submission for field {field} is invalid: {reason}"))
+        let problem = match value {
+            UpdateError::DatabaseError(_) => return Self::default(),
+            UpdateError::GetOneError(e) => return e.into(),
+            UpdateError::Validation { field, reason } => {
+                return Self::new("validation_failed").with_field(field, reason)
             }
+            UpdateError::Forbidden { .. } => Self::new("forbidden"),
+            UpdateError::PreconditionFailed => Self::new("precondition_failed"),
+            UpdateError::PreconditionRequired => Self::new("precondition_required"),
+            UpdateError::TestFailed(_) => Self::new("patch_test_failed"),
+            UpdateError::InvalidPayload(_) => Self::new("invalid_body"),
+            UpdateError::UnsupportedMediaType(_) => Self::new("unsupported_media_type"),
         };
 
-        let message = match value {
-            UpdateError::Forbidden { .. } => "No Permission for Update".into(),
-            _ => "Failed to retrieve user information".into(),
-        };
-
-        Self { cause, message }
+        problem.with_detail(value.to_string())
     }
 }
@@ -0,0 +1,261 @@
+use crate::database::Database;
+use crate::domain::user::actions::RestrictionError;
+use crate::domain::user::dto::GetUserResponse;
+use crate::domain::user::restriction::{RestrictionKind, RestrictionListing};
+use crate::domain::user::{self, privacy::Viewer};
+use crate::error::{self, ErrorResponse};
+use crate::i18n;
+use crate::openapi::envelope::Message;
+use crate::pagination::{ListQuery, Page};
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/block",
+    tag = "restrictions",
+    summary = "Block a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user was blocked", body = Message),
+        (status = 400, description = "Users cannot restrict themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn block(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    restrict(&db, &user_id, RestrictionKind::Block, &viewer, version).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/block",
+    tag = "restrictions",
+    summary = "Unblock a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user was unblocked", body = Message),
+        (status = 400, description = "Users cannot restrict themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn unblock(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    unrestrict(&db, &user_id, RestrictionKind::Block, &viewer, version).await
+}
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/mute",
+    tag = "restrictions",
+    summary = "Mute a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user was muted", body = Message),
+        (status = 400, description = "Users cannot restrict themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn mute(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    restrict(&db, &user_id, RestrictionKind::Mute, &viewer, version).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/mute",
+    tag = "restrictions",
+    summary = "Unmute a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user was unmuted", body = Message),
+        (status = 400, description = "Users cannot restrict themselves", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No user with this user_id can be found", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn unmute(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    unrestrict(&db, &user_id, RestrictionKind::Mute, &viewer, version).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/my_user/blocks",
+    tag = "restrictions",
+    summary = "List the users the signed in user has blocked",
+    params(("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "A page of blocked users, linked to the next one in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_blocks(
+    db: web::Data<Database>,
+    query: ListQuery<RestrictionListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, RestrictionError> {
+    list(&db, RestrictionKind::Block, &query, &viewer, &req).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/users/my_user/mutes",
+    tag = "restrictions",
+    summary = "List the users the signed in user has muted",
+    params(("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "A page of muted users, linked to the next one in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_mutes(
+    db: web::Data<Database>,
+    query: ListQuery<RestrictionListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, RestrictionError> {
+    list(&db, RestrictionKind::Mute, &query, &viewer, &req).await
+}
+
+async fn restrict(
+    db: &Database,
+    user_id: &str,
+    kind: RestrictionKind,
+    viewer: &Viewer,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    tracing::info!("Request to {kind:?} user {user_id:?}");
+
+    match user::actions::restrict(db, viewer, user_id, kind).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done(message(kind, true)))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            Err(e)
+        }
+    }
+}
+
+async fn unrestrict(
+    db: &Database,
+    user_id: &str,
+    kind: RestrictionKind,
+    viewer: &Viewer,
+    version: ApiVersion,
+) -> Result<HttpResponse, RestrictionError> {
+    tracing::info!("Request to lift {kind:?} of user {user_id:?}");
+
+    match user::actions::unrestrict(db, viewer, user_id, kind).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done(message(kind, false)))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            Err(e)
+        }
+    }
+}
+
+async fn list(
+    db: &Database,
+    kind: RestrictionKind,
+    query: &ListQuery<RestrictionListing>,
+    viewer: &Viewer,
+    req: &HttpRequest,
+) -> Result<HttpResponse, RestrictionError> {
+    tracing::info!("{kind:?} list requested");
+
+    match user::actions::list_restrictions(db, viewer, kind, query).await {
+        Ok(page) => {
+            tracing::info!("Request success: {page:?}");
+            Ok(page.respond(req))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            Err(e)
+        }
+    }
+}
+
+fn message(kind: RestrictionKind, applied: bool) -> &'static str {
+    match (kind, applied) {
+        (RestrictionKind::Block, true) => "User successfully blocked",
+        (RestrictionKind::Block, false) => "User successfully unblocked",
+        (RestrictionKind::Mute, true) => "User successfully muted",
+        (RestrictionKind::Mute, false) => "User successfully unmuted",
+    }
+}
+
+impl ResponseError for RestrictionError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            RestrictionError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            RestrictionError::NotFound(_) => StatusCode::NOT_FOUND,
+            RestrictionError::SelfRestriction => StatusCode::BAD_REQUEST,
+            RestrictionError::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&RestrictionError> for ErrorResponse
+where
+    RestrictionError: ResponseError,
+{
+    fn from(value: &RestrictionError) -> Self {
+        match value {
+            RestrictionError::DatabaseError(_) => Self::default(),
+            RestrictionError::NotFound(_) => {
+                Self::new("user_not_found").with_detail(i18n::Message::new("detail-user-not-found"))
+            }
+            RestrictionError::SelfRestriction => Self::new("self_restriction"),
+            RestrictionError::Unauthenticated => {
+                Self::new("unauthenticated").with_detail(value.to_string())
+            }
+        }
+    }
+}
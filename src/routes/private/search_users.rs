@@ -0,0 +1,82 @@
+use crate::database::Database;
+use crate::domain::user::actions::SearchError;
+use crate::domain::user::dto::GetUserResponse;
+use crate::domain::user::search::{SearchListing, SearchRateLimiter};
+use crate::domain::user::{self, privacy::Viewer};
+use crate::error::ErrorResponse;
+use crate::pagination::{ListQuery, Page};
+use actix_web::http::{header, StatusCode};
+use actix_web::{web, HttpRequest, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    get,
+    path = "/users",
+    tag = "users",
+    summary = "Search users by user_id and nickname",
+    params(("q" = String, Query, description = "The term to search for"),
+        ("limit" = Option<i64>, Query, description = "The number of users per page"),
+        ("cursor" = Option<String>, Query, description = "Where the page starts")),
+    responses(
+        (status = 200, description = "Users matching the search, from the best match to the worst, linked to the next page in the Link header", body = Page<GetUserResponse>),
+        (status = 400, description = "The query, cursor or limit is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 429, description = "Too many searches were made", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument(skip(limiter))]
+pub async fn search_users(
+    db: web::Data<Database>,
+    limiter: web::Data<SearchRateLimiter>,
+    query: ListQuery<SearchListing>,
+    viewer: web::ReqData<Viewer>,
+    req: HttpRequest,
+) -> Result<HttpResponse, SearchError> {
+    tracing::info!("Search requested: {:?}", query.parameter("q"));
+
+    match user::actions::search(&db, &viewer, &limiter, &query).await {
+        Ok(page) => {
+            tracing::info!("Request success: {page:?}");
+            Ok(page.respond(&req))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for SearchError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            SearchError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            SearchError::InvalidQuery => StatusCode::BAD_REQUEST,
+            SearchError::RateLimited { .. } => StatusCode::TOO_MANY_REQUESTS,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        let mut builder = HttpResponse::build(self.status_code());
+
+        if let SearchError::RateLimited { retry_after } = self {
+            builder.insert_header((header::RETRY_AFTER, retry_after.to_string()));
+        }
+
+        ErrorResponse::from(self).into_response(builder)
+    }
+}
+
+impl From<&SearchError> for ErrorResponse
+where
+    SearchError: ResponseError,
+{
+    fn from(value: &SearchError) -> Self {
+        let problem = match value {
+            SearchError::DatabaseError(_) => return Self::default(),
+            SearchError::InvalidQuery => Self::new("invalid_query"),
+            SearchError::RateLimited { .. } => Self::new("rate_limited"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -0,0 +1,68 @@
+use crate::database::Database;
+use crate::domain::user::actions::UnfollowError;
+use crate::domain::user::{self, privacy::Viewer};
+use crate::error::{self, ErrorResponse};
+use crate::openapi::envelope::Message;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    delete,
+    path = "/users/{user_id}/follow",
+    tag = "follows",
+    summary = "Stop following a user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    responses(
+        (status = 200, description = "The user is no longer followed", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn unfollow(
+    db: web::Data<Database>,
+    user_id: web::Path<String>,
+    viewer: web::ReqData<Viewer>,
+    version: ApiVersion,
+) -> Result<HttpResponse, UnfollowError> {
+    tracing::info!("Request to unfollow user {:?}", user_id.as_str());
+
+    match user::actions::unfollow(&db, &viewer, &user_id).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done("User successfully unfollowed"))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for UnfollowError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            UnfollowError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            UnfollowError::Unauthenticated => StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&UnfollowError> for ErrorResponse
+where
+    UnfollowError: ResponseError,
+{
+    fn from(value: &UnfollowError) -> Self {
+        match value {
+            UnfollowError::DatabaseError(_) => Self::default(),
+            UnfollowError::Unauthenticated => {
+                Self::new("unauthenticated").with_detail(value.to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::domain::user::actions::PrivacyError;
+use crate::domain::user::cache::UserCache;
+use crate::domain::user::{self, handle, BasicId};
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use crate::negotiation::Payload;
+use crate::openapi::envelope::PrivacyEnvelope;
+use crate::routes::version::ApiVersion;
+use crate::{database::Database, domain::user::dto::UpdatePrivacy};
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    put,
+    path = "/users/{user_id}/privacy",
+    tag = "users",
+    summary = "Change who can see the profile of the signed in user",
+    params(("user_id" = String, Path, description = "The `user_id` of the user")),
+    request_body = UpdatePrivacy,
+    responses(
+        (status = 200, description = "The privacy settings were changed", body = PrivacyEnvelope),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "Users may only change their own privacy settings", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn update_privacy(
+    db: web::Data<Database>,
+    cache: web::Data<UserCache>,
+    user_id: web::Path<String>,
+    update: Payload<UpdatePrivacy>,
+    requester_id: web::ReqData<BasicId>,
+    version: ApiVersion,
+) -> Result<HttpResponse, PrivacyError> {
+    tracing::info!("Request to update privacy settings {:?}", &update);
+
+    if !handle::matches(requester_id.as_str(), user_id.as_str()) {
+        Err(PrivacyError::Forbidden {
+            requester: requester_id.as_str().into(),
+            requested: user_id.as_str().to_owned(),
+        })?
+    }
+
+    match user::actions::update_privacy(&db, &cache, &requester_id, &update).await {
+        Ok(privacy) => {
+            tracing::info!("Request success: {privacy:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "Privacy settings successfully updated",
+                "privacy",
+                &privacy,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for PrivacyError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            PrivacyError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            PrivacyError::NotFound(_) => StatusCode::NOT_FOUND,
+            PrivacyError::Forbidden { .. } => StatusCode::FORBIDDEN,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&PrivacyError> for ErrorResponse
+where
+    PrivacyError: ResponseError,
+{
+    fn from(value: &PrivacyError) -> Self {
+        match value {
+            PrivacyError::DatabaseError(_) => Self::default(),
+            PrivacyError::NotFound(_) => {
+                Self::new("user_not_found").with_detail(Message::new("detail-user-not-found"))
+            }
+            PrivacyError::Forbidden { .. } => Self::new("forbidden").with_detail(value.to_string()),
+        }
+    }
+}
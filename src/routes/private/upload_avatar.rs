@@ -0,0 +1,131 @@
+use crate::blob::BlobStore;
+use crate::database::Database;
+use crate::domain::user::actions::AvatarError;
+use crate::domain::user::avatar::MAX_UPLOAD_BYTES;
+use crate::domain::user::cache::UserCache;
+use crate::domain::user::{self, BasicId};
+use crate::error::{self, ErrorResponse};
+use crate::openapi::envelope::{AvatarUpload, UserEnvelope};
+use crate::routes::version::ApiVersion;
+use actix_multipart::Multipart;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+use futures_util::StreamExt;
+
+/// The name of the multipart form field which carries the image.
+const AVATAR_FIELD: &str = "avatar";
+
+#[utoipa::path(
+    put,
+    path = "/users/my_user/avatar",
+    tag = "users",
+    summary = "Replace the avatar of the signed in user",
+    request_body(content = AvatarUpload, content_type = "multipart/form-data"),
+    responses(
+        (status = 200, description = "The avatar was replaced", body = UserEnvelope),
+        (status = 400, description = "The upload is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 413, description = "The image is too large", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 415, description = "The image format is not supported", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument(skip(payload))]
+pub async fn upload_avatar(
+    db: web::Data<Database>,
+    cache: web::Data<UserCache>,
+    store: web::Data<dyn BlobStore>,
+    requester_id: web::ReqData<BasicId>,
+    mut payload: Multipart,
+    version: ApiVersion,
+) -> Result<HttpResponse, AvatarError> {
+    tracing::info!("Avatar upload requested for {}", requester_id.as_str());
+
+    let upload = read_upload(&mut payload).await?;
+
+    match user::actions::upload_avatar(&db, &cache, store.get_ref(), &requester_id, upload).await {
+        Ok(user) => {
+            tracing::info!("Request success: {user:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "Avatar successfully updated",
+                "user",
+                &user,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+/// Read the avatar field from the multipart payload, refusing to buffer more
+/// than [MAX_UPLOAD_BYTES].
+async fn read_upload(payload: &mut Multipart) -> Result<Vec<u8>, AvatarError> {
+    while let Some(field) = payload.next().await {
+        let mut field = field.map_err(|e| AvatarError::Upload(e.to_string()))?;
+
+        if field.name() != AVATAR_FIELD {
+            continue;
+        }
+
+        let mut upload = Vec::new();
+        while let Some(chunk) = field.next().await {
+            let chunk = chunk.map_err(|e| AvatarError::Upload(e.to_string()))?;
+
+            if upload.len() + chunk.len() > MAX_UPLOAD_BYTES {
+                return Err(AvatarError::TooLarge);
+            }
+
+            upload.extend_from_slice(&chunk);
+        }
+
+        return Ok(upload);
+    }
+
+    Err(AvatarError::MissingFile)
+}
+
+impl ResponseError for AvatarError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            AvatarError::NotFound(_) => StatusCode::NOT_FOUND,
+            AvatarError::UnsupportedType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            AvatarError::TooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            AvatarError::InvalidImage(_)
+            | AvatarError::Dimensions { .. }
+            | AvatarError::MissingFile
+            | AvatarError::Upload(_) => StatusCode::BAD_REQUEST,
+            AvatarError::DatabaseError(_) | AvatarError::Blob(_) | AvatarError::Processing => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&AvatarError> for ErrorResponse
+where
+    AvatarError: ResponseError,
+{
+    fn from(value: &AvatarError) -> Self {
+        let problem = match value {
+            AvatarError::DatabaseError(_) | AvatarError::Blob(_) | AvatarError::Processing => {
+                return Self::default()
+            }
+            AvatarError::NotFound(_) => Self::new("user_not_found"),
+            AvatarError::UnsupportedType => Self::new("unsupported_image_type"),
+            AvatarError::TooLarge => Self::new("payload_too_large"),
+            AvatarError::InvalidImage(_) => Self::new("invalid_image"),
+            AvatarError::Dimensions { .. } => Self::new("invalid_image_dimensions"),
+            AvatarError::MissingFile => Self::new("missing_file"),
+            AvatarError::Upload(_) => Self::new("invalid_upload"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
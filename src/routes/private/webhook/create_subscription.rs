@@ -0,0 +1,86 @@
+use crate::database::Database;
+use crate::domain::user::privacy::Viewer;
+use crate::domain::webhook::{self, actions::WebhookError, dto::CreateSubscription};
+use crate::error::{self, ErrorResponse};
+use crate::negotiation::Payload;
+use crate::openapi::envelope::SubscriptionEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::http::StatusCode;
+use actix_web::{web, HttpResponse, ResponseError};
+
+#[utoipa::path(
+    post,
+    path = "/webhooks",
+    tag = "webhooks",
+    summary = "Subscribe a url to events of accounts",
+    request_body = CreateSubscription,
+    responses(
+        (status = 201, description = "The subscription was created", body = SubscriptionEnvelope),
+        (status = 400, description = "The url, secret or event types are invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not an admin", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn create_subscription(
+    db: web::Data<Database>,
+    viewer: web::ReqData<Viewer>,
+    subscription: Payload<CreateSubscription>,
+    version: ApiVersion,
+) -> Result<HttpResponse, WebhookError> {
+    tracing::info!("Request to subscribe {:?}", subscription.url);
+
+    match webhook::actions::create_subscription(&db, &viewer, &subscription).await {
+        Ok(subscription) => {
+            tracing::info!("Request success: {subscription:?}");
+            Ok(version.resource(
+                HttpResponse::Created(),
+                "Webhook subscription successfully created",
+                "subscription",
+                &subscription,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for WebhookError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            WebhookError::DatabaseError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            WebhookError::Forbidden => StatusCode::FORBIDDEN,
+            WebhookError::SubscriptionNotFound(_) => StatusCode::NOT_FOUND,
+            WebhookError::DeliveryNotFound(_) => StatusCode::NOT_FOUND,
+            WebhookError::NotDead(_) => StatusCode::CONFLICT,
+            WebhookError::Validation { .. } => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&WebhookError> for ErrorResponse
+where
+    WebhookError: ResponseError,
+{
+    fn from(value: &WebhookError) -> Self {
+        let problem = match value {
+            WebhookError::DatabaseError(_) => return Self::default(),
+            WebhookError::Validation { field, reason } => {
+                return Self::new("validation_failed").with_field(field, reason)
+            }
+            WebhookError::Forbidden => Self::new("forbidden"),
+            WebhookError::SubscriptionNotFound(_) => Self::new("webhook_not_found"),
+            WebhookError::DeliveryNotFound(_) => Self::new("delivery_not_found"),
+            WebhookError::NotDead(_) => Self::new("delivery_not_dead"),
+        };
+
+        problem.with_detail(value.to_string())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::database::Database;
+use crate::domain::user::privacy::Viewer;
+use crate::domain::webhook::{self, actions::WebhookError};
+use crate::error::ErrorResponse;
+use crate::openapi::envelope::Message;
+use crate::routes::version::ApiVersion;
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    delete,
+    path = "/webhooks/{subscription_id}",
+    tag = "webhooks",
+    summary = "Remove a webhook subscription along with its deliveries",
+    params(("subscription_id" = Uuid, Path, description = "The id of the subscription")),
+    responses(
+        (status = 200, description = "The subscription was removed", body = Message),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not an admin", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No subscription with this id exists", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn delete_subscription(
+    db: web::Data<Database>,
+    viewer: web::ReqData<Viewer>,
+    subscription_id: web::Path<Uuid>,
+    version: ApiVersion,
+) -> Result<HttpResponse, WebhookError> {
+    tracing::info!("Request to remove subscription {subscription_id}");
+
+    match webhook::actions::delete_subscription(&db, &viewer, *subscription_id).await {
+        Ok(()) => {
+            tracing::info!("Request success");
+            Ok(version.done("Webhook subscription successfully removed"))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
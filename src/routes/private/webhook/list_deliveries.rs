@@ -0,0 +1,43 @@
+use crate::database::Database;
+use crate::domain::user::privacy::Viewer;
+use crate::domain::webhook::{self, actions::WebhookError, dto::DeliveryQuery};
+use crate::error::ErrorResponse;
+use crate::negotiation;
+use crate::openapi::envelope::DeliveryList;
+use actix_web::{web, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/webhooks/deliveries",
+    tag = "webhooks",
+    summary = "List the most recent webhook deliveries",
+    params(DeliveryQuery),
+    responses(
+        (status = 200, description = "Up to 100 deliveries, from the most recent", body = DeliveryList),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not an admin", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_deliveries(
+    db: web::Data<Database>,
+    viewer: web::ReqData<Viewer>,
+    query: web::Query<DeliveryQuery>,
+) -> Result<HttpResponse, WebhookError> {
+    tracing::info!("Webhook deliveries requested");
+
+    match webhook::actions::list_deliveries(&db, &viewer, &query).await {
+        Ok(deliveries) => {
+            tracing::info!("Request success: {} deliveries", deliveries.len());
+            Ok(negotiation::respond(
+                HttpResponse::Ok(),
+                &serde_json::json!({ "deliveries": deliveries }),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
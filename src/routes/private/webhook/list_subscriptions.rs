@@ -0,0 +1,41 @@
+use crate::database::Database;
+use crate::domain::user::privacy::Viewer;
+use crate::domain::webhook::{self, actions::WebhookError};
+use crate::error::ErrorResponse;
+use crate::negotiation;
+use crate::openapi::envelope::SubscriptionList;
+use actix_web::{web, HttpResponse};
+
+#[utoipa::path(
+    get,
+    path = "/webhooks",
+    tag = "webhooks",
+    summary = "List the webhook subscriptions",
+    responses(
+        (status = 200, description = "Every subscription, from the oldest", body = SubscriptionList),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not an admin", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn list_subscriptions(
+    db: web::Data<Database>,
+    viewer: web::ReqData<Viewer>,
+) -> Result<HttpResponse, WebhookError> {
+    tracing::info!("Webhook subscriptions requested");
+
+    match webhook::actions::list_subscriptions(&db, &viewer).await {
+        Ok(subscriptions) => {
+            tracing::info!("Request success: {subscriptions:?}");
+            Ok(negotiation::respond(
+                HttpResponse::Ok(),
+                &serde_json::json!({ "subscriptions": subscriptions }),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
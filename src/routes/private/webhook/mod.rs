@@ -0,0 +1,27 @@
+//! Endpoints for managing webhook subscriptions and their deliveries. Only admins
+//! may use them.
+
+mod create_subscription;
+mod delete_subscription;
+mod list_deliveries;
+mod list_subscriptions;
+mod replay_delivery;
+
+use utoipa::OpenApi;
+
+pub use create_subscription::create_subscription;
+pub use delete_subscription::delete_subscription;
+pub use list_deliveries::list_deliveries;
+pub use list_subscriptions::list_subscriptions;
+pub use replay_delivery::replay_delivery;
+
+/// The OpenAPI description of the endpoints under `/webhooks`.
+#[derive(OpenApi)]
+#[openapi(paths(
+    create_subscription::create_subscription,
+    list_subscriptions::list_subscriptions,
+    delete_subscription::delete_subscription,
+    list_deliveries::list_deliveries,
+    replay_delivery::replay_delivery,
+))]
+pub struct WebhookApi;
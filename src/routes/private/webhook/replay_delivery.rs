@@ -0,0 +1,49 @@
+use crate::database::Database;
+use crate::domain::user::privacy::Viewer;
+use crate::domain::webhook::{self, actions::WebhookError};
+use crate::error::ErrorResponse;
+use crate::openapi::envelope::DeliveryEnvelope;
+use crate::routes::version::ApiVersion;
+use actix_web::{web, HttpResponse};
+use uuid::Uuid;
+
+#[utoipa::path(
+    post,
+    path = "/webhooks/deliveries/{delivery_id}/replay",
+    tag = "webhooks",
+    summary = "Send a dead webhook delivery again",
+    params(("delivery_id" = Uuid, Path, description = "The id of the delivery")),
+    responses(
+        (status = 200, description = "The delivery is pending again", body = DeliveryEnvelope),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 403, description = "The user is not an admin", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 404, description = "No delivery with this id exists", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 409, description = "The delivery is not dead", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+    security(("basic" = [])),
+)]
+#[tracing::instrument]
+pub async fn replay_delivery(
+    db: web::Data<Database>,
+    viewer: web::ReqData<Viewer>,
+    delivery_id: web::Path<Uuid>,
+    version: ApiVersion,
+) -> Result<HttpResponse, WebhookError> {
+    tracing::info!("Request to replay delivery {delivery_id}");
+
+    match webhook::actions::replay(&db, &viewer, *delivery_id).await {
+        Ok(delivery) => {
+            tracing::info!("Request success: {delivery:?}");
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "Webhook delivery successfully scheduled",
+                "delivery",
+                &delivery,
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::blob::{BlobError, BlobStore};
+use crate::error::{self, ErrorResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::{web, HttpResponse, ResponseError};
+
+/// Serves files from the configured blob store, such as avatar thumbnails.
+#[utoipa::path(
+    get,
+    path = "/blobs/{key}",
+    tag = "blobs",
+    summary = "Download a stored file, such as an avatar thumbnail",
+    params(("key" = String, Path, description = "The key of the file")),
+    responses(
+        (status = 200, description = "The file", content_type = "application/octet-stream", body = Vec<u8>),
+        (status = 404, description = "No file has this key"),
+    ),
+)]
+#[tracing::instrument]
+pub async fn get_blob(
+    store: web::Data<dyn BlobStore>,
+    key: web::Path<String>,
+) -> Result<HttpResponse, BlobError> {
+    match store.get(&key).await {
+        Ok(Some(blob)) => Ok(HttpResponse::Ok()
+            .content_type(blob.content_type)
+            .insert_header((header::CACHE_CONTROL, "public, max-age=31536000, immutable"))
+            .body(blob.data)),
+        Ok(None) => Ok(HttpResponse::NotFound().finish()),
+        Err(e) => {
+            tracing::error!("Request failure: {e}");
+            return Err(e);
+        }
+    }
+}
+
+impl ResponseError for BlobError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            BlobError::InvalidKey(_) => StatusCode::NOT_FOUND,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        error::respond(self)
+    }
+}
+
+impl From<&BlobError> for ErrorResponse
+where
+    BlobError: ResponseError,
+{
+    fn from(value: &BlobError) -> Self {
+        match value {
+            BlobError::InvalidKey(_) => Self::new("blob_not_found"),
+            _ => Self::default(),
+        }
+    }
+}
@@ -1,6 +1,15 @@
 use actix_web::HttpResponse;
 
 // TODO: convert to same as signup function
+#[utoipa::path(
+    get,
+    path = "/health_check",
+    tag = "health",
+    summary = "Check that the application is running",
+    responses(
+        (status = 200, description = "The application is running"),
+    ),
+)]
 #[tracing::instrument]
 pub async fn health_check() -> HttpResponse {
     tracing::info!("Health check requested");
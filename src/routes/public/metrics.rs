@@ -0,0 +1,22 @@
+use crate::domain::user::cache::UserCache;
+use crate::negotiation;
+use actix_web::{web, HttpResponse};
+use serde_json::json;
+
+/// Counters of the in-process caches.
+#[utoipa::path(
+    get,
+    path = "/metrics",
+    tag = "health",
+    summary = "Read the counters of the in-process caches",
+    responses(
+        (status = 200, description = "The counters of each cache", body = Object),
+    ),
+)]
+#[tracing::instrument]
+pub async fn metrics(user_cache: web::Data<UserCache>) -> HttpResponse {
+    negotiation::respond(
+        HttpResponse::Ok(),
+        &json!({ "user_cache": user_cache.stats() }),
+    )
+}
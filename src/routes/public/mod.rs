@@ -1,15 +1,39 @@
 //! Responsible for all endpoints that don't require authentication.
 
 use actix_web::web;
+use utoipa::OpenApi;
+
+mod blobs;
 mod health;
+mod metrics;
+mod openapi;
 mod signin;
 mod signup;
 
+/// The OpenAPI description of the public endpoints of the API.
+#[derive(OpenApi)]
+#[openapi(paths(signup::signup, signin::signin))]
+pub struct PublicApi;
+
+/// The OpenAPI description of the endpoints which are not versioned.
+#[derive(OpenApi)]
+#[openapi(paths(health::health_check, metrics::metrics, blobs::get_blob))]
+pub struct OperationalApi;
+
 pub fn public_services(cfg: &mut web::ServiceConfig) {
+    cfg.route("/signup", web::post().to(signup::signup))
+        .route("/signin", web::post().to(signin::signin));
+}
+
+pub fn operational_services(cfg: &mut web::ServiceConfig) {
+    #[cfg(feature = "docs-ui")]
     cfg.service(
-        web::scope("")
-            .route("/health_check", web::get().to(health::health_check))
-            .route("/signup", web::post().to(signup::signup))
-            .route("/signin", web::post().to(signin::signin)),
+        utoipa_swagger_ui::SwaggerUi::new("/docs/{_:.*}")
+            .config(utoipa_swagger_ui::Config::new(["/openapi.json"])),
     );
+
+    cfg.route("/health_check", web::get().to(health::health_check))
+        .route("/metrics", web::get().to(metrics::metrics))
+        .route("/openapi.json", web::get().to(openapi::openapi_document))
+        .route("/blobs/{key:.*}", web::get().to(blobs::get_blob));
 }
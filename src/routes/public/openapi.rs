@@ -0,0 +1,8 @@
+use crate::openapi;
+use actix_web::HttpResponse;
+
+/// The OpenAPI document describing every endpoint.
+#[tracing::instrument]
+pub async fn openapi_document() -> HttpResponse {
+    HttpResponse::Ok().json(openapi::document())
+}
@@ -1,23 +1,44 @@
 use crate::configuration::auth::AuthSettings;
 use crate::database::Database;
-use crate::domain::user::{self};
-use crate::error::ErrorResponse;
+use crate::domain::user::{self, cache::UserCache};
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use crate::negotiation::{self, Payload};
+use crate::openapi::envelope::Token;
+use actix_web::http::header::{CacheControl, CacheDirective};
 use actix_web::http::StatusCode;
 use actix_web::{web, HttpResponse, ResponseError};
 use serde_json::json;
 
+#[utoipa::path(
+    post,
+    path = "/signin",
+    tag = "auth",
+    summary = "Sign in and receive a JWT",
+    request_body = user::dto::Signin,
+    responses(
+        (status = 200, description = "The credentials are valid", body = Token),
+        (status = 400, description = "No user has this user_id", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 401, description = "The credentials are missing or invalid", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+)]
 #[tracing::instrument]
 pub async fn signin(
-    user_data: web::Json<user::dto::Signin>,
+    user_data: Payload<user::dto::Signin>,
     settings: web::Data<AuthSettings>,
     db: web::Data<Database>,
+    cache: web::Data<UserCache>,
 ) -> Result<HttpResponse, user::actions::SigninError> {
     tracing::info!("Signin requested: {user_data:?}");
 
-    match user::actions::signin(&db, &user_data.into_inner(), &settings.jwtsecret).await {
+    match user::actions::signin(&db, &cache, &user_data.into_inner(), &settings.jwtsecret).await {
         Ok(jwt) => {
             tracing::info!("Signin success: {jwt:?}");
-            Ok(HttpResponse::Ok().json(json!({"token": jwt})))
+            // The token is a credential, which is neither cached nor stored for
+            // the retries of an idempotent request.
+            let mut response = HttpResponse::Ok();
+            response.insert_header(CacheControl(vec![CacheDirective::NoStore]));
+            Ok(negotiation::respond(response, &json!({"token": jwt})))
         }
         Err(e) => {
             tracing::error!("Signin Failure: {e}");
@@ -40,10 +61,7 @@ impl ResponseError for user::actions::SigninError {
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        error::respond(self)
     }
 }
 
@@ -52,22 +70,13 @@ where
     user::actions::SigninError: ResponseError,
 {
     fn from(value: &user::actions::SigninError) -> Self {
-        let cause = match value {
-            user::actions::SigninError::UserNotFound => {
-                Some("User for submitted credentials does not exist".into())
-            }
+        match value {
+            user::actions::SigninError::UserNotFound => Self::new("user_not_found")
+                .with_detail(Message::new("detail-signin-user-not-found")),
             user::actions::SigninError::JwtError(crate::auth::JwtError::InvalidCredentials(_)) => {
-                Some(
-                    "The username and/or password submitted to not match any user in the system"
-                        .into(),
-                )
+                Self::new("invalid_credentials").with_detail(Message::new("detail-signin-mismatch"))
             }
-            _ => ErrorResponse::default().cause,
-        };
-
-        Self {
-            cause,
-            message: "Failed to signin user".into(),
+            _ => Self::default(),
         }
     }
 }
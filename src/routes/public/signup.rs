@@ -1,23 +1,46 @@
+use crate::configuration::user::UserSettings;
 use crate::database::Database;
 use crate::domain::user::actions::SignupError;
 use crate::domain::user::{self};
-use crate::error::ErrorResponse;
+use crate::error::{self, ErrorResponse};
+use crate::i18n::Message;
+use crate::negotiation::Payload;
+use crate::openapi::envelope::SignupEnvelope;
+use crate::routes::version::ApiVersion;
 use actix_web::http::StatusCode;
 use actix_web::{web, HttpResponse, ResponseError};
 
+#[utoipa::path(
+    post,
+    path = "/signup",
+    tag = "auth",
+    summary = "Create an account",
+    request_body = user::dto::Signup,
+    responses(
+        (status = 200, description = "The account was created", body = SignupEnvelope),
+        (status = 400, description = "The user_id or password is invalid", body = ErrorResponse, content_type = "application/problem+json"),
+        (status = 409, description = "The user_id is taken", body = ErrorResponse, content_type = "application/problem+json"),
+    ),
+)]
 #[tracing::instrument]
 pub async fn signup(
-    user_data: web::Json<user::dto::Signup>,
+    user_data: Payload<user::dto::Signup>,
     db: web::Data<Database>,
+    settings: web::Data<UserSettings>,
+    version: ApiVersion,
 ) -> Result<HttpResponse, SignupError> {
     tracing::info!("Signup requested: {user_data:?}");
 
-    match user::actions::signup(&db, user_data.into_inner()).await {
+    match user::actions::signup(&db, &settings, user_data.into_inner()).await {
         Ok(user) => {
             tracing::info!("Signup success: {user:?}");
 
-            Ok(HttpResponse::Ok()
-                .json(serde_json::json!({"message": "Account successfully created", "user": user})))
+            Ok(version.resource(
+                HttpResponse::Ok(),
+                "Account successfully created",
+                "user",
+                &user,
+            ))
         }
         Err(e) => {
             tracing::error!("Failed to persist user: {e}");
@@ -31,15 +54,14 @@ impl ResponseError for SignupError {
         match self {
             SignupError::InvalidPayload => StatusCode::BAD_REQUEST,
             SignupError::Validation { .. } => StatusCode::BAD_REQUEST,
+            SignupError::UserAlreadyExists(..) => StatusCode::CONFLICT,
+            SignupError::Confusable(..) => StatusCode::CONFLICT,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
     fn error_response(&self) -> HttpResponse {
-        let response: ErrorResponse = self.into();
-        HttpResponse::build(self.status_code())
-            .content_type("application/json")
-            .json(response)
+        error::respond(self)
     }
 }
 
@@ -48,18 +70,19 @@ where
     SignupError: ResponseError,
 {
     fn from(value: &SignupError) -> Self {
-        let cause = match value {
-            SignupError::InvalidPayload => Some("required user_id and password".into()),
-            SignupError::UserAlreadyExists(..) => Some("already same user_id is used".into()),
+        match value {
+            SignupError::InvalidPayload => {
+                Self::new("invalid_body").with_detail(Message::new("detail-missing-credentials"))
+            }
+            SignupError::UserAlreadyExists(..) => {
+                Self::new("user_id_taken").with_detail(Message::new("detail-user-id-taken"))
+            }
+            SignupError::Confusable(..) => Self::new("user_id_confusable")
+                .with_detail(Message::new("detail-user-id-confusable")),
             SignupError::Validation { field, reason } => {
-                Some(format!("Submission for field {field} is invalid: {reason}"))
+                Self::new("validation_failed").with_field(field, reason)
             }
-            _ => ErrorResponse::default().cause,
-        };
-
-        Self {
-            cause,
-            message: "Account creation failed".into(),
+            _ => Self::default(),
         }
     }
 }
@@ -0,0 +1,91 @@
+//! The versions of the API. Every version is mounted under its own prefix and
+//! shares the handlers and domain actions with the others. Handlers extract the
+//! [ApiVersion] of the request to shape their responses.
+
+use crate::negotiation;
+use actix_web::dev::Payload;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, HttpResponseBuilder};
+use futures_util::future::{ready, Ready};
+use serde::Serialize;
+use std::convert::Infallible;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApiVersion {
+    /// Wraps resources in an envelope along with a message, and describes
+    /// actions which return nothing with a message as well.
+    V1,
+    /// Responds with bare resources, and with `204 No Content` to actions which
+    /// return nothing.
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [Self; 2] = [Self::V1, Self::V2];
+
+    /// The prefix of the paths of the version, such as `/v1`.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Self::V1 => "/v1",
+            Self::V2 => "/v2",
+        }
+    }
+
+    /// The version which supersedes this one, if any.
+    pub fn successor(self) -> Option<Self> {
+        match self {
+            Self::V1 => Some(Self::V2),
+            Self::V2 => None,
+        }
+    }
+
+    /// The version which serves `req`.
+    pub fn of(req: &HttpRequest) -> Self {
+        req.app_data::<ApiVersion>()
+            .copied()
+            .expect("The version is registered as app data of its scope")
+    }
+
+    /// Respond with `resource`. Version 1 puts it under `name` in an envelope
+    /// along with `message`.
+    pub fn resource(
+        self,
+        builder: HttpResponseBuilder,
+        message: &str,
+        name: &str,
+        resource: &impl Serialize,
+    ) -> HttpResponse {
+        negotiation::respond(builder, &self.body(message, name, resource))
+    }
+
+    /// The body of [ApiVersion::resource], for handlers which need to inspect it
+    /// before responding.
+    pub fn body(self, message: &str, name: &str, resource: &impl Serialize) -> serde_json::Value {
+        let resource = serde_json::to_value(resource).expect("Resources can be serialized");
+
+        match self {
+            Self::V1 => serde_json::json!({ "message": message, name: resource }),
+            Self::V2 => resource,
+        }
+    }
+
+    /// Respond to an action which returns nothing. Version 1 describes it with
+    /// `message`.
+    pub fn done(self, message: &str) -> HttpResponse {
+        match self {
+            Self::V1 => negotiation::respond(
+                HttpResponse::Ok(),
+                &serde_json::json!({ "message": message }),
+            ),
+            Self::V2 => HttpResponse::NoContent().finish(),
+        }
+    }
+}
+
+impl FromRequest for ApiVersion {
+    type Error = Infallible;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(Ok(Self::of(req)))
+    }
+}
@@ -0,0 +1,224 @@
+use track_api_challenge::grpc::proto::{
+    CloseAccountRequest, GetUserRequest, SigninRequest, SignupRequest, UpdateUserRequest,
+};
+use track_api_challenge::tonic::{Code, Request};
+use utilities::{
+    dummy::{gen_dummy_user, gen_dummy_user_id},
+    spawn::spawn_app_with,
+    test_app::TestApp,
+};
+
+/// Spawn the application with the gRPC server, which is off by default.
+async fn spawn_app() -> anyhow::Result<TestApp> {
+    spawn_app_with(|settings| settings.grpc.enabled = true).await
+}
+
+fn signup_request() -> SignupRequest {
+    let user_data = gen_dummy_user();
+    SignupRequest {
+        user_id: user_data["user_id"].as_str().unwrap().to_owned(),
+        password: user_data["password"].as_str().unwrap().to_owned(),
+    }
+}
+
+/// Attach the JWT of `token` to `message`.
+fn authorized<T>(message: T, token: &str) -> Request<T> {
+    let mut request = Request::new(message);
+    request
+        .metadata_mut()
+        .insert("authorization", format!("Bearer {token}").parse().unwrap());
+    request
+}
+
+#[actix_web::test]
+async fn signed_in_users_can_read_and_update_themselves() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let mut client = test_app.grpc().await?;
+    let signup = signup_request();
+    client.signup(signup.clone()).await?;
+    let token = client
+        .signin(SigninRequest {
+            user_id: signup.user_id.clone(),
+            password: signup.password.clone(),
+        })
+        .await?
+        .into_inner()
+        .token;
+
+    // Act
+    let updated = client
+        .update_user(authorized(
+            UpdateUserRequest {
+                user_id: signup.user_id.clone(),
+                comment: Some("hello".to_owned()),
+                ..Default::default()
+            },
+            &token,
+        ))
+        .await?
+        .into_inner();
+    let user = client
+        .get_user(authorized(
+            GetUserRequest {
+                user_id: signup.user_id.clone(),
+            },
+            &token,
+        ))
+        .await?
+        .into_inner();
+
+    // Assert
+    assert_eq!(updated.comment.as_deref(), Some("hello"));
+    assert_eq!(user.user_id.as_deref(), Some(signup.user_id.as_str()));
+    assert_eq!(user.comment.as_deref(), Some("hello"));
+    assert_eq!(user.version, updated.version);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn strict_mode_requires_an_expected_version() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| {
+        settings.grpc.enabled = true;
+        settings.user.require_if_match = true;
+    })
+    .await?;
+    let mut client = test_app.grpc().await?;
+    let signup = signup_request();
+    client.signup(signup.clone()).await?;
+    let token = client
+        .signin(SigninRequest {
+            user_id: signup.user_id.clone(),
+            password: signup.password.clone(),
+        })
+        .await?
+        .into_inner()
+        .token;
+    let request = UpdateUserRequest {
+        user_id: signup.user_id.clone(),
+        comment: Some("hello".to_owned()),
+        ..Default::default()
+    };
+
+    // Act
+    let status = client
+        .update_user(authorized(request.clone(), &token))
+        .await
+        .expect_err("Expected the update to be rejected");
+    let version = client
+        .get_user(authorized(
+            GetUserRequest {
+                user_id: signup.user_id.clone(),
+            },
+            &token,
+        ))
+        .await?
+        .into_inner()
+        .version;
+    let updated = client
+        .update_user(authorized(
+            UpdateUserRequest {
+                expected_version: Some(version),
+                ..request
+            },
+            &token,
+        ))
+        .await?
+        .into_inner();
+
+    // Assert
+    assert_eq!(status.code(), Code::FailedPrecondition);
+    assert_eq!(
+        status.metadata().get("problem-code").unwrap(),
+        "precondition_required"
+    );
+    assert_eq!(updated.comment.as_deref(), Some("hello"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn calls_without_a_token_are_unauthenticated() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let mut client = test_app.grpc().await?;
+
+    // Act
+    let status = client
+        .get_user(GetUserRequest {
+            user_id: gen_dummy_user_id(),
+        })
+        .await
+        .expect_err("Expected the call to be rejected");
+
+    // Assert
+    assert_eq!(status.code(), Code::Unauthenticated);
+    assert_eq!(
+        status.metadata().get("problem-code").unwrap(),
+        "invalid_credentials"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn taken_user_ids_map_to_already_exists() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let mut client = test_app.grpc().await?;
+    let signup = signup_request();
+    client.signup(signup.clone()).await?;
+
+    // Act
+    let status = client
+        .signup(signup)
+        .await
+        .expect_err("Expected the signup to be rejected");
+
+    // Assert
+    assert_eq!(status.code(), Code::AlreadyExists);
+    assert_eq!(
+        status.metadata().get("problem-code").unwrap(),
+        "user_id_taken"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn closed_accounts_can_no_longer_call() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let mut client = test_app.grpc().await?;
+    let signup = signup_request();
+    client.signup(signup.clone()).await?;
+    let token = client
+        .signin(SigninRequest {
+            user_id: signup.user_id.clone(),
+            password: signup.password,
+        })
+        .await?
+        .into_inner()
+        .token;
+
+    // Act
+    client
+        .close_account(authorized(CloseAccountRequest {}, &token))
+        .await?;
+    let status = client
+        .get_user(authorized(
+            GetUserRequest {
+                user_id: signup.user_id,
+            },
+            &token,
+        ))
+        .await
+        .expect_err("Expected the call to be rejected");
+
+    // Assert
+    assert_eq!(status.code(), Code::Unauthenticated);
+
+    Ok(())
+}
@@ -1,3 +1,4 @@
+pub mod grpc;
 pub mod routes;
 
 // TODO: add additional tests to confirm failure modes behave as intended
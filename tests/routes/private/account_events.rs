@@ -0,0 +1,131 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use std::time::Duration;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app, test_app::TestApp};
+
+async fn signup(test_app: &TestApp) -> anyhow::Result<(Basic, serde_json::Value)> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    Ok((
+        Basic::new(user_id.to_owned(), Some(password.to_owned())),
+        user_data,
+    ))
+}
+
+/// The name and data of the next event of the stream, skipping comments.
+async fn next_event(
+    resp: &mut reqwest::Response,
+    buffer: &mut String,
+) -> anyhow::Result<(String, serde_json::Value)> {
+    loop {
+        if let Some(end) = buffer.find("\n\n") {
+            let frame: String = buffer.drain(..end + 2).collect();
+            let field = |name: &str| {
+                frame
+                    .lines()
+                    .find_map(|line| line.strip_prefix(name))
+                    .map(str::trim)
+                    .map(ToOwned::to_owned)
+            };
+            if let (Some(event), Some(data)) = (field("event:"), field("data:")) {
+                return Ok((event, serde_json::from_str(&data)?));
+            }
+            continue;
+        }
+
+        let chunk = actix_web::rt::time::timeout(Duration::from_secs(5), resp.chunk())
+            .await??
+            .ok_or_else(|| anyhow::anyhow!("The stream ended"))?;
+        buffer.push_str(std::str::from_utf8(&chunk)?);
+    }
+}
+
+#[actix_web::test]
+async fn profile_updates_are_streamed() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (credentials, _) = signup(&test_app).await?;
+    let mut resp = test_app.account_events(Some(credentials.clone())).await?;
+    let mut buffer = String::new();
+
+    // Act
+    test_app
+        .update_user(
+            credentials.user_id(),
+            Some(credentials.clone()),
+            &json!({ "comment": "hello" }),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "text/event-stream");
+    let (event, data) = next_event(&mut resp, &mut buffer).await?;
+    assert_eq!(event, "profile_updated");
+    assert_eq!(data["type"], "profile_updated");
+    assert!(data["version"].is_i64());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn sign_ins_are_streamed() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (credentials, user_data) = signup(&test_app).await?;
+    let mut resp = test_app.account_events(Some(credentials)).await?;
+    let mut buffer = String::new();
+
+    // Act
+    test_app.signin(&user_data).await?;
+
+    // Assert
+    let (event, data) = next_event(&mut resp, &mut buffer).await?;
+    assert_eq!(event, "signed_in");
+    assert_eq!(data, json!({ "type": "signed_in" }));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn events_of_other_users_are_not_streamed() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (credentials, _) = signup(&test_app).await?;
+    let (_, other_data) = signup(&test_app).await?;
+    let mut resp = test_app.account_events(Some(credentials.clone())).await?;
+    let mut buffer = String::new();
+
+    // Act
+    test_app.signin(&other_data).await?;
+    test_app
+        .update_user(
+            credentials.user_id(),
+            Some(credentials.clone()),
+            &json!({ "bio": "mine" }),
+        )
+        .await?;
+
+    // Assert
+    let (event, _) = next_event(&mut resp, &mut buffer).await?;
+    assert_eq!(event, "profile_updated");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn account_events_require_credentials() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app.account_events(None).await?;
+
+    // Assert
+    assert_eq!(401, resp.status().as_u16());
+
+    Ok(())
+}
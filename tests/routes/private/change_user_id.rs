@@ -0,0 +1,217 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use utilities::{
+    dummy::{gen_dummy_user, gen_dummy_user_id},
+    spawn::spawn_app,
+};
+
+use crate::routes::private::{RESERVED_USER_ID, RESERVED_USER_PASS};
+
+#[actix_web::test]
+async fn can_change_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 200;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let new_user_id = gen_dummy_user_id();
+
+    // Act
+    let resp = test_app
+        .change_user_id(
+            user_id,
+            Some(Basic::new(user_id.to_owned(), Some(password.to_owned()))),
+            &new_user_id,
+        )
+        .await?;
+
+    let status = resp.status();
+
+    let body = resp
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let message = match body.get("message").as_ref().unwrap() {
+        serde_json::Value::String(value) => value,
+        _ => panic!("Should have gotten a string"),
+    };
+
+    assert_eq!(message, "User id successfully changed");
+    assert_eq!(body["user"]["user_id"], new_user_id.as_str());
+
+    let resp = test_app
+        .get_user(
+            &new_user_id,
+            Some(Basic::new(new_user_id.clone(), Some(password.to_owned()))),
+        )
+        .await?;
+
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn old_user_id_redirects_to_new_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let new_user_id = gen_dummy_user_id();
+
+    test_app
+        .change_user_id(
+            user_id,
+            Some(Basic::new(user_id.to_owned(), Some(password.to_owned()))),
+            &new_user_id,
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .get_user(
+            user_id,
+            Some(Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS))),
+        )
+        .await?;
+
+    let status = resp.status();
+    let url = resp.url().clone();
+
+    let body = resp
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    // Assert
+    assert_eq!(200, status.as_u16());
+    assert!(url.path().ends_with(&new_user_id));
+    assert_eq!(body["user"]["user_id"], new_user_id.as_str());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_sign_up_with_a_reserved_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 409;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    test_app
+        .change_user_id(
+            user_id,
+            Some(Basic::new(user_id.to_owned(), Some(password.to_owned()))),
+            &gen_dummy_user_id(),
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .signup(&serde_json::json!({ "user_id": user_id, "password": "password123" }))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_change_user_id_again_within_the_change_interval() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 429;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let new_user_id = gen_dummy_user_id();
+
+    test_app
+        .change_user_id(
+            user_id,
+            Some(Basic::new(user_id.to_owned(), Some(password.to_owned()))),
+            &new_user_id,
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .change_user_id(
+            &new_user_id,
+            Some(Basic::new(new_user_id.clone(), Some(password.to_owned()))),
+            &gen_dummy_user_id(),
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+    assert!(resp.headers().get("retry-after").is_some());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_change_the_user_id_of_a_different_account() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+
+    // Act
+    let resp = test_app
+        .change_user_id(
+            user_id,
+            Some(Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS))),
+            &gen_dummy_user_id(),
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
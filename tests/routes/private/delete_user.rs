@@ -1,4 +1,5 @@
 use actix_web_httpauth::headers::authorization::Basic;
+use futures_util::future::join;
 use utilities::{dummy::gen_dummy_user, spawn::spawn_app};
 
 #[actix_web::test]
@@ -30,12 +31,8 @@ async fn cannot_delete_account_without_authorization() -> anyhow::Result<()> {
     );
 
     assert!(body.is_object());
-    let message = match body.get("message").as_ref().unwrap() {
-        serde_json::Value::String(value) => value,
-        _ => panic!("Should have gotten a string"),
-    };
-
-    assert_eq!(message, "Authentication Failed");
+    assert_eq!(body["code"], "invalid_credentials");
+    assert_eq!(body["status"], 401);
 
     Ok(())
 }
@@ -87,3 +84,32 @@ async fn can_delete_account() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_web::test]
+async fn concurrent_closes_delete_the_account_once() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let credentials = Basic::new(
+        user_data["user_id"].as_str().unwrap().to_owned(),
+        Some(user_data["password"].as_str().unwrap().to_owned()),
+    );
+
+    // Act
+    let (first, second) = join(
+        test_app.close_account(Some(credentials.clone())),
+        test_app.close_account(Some(credentials.clone())),
+    )
+    .await;
+
+    // Assert
+    let mut statuses = [first?.status().as_u16(), second?.status().as_u16()];
+    statuses.sort();
+    assert!(
+        statuses == [200, 401] || statuses == [200, 404],
+        "Expected one close and a rejection, got {statuses:?}"
+    );
+
+    Ok(())
+}
@@ -0,0 +1,249 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app, test_app::TestApp};
+
+async fn signup(test_app: &TestApp) -> anyhow::Result<Basic> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    Ok(Basic::new(user_id.to_owned(), Some(password.to_owned())))
+}
+
+async fn get_user(
+    test_app: &TestApp,
+    user_id: &str,
+    credentials: &Basic,
+) -> anyhow::Result<serde_json::Value> {
+    let resp = test_app
+        .get_user(user_id, Some(credentials.clone()))
+        .await?;
+
+    Ok(resp.json::<serde_json::Value>().await?["user"].clone())
+}
+
+async fn list_user_ids(
+    test_app: &TestApp,
+    user_id: &str,
+    list: &str,
+    query: &str,
+    credentials: &Basic,
+) -> anyhow::Result<(Vec<String>, Option<String>)> {
+    let resp = test_app
+        .list_follows(user_id, list, query, Some(credentials.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+
+    let user_ids = body["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["user_id"].as_str().unwrap().to_owned())
+        .collect();
+    let next_cursor = body["next_cursor"].as_str().map(ToOwned::to_owned);
+
+    Ok((user_ids, next_cursor))
+}
+
+#[actix_web::test]
+async fn can_follow_a_user() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 200;
+    let follower = signup(&test_app).await?;
+    let followee = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .follow(followee.user_id(), Some(follower.clone()))
+        .await?;
+
+    let status = resp.status();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+    assert_eq!(body["follow"]["status"], "accepted");
+
+    let user = get_user(&test_app, followee.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 1);
+    assert_eq!(user["following_count"], 0);
+
+    let user = get_user(&test_app, follower.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 0);
+    assert_eq!(user["following_count"], 1);
+
+    let (followers, _) =
+        list_user_ids(&test_app, followee.user_id(), "followers", "", &follower).await?;
+    assert_eq!(followers, vec![follower.user_id().to_string()]);
+
+    let (following, _) =
+        list_user_ids(&test_app, follower.user_id(), "following", "", &follower).await?;
+    assert_eq!(following, vec![followee.user_id().to_string()]);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn can_unfollow_a_user() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let follower = signup(&test_app).await?;
+    let followee = signup(&test_app).await?;
+    test_app
+        .follow(followee.user_id(), Some(follower.clone()))
+        .await?;
+
+    // Act
+    let resp = test_app
+        .unfollow(followee.user_id(), Some(follower.clone()))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let user = get_user(&test_app, followee.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 0);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_follow_yourself() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+    let user = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app.follow(user.user_id(), Some(user.clone())).await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn followers_are_paginated_with_a_cursor() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let followee = signup(&test_app).await?;
+    let mut followers = Vec::new();
+    for _ in 0..3 {
+        let follower = signup(&test_app).await?;
+        test_app
+            .follow(followee.user_id(), Some(follower.clone()))
+            .await?;
+        followers.push(follower.user_id().to_string());
+    }
+
+    // Act
+    let (first_page, cursor) = list_user_ids(
+        &test_app,
+        followee.user_id(),
+        "followers",
+        "?limit=2",
+        &followee,
+    )
+    .await?;
+    let cursor = cursor.expect("Expected a cursor for the next page");
+    let (second_page, cursor) = list_user_ids(
+        &test_app,
+        followee.user_id(),
+        "followers",
+        &format!("?limit=2&cursor={cursor}"),
+        &followee,
+    )
+    .await?;
+
+    // Assert
+    assert_eq!(first_page.len(), 2);
+    assert_eq!(second_page.len(), 1);
+    assert!(cursor.is_none());
+
+    let mut listed = [first_page, second_page].concat();
+    listed.sort();
+    followers.sort();
+    assert_eq!(listed, followers);
+
+    let resp = test_app
+        .list_follows(
+            followee.user_id(),
+            "followers",
+            "?cursor=not-a-cursor",
+            Some(followee.clone()),
+        )
+        .await?;
+    assert_eq!(400, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn follows_of_private_accounts_need_approval() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let follower = signup(&test_app).await?;
+    let followee = signup(&test_app).await?;
+    test_app
+        .update_privacy(
+            followee.user_id(),
+            Some(followee.clone()),
+            &json!({ "approve_followers": true }),
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .follow(followee.user_id(), Some(follower.clone()))
+        .await?;
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(body["follow"]["status"], "pending");
+
+    let user = get_user(&test_app, followee.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 0);
+
+    let requests = test_app
+        .follow_requests(Some(followee.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(requests["items"][0]["user_id"], follower.user_id());
+
+    let resp = test_app
+        .approve_follower(follower.user_id(), Some(followee.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let user = get_user(&test_app, followee.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 1);
+
+    let resp = test_app
+        .remove_follower(follower.user_id(), Some(followee.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let user = get_user(&test_app, followee.user_id(), &follower).await?;
+    assert_eq!(user["followers_count"], 0);
+
+    Ok(())
+}
@@ -1,5 +1,9 @@
 use actix_web_httpauth::headers::authorization::Basic;
-use utilities::{dummy::gen_dummy_user, spawn::spawn_app};
+use serde_json::json;
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+};
 
 use crate::routes::private::{RESERVED_USER_ID, RESERVED_USER_PASS};
 
@@ -28,12 +32,9 @@ async fn cannot_get_user_information_without_authorization() -> anyhow::Result<(
     );
 
     assert!(body.is_object());
-    let message = match body.get("message").as_ref().unwrap() {
-        serde_json::Value::String(value) => value,
-        _ => panic!("Should have gotten a string"),
-    };
-
-    assert_eq!(message, "Authentication Failed");
+    assert_eq!(body["code"], "invalid_credentials");
+    assert_eq!(body["status"], 401);
+    assert_eq!(body["instance"], format!("/users/{RESERVED_USER_ID}"));
 
     Ok(())
 }
@@ -124,3 +125,153 @@ async fn can_get_user_information_of_different_user_id() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_web::test]
+async fn unchanged_users_are_not_sent_again() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    let viewer = Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS));
+
+    let resp = test_app.get_user(user_id, Some(viewer.clone())).await?;
+    let etag = resp.headers()["etag"].to_str()?.to_owned();
+    let last_modified = resp.headers()["last-modified"].to_str()?.to_owned();
+    assert!(!etag.starts_with("W/"));
+
+    // Act
+    let by_etag = test_app
+        .get_user_if(user_id, Some(viewer.clone()), "If-None-Match", &etag)
+        .await?;
+    let by_date = test_app
+        .get_user_if(
+            user_id,
+            Some(viewer.clone()),
+            "If-Modified-Since",
+            &last_modified,
+        )
+        .await?;
+    test_app
+        .update_user(user_id, Some(credentials), &json!({ "comment": "Changed" }))
+        .await?;
+    let changed = test_app
+        .get_user_if(user_id, Some(viewer), "If-None-Match", &etag)
+        .await?;
+
+    // Assert
+    assert_eq!(304, by_etag.status().as_u16());
+    assert_eq!(etag, by_etag.headers()["etag"].to_str()?);
+    assert_eq!(304, by_date.status().as_u16());
+    assert_eq!(200, changed.status().as_u16());
+    assert_ne!(etag, changed.headers()["etag"].to_str()?);
+    let body = changed.json::<serde_json::Value>().await?;
+    assert_eq!(body["user"]["comment"], "Changed");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn authenticated_responses_may_not_be_stored_by_shared_caches() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    let viewer = Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS));
+
+    // Act
+    let public = test_app.get_user(user_id, Some(viewer.clone())).await?;
+    test_app
+        .update_privacy(
+            user_id,
+            Some(credentials),
+            &json!({ "visibility": { "comment": "authenticated" } }),
+        )
+        .await?;
+    let private = test_app.get_user(user_id, Some(viewer)).await?;
+
+    // Assert
+    assert_eq!(
+        "private, no-cache",
+        public.headers()["cache-control"].to_str()?
+    );
+    assert_eq!(
+        "private, no-cache",
+        private.headers()["cache-control"].to_str()?
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cached_users_are_invalidated_on_changes() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| settings.cache.user_capacity = 100).await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    let viewer = Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS));
+    test_app.get_user(user_id, Some(viewer.clone())).await?;
+
+    // Act
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "Changed" }),
+        )
+        .await?;
+    let updated = test_app.get_user(user_id, Some(viewer.clone())).await?;
+    test_app.close_account(Some(credentials)).await?;
+    let deleted = test_app.get_user(user_id, Some(viewer)).await?;
+
+    // Assert
+    let body = updated.json::<serde_json::Value>().await?;
+    assert_eq!(body["user"]["comment"], "Changed");
+    assert_eq!(404, deleted.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cached_users_are_evicted_when_the_database_announces_changes() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app_with(|settings| {
+        settings.cache.user_capacity = 100;
+        settings.cache.user_ttl_seconds = 600;
+        settings.cache.listen = true;
+    })
+    .await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let viewer = Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS));
+    test_app.get_user(user_id, Some(viewer.clone())).await?;
+
+    // Act
+    sqlx::query("UPDATE user_ SET comment = 'Changed elsewhere' WHERE user_id = $1")
+        .bind(user_id)
+        .execute(test_app.db().inner())
+        .await?;
+
+    // Assert
+    let mut comment = serde_json::Value::Null;
+    for _ in 0..50 {
+        let resp = test_app.get_user(user_id, Some(viewer.clone())).await?;
+        comment = resp.json::<serde_json::Value>().await?["user"]["comment"].take();
+        if comment == "Changed elsewhere" {
+            break;
+        }
+        actix_web::rt::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    assert_eq!(comment, "Changed elsewhere");
+
+    Ok(())
+}
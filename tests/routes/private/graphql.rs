@@ -0,0 +1,323 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+    test_app::TestApp,
+};
+
+async fn signup(test_app: &TestApp) -> anyhow::Result<Basic> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    Ok(Basic::new(user_id.to_owned(), Some(password.to_owned())))
+}
+
+#[actix_web::test]
+async fn me_returns_the_signed_in_user() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let credentials = signup(&test_app).await?;
+    let query = "{ me { userId nickname followersCount followingCount } }";
+
+    // Act
+    let resp = test_app
+        .graphql(query, json!({}), Some(credentials.clone()))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["errors"].is_null(), "{body}");
+    assert_eq!(body["data"]["me"]["userId"], credentials.user_id());
+    assert_eq!(body["data"]["me"]["followersCount"], 0);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn users_and_their_follows_are_fetched_in_one_query() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let follower = signup(&test_app).await?;
+    let followee = signup(&test_app).await?;
+    test_app
+        .follow(followee.user_id(), Some(follower.clone()))
+        .await?;
+    let query = r#"
+        query ($userId: String!) {
+            user(userId: $userId) {
+                userId
+                followersCount
+                followers { users { userId followingCount } nextCursor }
+            }
+            me { following { users { userId } } }
+        }
+    "#;
+
+    // Act
+    let resp = test_app
+        .graphql(
+            query,
+            json!({ "userId": followee.user_id() }),
+            Some(follower.clone()),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["errors"].is_null(), "{body}");
+    let user = &body["data"]["user"];
+    assert_eq!(user["followersCount"], 1);
+    assert_eq!(
+        user["followers"]["users"],
+        json!([{ "userId": follower.user_id(), "followingCount": 1 }])
+    );
+    assert!(user["followers"]["nextCursor"].is_null());
+    assert_eq!(
+        body["data"]["me"]["following"]["users"][0]["userId"],
+        followee.user_id()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn follows_of_several_users_are_paged_separately() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let followees = [signup(&test_app).await?, signup(&test_app).await?];
+    for _ in 0..2 {
+        let follower = signup(&test_app).await?;
+        for followee in &followees {
+            test_app
+                .follow(followee.user_id(), Some(follower.clone()))
+                .await?;
+        }
+    }
+    let query = r#"
+        query ($first: String!, $second: String!) {
+            first: user(userId: $first) { followers(limit: 1) { users { userId } nextCursor } }
+            second: user(userId: $second) { followers(limit: 1) { users { userId } nextCursor } }
+        }
+    "#;
+
+    // Act
+    let resp = test_app
+        .graphql(
+            query,
+            json!({ "first": followees[0].user_id(), "second": followees[1].user_id() }),
+            Some(followees[0].clone()),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["errors"].is_null(), "{body}");
+    for name in ["first", "second"] {
+        let followers = &body["data"][name]["followers"];
+        assert_eq!(followers["users"].as_array().unwrap().len(), 1);
+        assert!(followers["nextCursor"].is_string());
+    }
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn queries_which_are_too_complex_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let credentials = signup(&test_app).await?;
+    let query = r#"
+        {
+            me {
+                followers(limit: 100) {
+                    users { followers(limit: 100) { users { userId } } }
+                }
+            }
+        }
+    "#;
+
+    // Act
+    let resp = test_app
+        .graphql(query, json!({}), Some(credentials))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["data"].is_null(), "{body}");
+    assert!(body["errors"][0]["message"]
+        .as_str()
+        .unwrap()
+        .contains("complex"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn users_who_cannot_be_found_are_null() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let credentials = signup(&test_app).await?;
+    let query = "query ($userId: String!) { user(userId: $userId) { userId } }";
+
+    // Act
+    let resp = test_app
+        .graphql(query, json!({ "userId": "nobody" }), Some(credentials))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["data"]["user"].is_null());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn update_profile_changes_and_clears_fields() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let credentials = signup(&test_app).await?;
+    test_app
+        .update_user(
+            credentials.user_id(),
+            Some(credentials.clone()),
+            &json!({ "comment": "hello" }),
+        )
+        .await?;
+    let query = r#"
+        mutation ($userId: String!) {
+            updateProfile(userId: $userId, profile: { nickname: "taro", comment: null }) {
+                nickname
+                comment
+            }
+        }
+    "#;
+
+    // Act
+    let resp = test_app
+        .graphql(
+            query,
+            json!({ "userId": credentials.user_id() }),
+            Some(credentials.clone()),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["errors"].is_null(), "{body}");
+    assert_eq!(
+        body["data"]["updateProfile"],
+        json!({ "nickname": "taro", "comment": null })
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn strict_mode_requires_an_expected_version() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| settings.user.require_if_match = true).await?;
+    let credentials = signup(&test_app).await?;
+    let query = r#"
+        mutation ($userId: String!, $expectedVersion: Int) {
+            updateProfile(
+                userId: $userId
+                profile: { nickname: "taro" }
+                expectedVersion: $expectedVersion
+            ) { nickname }
+        }
+    "#;
+    let version = test_app
+        .graphql("{ me { version } }", json!({}), Some(credentials.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?["data"]["me"]["version"]
+        .clone();
+
+    // Act
+    let rejected = test_app
+        .graphql(
+            query,
+            json!({ "userId": credentials.user_id() }),
+            Some(credentials.clone()),
+        )
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let updated = test_app
+        .graphql(
+            query,
+            json!({ "userId": credentials.user_id(), "expectedVersion": version }),
+            Some(credentials.clone()),
+        )
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+
+    // Assert
+    assert!(rejected["data"].is_null());
+    assert_eq!(
+        rejected["errors"][0]["extensions"]["code"],
+        "precondition_required"
+    );
+    assert_eq!(rejected["errors"][0]["extensions"]["status"], 428);
+    assert!(updated["errors"].is_null(), "{updated}");
+    assert_eq!(updated["data"]["updateProfile"]["nickname"], "taro");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn update_profile_of_another_user_is_forbidden() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let credentials = signup(&test_app).await?;
+    let other = signup(&test_app).await?;
+    let query = r#"
+        mutation ($userId: String!) {
+            updateProfile(userId: $userId, profile: { nickname: "taro" }) { nickname }
+        }
+    "#;
+
+    // Act
+    let resp = test_app
+        .graphql(
+            query,
+            json!({ "userId": other.user_id() }),
+            Some(credentials),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert!(body["data"].is_null());
+    assert_eq!(body["errors"][0]["extensions"]["code"], "forbidden");
+    assert_eq!(body["errors"][0]["extensions"]["status"], 403);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn graphql_requires_credentials() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .graphql("{ me { userId } }", json!({}), None)
+        .await?;
+
+    // Assert
+    assert_eq!(401, resp.status().as_u16());
+
+    Ok(())
+}
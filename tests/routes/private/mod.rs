@@ -1,6 +1,16 @@
+mod account_events;
+mod change_user_id;
 mod delete_user;
+mod follow;
 mod get_user;
+mod graphql;
+mod organization;
+mod privacy;
+mod restrict;
+mod search_users;
 mod update_user;
+mod upload_avatar;
+mod webhook;
 
 pub static RESERVED_USER_ID: &str = "TaroYamada";
 pub static RESERVED_USER_PASS: &str = "PaSSwd4TY";
@@ -0,0 +1,423 @@
+use serde_json::json;
+use track_api_challenge::database::TenantScope;
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+    test_app::TestApp,
+};
+use uuid::Uuid;
+
+/// Sign up a new user and return their `user_id` along with a JWT.
+async fn signup(test_app: &TestApp) -> anyhow::Result<(String, String)> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let token = test_app.signin_token(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap().to_owned();
+
+    Ok((user_id, token))
+}
+
+/// Create an organization and return its id along with a JWT acting in it.
+async fn create_organization(test_app: &TestApp, token: &str) -> anyhow::Result<(String, String)> {
+    let body = test_app
+        .create_organization(token, "Acme")
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let organization_id = body["organization"]["id"].as_str().unwrap().to_owned();
+
+    let body = test_app
+        .switch_organization(token, Some(&organization_id))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+
+    Ok((organization_id, body["token"].as_str().unwrap().to_owned()))
+}
+
+async fn invite(
+    test_app: &TestApp,
+    token: &str,
+    invitation: serde_json::Value,
+) -> anyhow::Result<String> {
+    let resp = test_app.create_invitation(token, &invitation).await?;
+    assert_eq!(201, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+
+    Ok(body["invitation"]["token"].as_str().unwrap().to_owned())
+}
+
+#[actix_web::test]
+async fn can_create_an_organization() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 201;
+    let (_, token) = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app.create_organization(&token, "Acme").await?;
+
+    let status = resp.status();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+    assert_eq!(body["organization"]["name"], "Acme");
+    assert_eq!(body["organization"]["role"], "owner");
+
+    let body = test_app
+        .list_organizations(&token)
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(body["organizations"].as_array().unwrap().len(), 1);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn organization_endpoints_require_a_token() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 401;
+
+    // Act
+    let resp = test_app.list_organizations("not-a-token").await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn members_require_an_active_organization() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let (_, token) = signup(&test_app).await?;
+    test_app.create_organization(&token, "Acme").await?;
+
+    // Act
+    let resp = test_app.list_members(&token).await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_switch_to_an_organization_without_membership() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (_, other_token) = signup(&test_app).await?;
+    let (organization_id, _) = create_organization(&test_app, &owner_token).await?;
+
+    // Act
+    let resp = test_app
+        .switch_organization(&other_token, Some(&organization_id))
+        .await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn email_invitations_can_be_accepted_once_by_whoever_holds_the_token() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (member_id, member_token) = signup(&test_app).await?;
+    let (_, third_token) = signup(&test_app).await?;
+    let (organization_id, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(
+        &test_app,
+        &owner_token,
+        json!({ "email": "member@example.com", "role": "admin" }),
+    )
+    .await?;
+
+    // Act
+    // The accepting account has no relation to the email, the token alone counts
+    let resp = test_app
+        .accept_invitation(&member_token, &invitation)
+        .await?;
+
+    let status = resp.status();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(200, status.as_u16());
+    assert_eq!(body["organization"]["id"], organization_id.as_str());
+    assert_eq!(body["organization"]["role"], "admin");
+
+    let members = test_app
+        .list_members(&owner_token)
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let members = members["items"].as_array().unwrap();
+    assert_eq!(members.len(), 2);
+    assert_eq!(members[1]["user_id"], member_id.as_str());
+
+    // Email invitations can only be used once
+    let resp = test_app
+        .accept_invitation(&third_token, &invitation)
+        .await?;
+    assert_eq!(410, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn link_invitations_can_be_used_by_many_users() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (_, first_token) = signup(&test_app).await?;
+    let (_, second_token) = signup(&test_app).await?;
+    let (_, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+
+    // Act
+    let first = test_app
+        .accept_invitation(&first_token, &invitation)
+        .await?;
+    let second = test_app
+        .accept_invitation(&second_token, &invitation)
+        .await?;
+
+    // Assert
+    assert_eq!(200, first.status().as_u16());
+    assert_eq!(200, second.status().as_u16());
+
+    let members = test_app
+        .list_members(&owner_token)
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(members["items"].as_array().unwrap().len(), 3);
+    assert_eq!(members["items"][1]["role"], "member");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn expired_invitations_cannot_be_accepted() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| {
+        settings.organization.invitation_ttl_seconds = -1;
+    })
+    .await?;
+    let expected_code = 410;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (_, member_token) = signup(&test_app).await?;
+    let (_, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+
+    // Act
+    let resp = test_app
+        .accept_invitation(&member_token, &invitation)
+        .await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn members_cannot_invite_or_remove_others() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let (owner_id, owner_token) = signup(&test_app).await?;
+    let (_, member_token) = signup(&test_app).await?;
+    let (organization_id, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+    test_app
+        .accept_invitation(&member_token, &invitation)
+        .await?;
+    let body = test_app
+        .switch_organization(&member_token, Some(&organization_id))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let member_token = body["token"].as_str().unwrap();
+
+    // Act
+    let invite_resp = test_app.create_invitation(member_token, &json!({})).await?;
+    let remove_resp = test_app.remove_member(member_token, &owner_id).await?;
+
+    // Assert
+    assert_eq!(expected_code, invite_resp.status().as_u16());
+    assert_eq!(expected_code, remove_resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn admins_can_remove_members() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (member_id, member_token) = signup(&test_app).await?;
+    let (organization_id, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+    test_app
+        .accept_invitation(&member_token, &invitation)
+        .await?;
+
+    // Act
+    let resp = test_app.remove_member(&owner_token, &member_id).await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let resp = test_app
+        .switch_organization(&member_token, Some(&organization_id))
+        .await?;
+    assert_eq!(403, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn scoped_transactions_only_see_their_tenant() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let (user_id, token) = signup(&test_app).await?;
+    let (_, other_token) = signup(&test_app).await?;
+    let (organization_id, token) = create_organization(&test_app, &token).await?;
+    let (other_organization_id, other_token) = create_organization(&test_app, &other_token).await?;
+    invite(&test_app, &token, json!({})).await?;
+    invite(&test_app, &other_token, json!({})).await?;
+
+    let db = test_app.db();
+    let user_id = sqlx::query_scalar::<_, Uuid>("SELECT id FROM user_ WHERE user_id = $1")
+        .bind(&user_id)
+        .fetch_one(db.inner())
+        .await?;
+    let scope = TenantScope {
+        tenant_id: Some(organization_id.parse()?),
+        user_id,
+    };
+
+    // Act
+    let mut tx = db.scoped(&scope).await?;
+    let invitations = sqlx::query_scalar::<_, Uuid>("SELECT tenant_id FROM invitation")
+        .fetch_all(&mut *tx)
+        .await?;
+    let organizations = sqlx::query_scalar::<_, Uuid>("SELECT id FROM organization")
+        .fetch_all(&mut *tx)
+        .await?;
+    let inserted = sqlx::query("INSERT INTO membership (tenant_id, user_id, role, created_at) VALUES ($1, $2, 'owner', now())")
+        .bind(other_organization_id.parse::<Uuid>()?)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await;
+
+    // Assert
+    assert_eq!(vec![scope.tenant_id.unwrap()], invitations);
+    assert_eq!(vec![scope.tenant_id.unwrap()], organizations);
+    assert!(inserted.is_err());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn members_can_be_paged_sorted_and_filtered() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let (owner_id, owner_token) = signup(&test_app).await?;
+    let (_, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+    let mut member_ids = vec![];
+    for _ in 0..2 {
+        let (member_id, member_token) = signup(&test_app).await?;
+        test_app
+            .accept_invitation(&member_token, &invitation)
+            .await?;
+        member_ids.push(member_id);
+    }
+
+    // Act
+    let resp = test_app
+        .list_members_with(&owner_token, "?limit=2&sort=-joined_at")
+        .await?;
+    let link = resp.headers()["link"].to_str()?.to_owned();
+    let first = resp.json::<serde_json::Value>().await?;
+    let cursor = first["next_cursor"].as_str().unwrap();
+    let second = test_app
+        .list_members_with(
+            &owner_token,
+            &format!("?limit=2&sort=-joined_at&cursor={cursor}"),
+        )
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let owners = test_app
+        .list_members_with(&owner_token, "?role=owner")
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+
+    // Assert
+    assert!(link.contains("rel=\"next\""));
+    assert_eq!(first["items"][0]["user_id"], member_ids[1].as_str());
+    assert_eq!(first["items"][1]["user_id"], member_ids[0].as_str());
+    assert_eq!(second["items"][0]["user_id"], owner_id.as_str());
+    assert!(second.get("next_cursor").is_none());
+    assert_eq!(owners["items"].as_array().unwrap().len(), 1);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn tampered_cursors_and_unknown_parameters_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+    let (_, owner_token) = signup(&test_app).await?;
+    let (_, owner_token) = create_organization(&test_app, &owner_token).await?;
+    let invitation = invite(&test_app, &owner_token, json!({})).await?;
+    let (_, member_token) = signup(&test_app).await?;
+    test_app
+        .accept_invitation(&member_token, &invitation)
+        .await?;
+    let page = test_app
+        .list_members_with(&owner_token, "?limit=1")
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let cursor = page["next_cursor"].as_str().unwrap();
+
+    // Act
+    let responses = [
+        format!("?limit=1&cursor=x{cursor}"),
+        format!("?limit=1&sort=user_id&cursor={cursor}"),
+        format!("?limit=1&role=owner&cursor={cursor}"),
+        "?sort=password".to_owned(),
+        "?nickname=Taro".to_owned(),
+        "?limit=0".to_owned(),
+    ];
+
+    // Assert
+    for query in responses {
+        let resp = test_app.list_members_with(&owner_token, &query).await?;
+        assert_eq!(expected_code, resp.status().as_u16(), "{query}");
+    }
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app, test_app::TestApp};
+
+use crate::routes::private::{RESERVED_USER_ID, RESERVED_USER_PASS};
+
+/// Sign up a user with a fully filled in profile and return their credentials.
+async fn signup_with_profile(test_app: &TestApp) -> anyhow::Result<Basic> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({
+                "nickname": "TaroNickname",
+                "comment": "Hello!",
+                "bio": "A short bio",
+                "website": "https://example.com"
+            }),
+        )
+        .await?;
+
+    Ok(credentials)
+}
+
+fn reserved_user() -> Basic {
+    Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS))
+}
+
+async fn get_user_body(
+    test_app: &TestApp,
+    user_id: &str,
+    credentials: Basic,
+) -> anyhow::Result<serde_json::Value> {
+    let resp = test_app.get_user(user_id, Some(credentials)).await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(resp.json::<serde_json::Value>().await?["user"].clone())
+}
+
+#[actix_web::test]
+async fn fields_are_visible_to_other_users_by_default() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let owner = signup_with_profile(&test_app).await?;
+
+    // Act
+    let user = get_user_body(&test_app, owner.user_id(), reserved_user()).await?;
+
+    // Assert
+    assert_eq!(user["nickname"], "TaroNickname");
+    assert_eq!(user["comment"], "Hello!");
+    assert_eq!(user["bio"], "A short bio");
+    assert_eq!(user["website"], "https://example.com/");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn only_me_fields_are_hidden_from_other_users() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 200;
+    let owner = signup_with_profile(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .update_privacy(
+            owner.user_id(),
+            Some(owner.clone()),
+            &json!({
+                "visibility": {
+                    "nickname": "only_me",
+                    "comment": "only_me",
+                    "bio": "authenticated"
+                }
+            }),
+        )
+        .await?;
+
+    let status = resp.status();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+    assert_eq!(body["privacy"]["visibility"]["comment"], "only_me");
+    assert_eq!(body["privacy"]["hidden"], false);
+
+    let user = get_user_body(&test_app, owner.user_id(), reserved_user()).await?;
+    assert_eq!(user["nickname"], owner.user_id());
+    assert!(user.get("comment").is_none());
+    assert_eq!(user["bio"], "A short bio");
+    assert_eq!(user["website"], "https://example.com/");
+
+    let user = get_user_body(&test_app, owner.user_id(), owner.clone()).await?;
+    assert_eq!(user["nickname"], "TaroNickname");
+    assert_eq!(user["comment"], "Hello!");
+
+    let user = test_app
+        .my_user(Some(owner))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(user["nickname"], "TaroNickname");
+    assert_eq!(user["comment"], "Hello!");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn hidden_accounts_cannot_be_looked_up() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let owner = signup_with_profile(&test_app).await?;
+    let missing = test_app
+        .get_user("nobody_here", Some(reserved_user()))
+        .await?;
+    let expected_code = missing.status().as_u16();
+
+    // Act
+    test_app
+        .update_privacy(
+            owner.user_id(),
+            Some(owner.clone()),
+            &json!({ "hidden": true }),
+        )
+        .await?;
+
+    let resp = test_app
+        .get_user(owner.user_id(), Some(reserved_user()))
+        .await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    let resp = test_app
+        .get_user(owner.user_id(), Some(owner.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn admins_can_see_everything() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let owner = signup_with_profile(&test_app).await?;
+    test_app
+        .update_privacy(
+            owner.user_id(),
+            Some(owner.clone()),
+            &json!({ "hidden": true, "visibility": { "comment": "only_me" } }),
+        )
+        .await?;
+    sqlx::query("UPDATE user_ SET role = 'admin' WHERE user_id = $1")
+        .bind(RESERVED_USER_ID)
+        .execute(test_app.db().inner())
+        .await?;
+
+    // Act
+    let user = get_user_body(&test_app, owner.user_id(), reserved_user()).await?;
+
+    // Assert
+    assert_eq!(user["nickname"], "TaroNickname");
+    assert_eq!(user["comment"], "Hello!");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_update_privacy_of_another_user() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let owner = signup_with_profile(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .update_privacy(RESERVED_USER_ID, Some(owner), &json!({ "hidden": true }))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app, test_app::TestApp};
+
+async fn signup(test_app: &TestApp) -> anyhow::Result<Basic> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    Ok(Basic::new(user_id.to_owned(), Some(password.to_owned())))
+}
+
+#[actix_web::test]
+async fn blocked_users_cannot_see_the_blocker() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 404;
+    let blocker = signup(&test_app).await?;
+    let blocked = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .restrict(blocked.user_id(), "block", Some(blocker.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let resp = test_app
+        .get_user(blocker.user_id(), Some(blocked.clone()))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let resp = test_app
+        .get_user(blocked.user_id(), Some(blocker.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let blocks = test_app
+        .list_restrictions("blocks", Some(blocker))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(blocks["items"][0]["user_id"], blocked.user_id());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn blocked_users_cannot_follow_the_blocker() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 404;
+    let blocker = signup(&test_app).await?;
+    let blocked = signup(&test_app).await?;
+    test_app
+        .follow(blocker.user_id(), Some(blocked.clone()))
+        .await?;
+
+    // Act
+    test_app
+        .restrict(blocked.user_id(), "block", Some(blocker.clone()))
+        .await?;
+
+    let resp = test_app
+        .follow(blocker.user_id(), Some(blocked.clone()))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let user = test_app
+        .my_user(Some(blocker))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(user["followers_count"], 0);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn unblocking_restores_access() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let blocker = signup(&test_app).await?;
+    let blocked = signup(&test_app).await?;
+    test_app
+        .restrict(blocked.user_id(), "block", Some(blocker.clone()))
+        .await?;
+
+    // Act
+    let resp = test_app
+        .unrestrict(blocked.user_id(), "block", Some(blocker.clone()))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let resp = test_app
+        .get_user(blocker.user_id(), Some(blocked.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let resp = test_app.follow(blocker.user_id(), Some(blocked)).await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn muted_users_can_still_see_the_muter() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let muter = signup(&test_app).await?;
+    let muted = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .restrict(muted.user_id(), "mute", Some(muter.clone()))
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let resp = test_app
+        .get_user(muter.user_id(), Some(muted.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    let mutes = test_app
+        .list_restrictions("mutes", Some(muter.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(mutes["items"][0]["user_id"], muted.user_id());
+
+    let blocks = test_app
+        .list_restrictions("blocks", Some(muter))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(blocks["items"].as_array().unwrap().len(), 0);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn muted_users_are_left_out_of_search_and_follow_requests() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let muter = signup(&test_app).await?;
+    let muted = signup(&test_app).await?;
+    test_app
+        .update_privacy(
+            muter.user_id(),
+            Some(muter.clone()),
+            &json!({ "approve_followers": true }),
+        )
+        .await?;
+    test_app
+        .follow(muter.user_id(), Some(muted.clone()))
+        .await?;
+
+    // Act
+    let resp = test_app
+        .restrict(muted.user_id(), "mute", Some(muter.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    // Assert
+    let page = test_app
+        .search_users(&format!("?q={}", muted.user_id()), Some(muter.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(page["items"].as_array().unwrap().len(), 0);
+
+    let requests = test_app
+        .follow_requests(Some(muter.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(requests["items"].as_array().unwrap().len(), 0);
+
+    test_app
+        .unrestrict(muted.user_id(), "mute", Some(muter.clone()))
+        .await?;
+    let requests = test_app
+        .follow_requests(Some(muter))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(requests["items"][0]["user_id"], muted.user_id());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_block_yourself() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+    let user = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .restrict(user.user_id(), "block", Some(user.clone()))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,207 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use serde_json::json;
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+    test_app::TestApp,
+};
+
+async fn signup(test_app: &TestApp, user_id: &str) -> anyhow::Result<Basic> {
+    let mut user_data = gen_dummy_user();
+    user_data["user_id"] = json!(user_id);
+    test_app.signup(&user_data).await?;
+    let password = user_data["password"].as_str().unwrap();
+
+    Ok(Basic::new(user_id.to_owned(), Some(password.to_owned())))
+}
+
+async fn search(
+    test_app: &TestApp,
+    query: &str,
+    credentials: &Basic,
+) -> anyhow::Result<serde_json::Value> {
+    let resp = test_app
+        .search_users(query, Some(credentials.clone()))
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(resp.json::<serde_json::Value>().await?)
+}
+
+fn user_ids(page: &serde_json::Value) -> Vec<&str> {
+    page["items"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|user| user["user_id"].as_str().unwrap())
+        .collect()
+}
+
+#[actix_web::test]
+async fn prefix_matches_are_ranked_first() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let viewer = signup(&test_app, "viewer01").await?;
+    signup(&test_app, "xhanakoyama").await?;
+    signup(&test_app, "HanakoYamada").await?;
+    signup(&test_app, "tarotanaka").await?;
+
+    // Act
+    let page = search(&test_app, "?q=hanakoyam", &viewer).await?;
+
+    // Assert
+    assert_eq!(vec!["HanakoYamada", "xhanakoyama"], user_ids(&page));
+    assert!(page.get("next_cursor").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn nicknames_are_only_matched_where_visible() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let viewer = signup(&test_app, "viewer01").await?;
+    let public = signup(&test_app, "user0001").await?;
+    let private = signup(&test_app, "user0002").await?;
+    for user in [&public, &private] {
+        test_app
+            .update_user(
+                user.user_id(),
+                Some(user.clone()),
+                &json!({ "nickname": "SakuraHaruno" }),
+            )
+            .await?;
+    }
+    test_app
+        .update_privacy(
+            private.user_id(),
+            Some(private.clone()),
+            &json!({ "visibility": { "nickname": "only_me" } }),
+        )
+        .await?;
+
+    // Act
+    let page = search(&test_app, "?q=saku", &viewer).await?;
+    let own = search(&test_app, "?q=saku", &private).await?;
+
+    // Assert
+    assert_eq!(vec!["user0001"], user_ids(&page));
+    assert_eq!(page["items"][0]["nickname"], "SakuraHaruno");
+    assert_eq!(2, user_ids(&own).len());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn hidden_and_blocking_users_are_not_found() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let viewer = signup(&test_app, "viewer01").await?;
+    let hidden = signup(&test_app, "searchable1").await?;
+    let blocker = signup(&test_app, "searchable2").await?;
+    signup(&test_app, "searchable3").await?;
+    signup(&test_app, "searchable4").await?;
+    test_app
+        .update_privacy(
+            hidden.user_id(),
+            Some(hidden.clone()),
+            &json!({ "hidden": true }),
+        )
+        .await?;
+    test_app
+        .restrict(viewer.user_id(), "block", Some(blocker.clone()))
+        .await?;
+    test_app
+        .restrict("searchable4", "block", Some(viewer.clone()))
+        .await?;
+
+    // Act
+    let page = search(&test_app, "?q=searchable", &viewer).await?;
+
+    // Assert
+    assert_eq!(vec!["searchable3"], user_ids(&page));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn results_are_paginated() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let viewer = signup(&test_app, "viewer01").await?;
+    for user_id in ["paged001", "paged002", "paged003"] {
+        signup(&test_app, user_id).await?;
+    }
+
+    // Act
+    let mut found = vec![];
+    let mut query = "?q=paged&limit=2".to_owned();
+    loop {
+        let page = search(&test_app, &query, &viewer).await?;
+        found.extend(user_ids(&page).into_iter().map(String::from));
+        match page["next_cursor"].as_str() {
+            Some(cursor) => query = format!("?q=paged&limit=2&cursor={cursor}"),
+            None => break,
+        }
+    }
+    found.sort();
+
+    // Assert
+    assert_eq!(vec!["paged001", "paged002", "paged003"], found);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cursors_only_work_with_their_search_term() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let viewer = signup(&test_app, "viewer01").await?;
+    for user_id in ["paged001", "paged002"] {
+        signup(&test_app, user_id).await?;
+    }
+    let page = search(&test_app, "?q=paged&limit=1", &viewer).await?;
+    let cursor = page["next_cursor"].as_str().unwrap();
+
+    // Act
+    let resp = test_app
+        .search_users(
+            &format!("?q=viewer&limit=1&cursor={cursor}"),
+            Some(viewer.clone()),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(400, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn searches_are_rate_limited() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| settings.user.search_requests_per_minute = 2).await?;
+    let expected_code = 429;
+    let viewer = signup(&test_app, "viewer01").await?;
+    search(&test_app, "?q=anyone", &viewer).await?;
+    search(&test_app, "?q=anyone", &viewer).await?;
+
+    // Act
+    let resp = test_app
+        .search_users("?q=anyone", Some(viewer.clone()))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+    assert!(resp.headers().contains_key("retry-after"));
+
+    Ok(())
+}
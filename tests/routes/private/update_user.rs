@@ -4,7 +4,10 @@ use fake::faker::company::en::BsNoun;
 use fake::faker::company::en::CatchPhase;
 use fake::Fake;
 use serde_json::json;
-use utilities::{dummy::gen_dummy_user, spawn::spawn_app};
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+};
 
 use crate::routes::private::RESERVED_USER_ID;
 use crate::routes::private::RESERVED_USER_PASS;
@@ -44,12 +47,8 @@ async fn cannot_update_user_information_without_authorization() -> anyhow::Resul
     );
 
     assert!(body.is_object());
-    let message = match body.get("message").as_ref().unwrap() {
-        serde_json::Value::String(value) => value,
-        _ => panic!("Should have gotten a string"),
-    };
-
-    assert_eq!(message, "Authentication Failed");
+    assert_eq!(body["code"], "invalid_credentials");
+    assert_eq!(body["status"], 401);
 
     Ok(())
 }
@@ -161,12 +160,515 @@ async fn cannot_update_the_information_of_a_different_account() -> anyhow::Resul
     );
 
     assert!(body.is_object());
-    let message = match body.get("message").as_ref().unwrap() {
-        serde_json::Value::String(value) => value,
-        _ => panic!("Should have gotten a string"),
-    };
+    assert_eq!(body["code"], "forbidden");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn the_account_is_checked_before_the_body() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 403;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+
+    // Act
+    let resp = test_app
+        .patch_user(
+            user_id,
+            Some(Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS))),
+            "text/plain",
+            &json!({ "nickname": 1 }),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(expected_code, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn can_update_profile_fields() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 200;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let credentials = || Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    let bio: String = CatchPhase()
+        .fake::<String>()
+        .repeat(3)
+        .chars()
+        .take(200)
+        .collect();
+    let new_details = json!({
+        "display_name": "Taro Yamada",
+        "bio": bio,
+        "locale": "ja-JP",
+        "timezone": "Asia/Tokyo",
+        "website": "https://example.com/taro"
+    });
+
+    // Act
+    let resp = test_app
+        .update_user(user_id, Some(credentials()), &new_details)
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let body = test_app
+        .my_user(Some(credentials()))
+        .await?
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    assert_eq!(body["display_name"], "Taro Yamada");
+    assert_eq!(body["bio"], bio.as_str());
+    assert_eq!(body["locale"], "ja-JP");
+    assert_eq!(body["timezone"], "Asia/Tokyo");
+    assert_eq!(body["website"], "https://example.com/taro");
+    assert!(body.get("password").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn can_clear_a_profile_field_with_null() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let credentials = || Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials()),
+            &json!({ "comment": "hello", "display_name": "Taro" }),
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .update_user(user_id, Some(credentials()), &json!({ "comment": null }))
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(200, status.as_u16());
+
+    let body = test_app
+        .my_user(Some(credentials()))
+        .await?
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    assert!(body.get("comment").is_none());
+    assert_eq!(body["display_name"], "Taro");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_set_invalid_profile_fields() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    for (field, value) in [
+        ("locale", "not a locale"),
+        ("timezone", "Mars/Olympus_Mons"),
+        ("website", "ftp://example.com"),
+        ("avatar_url", "example"),
+        ("display_name", "   "),
+    ] {
+        // Act
+        let resp = test_app
+            .update_user(
+                user_id,
+                Some(Basic::new(user_id.to_owned(), Some(password.to_owned()))),
+                &json!({ field: value }),
+            )
+            .await?;
+
+        let status = resp.status();
+
+        let body = resp
+            .json::<serde_json::Value>()
+            .await
+            .expect("Expected a valid json body");
+
+        // Assert
+        assert_eq!(
+            expected_code,
+            status.as_u16(),
+            "Expected the api to return {} for {} but instead got {}",
+            expected_code,
+            field,
+            status.as_str()
+        );
+
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["errors"][0]["field"], field);
+    }
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn stale_updates_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 412;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    let resp = test_app.my_user(Some(credentials.clone())).await?;
+    let etag = resp.headers()["etag"].to_str()?.to_owned();
+    let resp = test_app
+        .update_user_if_match(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "First" }),
+            &etag,
+        )
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+    let new_etag = resp.headers()["etag"].to_str()?.to_owned();
+    assert_ne!(etag, new_etag);
+
+    // Act
+    let resp = test_app
+        .update_user_if_match(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "Second" }),
+            &etag,
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    // The tags of a read start with the version of the user.
+    let resp = test_app.get_user(user_id, Some(credentials)).await?;
+    let version = new_etag.trim_end_matches('"');
+    assert!(resp.headers()["etag"]
+        .to_str()?
+        .starts_with(&format!("{version}-")));
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["user"]["comment"], "First");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn strict_mode_requires_if_match() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| settings.user.require_if_match = true).await?;
+    let expected_code = 428;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    // Act
+    let resp = test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "Hello" }),
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let resp = test_app
+        .update_user_if_match(
+            user_id,
+            Some(credentials),
+            &json!({ "comment": "Hello" }),
+            "*",
+        )
+        .await?;
+    assert_eq!(200, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn merge_patches_clear_fields_with_null() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "hello", "bio": "A bio" }),
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/merge-patch+json",
+            &json!({ "comment": null }),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let body = test_app
+        .my_user(Some(credentials))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert!(body.get("comment").is_none());
+    assert_eq!(body["bio"], "A bio");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn json_patches_test_replace_and_remove_fields() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "hello", "bio": "A bio" }),
+        )
+        .await?;
+
+    // Act
+    let resp = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/json-patch+json",
+            &json!([
+                { "op": "test", "path": "/comment", "value": "hello" },
+                { "op": "replace", "path": "/comment", "value": "goodbye" },
+                { "op": "test", "path": "/comment", "value": "goodbye" },
+                { "op": "remove", "path": "/bio" },
+                { "op": "test", "path": "/website", "value": null }
+            ]),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+
+    let body = test_app
+        .my_user(Some(credentials))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(body["comment"], "goodbye");
+    assert!(body.get("bio").is_none());
+
+    Ok(())
+}
 
-    assert_eq!(message, "No Permission for Update");
+#[actix_web::test]
+async fn json_patches_are_applied_atomically() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    test_app
+        .update_user(
+            user_id,
+            Some(credentials.clone()),
+            &json!({ "comment": "hello" }),
+        )
+        .await?;
+
+    // Act
+    let failed_test = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/json-patch+json",
+            &json!([
+                { "op": "replace", "path": "/bio", "value": "A bio" },
+                { "op": "test", "path": "/comment", "value": "goodbye" }
+            ]),
+        )
+        .await?;
+    let invalid_value = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/json-patch+json",
+            &json!([
+                { "op": "replace", "path": "/bio", "value": "A bio" },
+                { "op": "replace", "path": "/comment", "value": "a".repeat(1000) }
+            ]),
+        )
+        .await?;
+    let unknown_path = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/json-patch+json",
+            &json!([{ "op": "replace", "path": "/password", "value": "secret" }]),
+        )
+        .await?;
+    let unsupported_op = test_app
+        .patch_user(
+            user_id,
+            Some(credentials.clone()),
+            "application/json-patch+json",
+            &json!([{ "op": "move", "from": "/comment", "path": "/bio" }]),
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(409, failed_test.status().as_u16());
+    assert_eq!(400, invalid_value.status().as_u16());
+    assert_eq!(400, unknown_path.status().as_u16());
+    assert_eq!(400, unsupported_op.status().as_u16());
+
+    let body = test_app
+        .my_user(Some(credentials))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    assert_eq!(body["comment"], "hello");
+    assert!(body.get("bio").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn unsupported_content_types_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 415;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+    let credentials = Basic::new(user_id.to_owned(), Some(password.to_owned()));
+
+    // Act
+    let resp = test_app
+        .patch_user(
+            user_id,
+            Some(credentials),
+            "text/plain",
+            &json!({ "comment": "hello" }),
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn errors_are_localized_for_the_saved_locale() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+    let credentials = || Basic::new(user_id.to_owned(), Some(password.to_owned()));
+    test_app
+        .update_user(user_id, Some(credentials()), &json!({ "locale": "ja-JP" }))
+        .await?;
+
+    // Act
+    let resp = test_app
+        .update_user(
+            user_id,
+            Some(credentials()),
+            &json!({ "timezone": "Mars/Olympus_Mons" }),
+        )
+        .await?;
+    let content_language = resp.headers()["content-language"].to_str()?.to_owned();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(content_language, "ja");
+    assert_eq!(body["title"], "入力内容が不正です");
+    assert_eq!(
+        body["errors"][0]["detail"],
+        "IANA のタイムゾーン名である必要があります"
+    );
 
     Ok(())
 }
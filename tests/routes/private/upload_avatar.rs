@@ -0,0 +1,304 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use image::{GenericImageView, ImageFormat, RgbImage};
+use std::io::Cursor;
+use track_api_challenge::configuration::blob::{BlobBackend, S3Settings};
+use track_api_challenge::secrecy::Secret;
+use utilities::{
+    dummy::gen_dummy_user,
+    s3_stub::spawn_s3_stub,
+    spawn::{spawn_app, spawn_app_with},
+    test_app::TestApp,
+};
+
+const EXIF_MARKER: &[u8] = b"SECRET-GPS-COORDINATES";
+
+fn gen_image(width: u32, height: u32, format: ImageFormat) -> Vec<u8> {
+    let image = RgbImage::from_fn(width, height, |x, y| {
+        image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+    });
+    let mut data = Vec::new();
+    image.write_to(&mut Cursor::new(&mut data), format).unwrap();
+    data
+}
+
+/// Generate a jpeg with an APP1 segment carrying EXIF data right after the SOI marker.
+fn gen_jpeg_with_exif(width: u32, height: u32) -> Vec<u8> {
+    let jpeg = gen_image(width, height, ImageFormat::Jpeg);
+    let mut exif = b"Exif\0\0MM\0*\0\0\0\x08".to_vec();
+    exif.extend_from_slice(EXIF_MARKER);
+    let length = (exif.len() + 2) as u16;
+
+    let mut data = jpeg[..2].to_vec();
+    data.extend_from_slice(&[0xFF, 0xE1]);
+    data.extend_from_slice(&length.to_be_bytes());
+    data.extend_from_slice(&exif);
+    data.extend_from_slice(&jpeg[2..]);
+    data
+}
+
+async fn signup(test_app: &TestApp) -> anyhow::Result<Basic> {
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data.get("user_id").unwrap().as_str().unwrap();
+    let password = user_data.get("password").unwrap().as_str().unwrap();
+
+    Ok(Basic::new(user_id.to_owned(), Some(password.to_owned())))
+}
+
+#[actix_web::test]
+async fn can_upload_an_avatar() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 200;
+    let credentials = signup(&test_app).await?;
+    let upload = gen_jpeg_with_exif(400, 300);
+    assert!(upload
+        .windows(EXIF_MARKER.len())
+        .any(|window| window == EXIF_MARKER));
+
+    // Act
+    let resp = test_app
+        .upload_avatar(Some(credentials.clone()), upload, "image/jpeg")
+        .await?;
+
+    let status = resp.status();
+
+    let body = resp
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}: {}",
+        expected_code,
+        status.as_str(),
+        body
+    );
+
+    let thumbnails = body["user"]["avatar_thumbnails"].as_object().unwrap();
+    assert_eq!(thumbnails.len(), 3);
+    assert_eq!(body["user"]["avatar_url"], thumbnails["256"]);
+
+    for (size, url) in thumbnails {
+        let resp = test_app.get_url(url.as_str().unwrap()).await?;
+        assert_eq!(200, resp.status().as_u16());
+        assert_eq!(resp.headers()["content-type"], "image/png");
+
+        let data = resp.bytes().await?;
+        assert!(!data
+            .windows(EXIF_MARKER.len())
+            .any(|window| window == EXIF_MARKER));
+
+        let size: u32 = size.parse()?;
+        let thumbnail = image::load_from_memory(&data)?;
+        assert_eq!(thumbnail.dimensions(), (size, size));
+    }
+
+    let resp = test_app.my_user(Some(credentials)).await?;
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["avatar_thumbnails"].as_object().unwrap().len(), 3);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_upload_a_file_which_is_not_an_image() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 415;
+    let credentials = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .upload_avatar(
+            Some(credentials),
+            b"definitely not an image".to_vec(),
+            "image/png",
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_upload_an_image_which_is_too_small() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+    let credentials = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .upload_avatar(
+            Some(credentials),
+            gen_image(16, 16, ImageFormat::Png),
+            "image/png",
+        )
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_upload_an_avatar_without_authorization() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 401;
+
+    // Act
+    let resp = test_app
+        .upload_avatar(None, gen_image(128, 128, ImageFormat::Png), "image/png")
+        .await?;
+
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn can_upload_an_avatar_to_an_s3_compatible_store() -> anyhow::Result<()> {
+    // Arrange
+    let s3 = spawn_s3_stub().await?;
+    let test_app = spawn_app_with(|settings| {
+        settings.blob.backend = BlobBackend::S3;
+        settings.blob.s3 = Some(S3Settings {
+            endpoint: s3.endpoint().to_owned(),
+            bucket: "avatars-bucket".into(),
+            region: "us-east-1".into(),
+            accesskey: Secret::new("minio".into()),
+            secretkey: Secret::new("minio-secret".into()),
+        });
+    })
+    .await?;
+    let credentials = signup(&test_app).await?;
+
+    // Act
+    let resp = test_app
+        .upload_avatar(
+            Some(credentials.clone()),
+            gen_image(300, 300, ImageFormat::Png),
+            "image/png",
+        )
+        .await?;
+
+    let status = resp.status();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(200, status.as_u16(), "{body}");
+
+    let keys = s3.keys();
+    assert_eq!(keys.len(), 3);
+    assert!(keys
+        .iter()
+        .all(|key| key.starts_with("avatars-bucket/avatars/")));
+
+    let url = body["user"]["avatar_url"].as_str().unwrap();
+    let resp = test_app.get_url(url).await?;
+    assert_eq!(200, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "image/png");
+
+    // Replacing the avatar removes the previous thumbnails
+    test_app
+        .upload_avatar(
+            Some(credentials),
+            gen_image(200, 200, ImageFormat::Png),
+            "image/png",
+        )
+        .await?;
+
+    let new_keys = s3.keys();
+    assert_eq!(new_keys.len(), 3);
+    assert!(new_keys.iter().all(|key| !keys.contains(key)));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn concurrent_uploads_leave_only_the_current_avatar_in_the_store() -> anyhow::Result<()> {
+    // Arrange
+    let s3 = spawn_s3_stub().await?;
+    let test_app = spawn_app_with(|settings| {
+        settings.blob.backend = BlobBackend::S3;
+        settings.blob.s3 = Some(S3Settings {
+            endpoint: s3.endpoint().to_owned(),
+            bucket: "avatars-bucket".into(),
+            region: "us-east-1".into(),
+            accesskey: Secret::new("minio".into()),
+            secretkey: Secret::new("minio-secret".into()),
+        });
+    })
+    .await?;
+    let credentials = signup(&test_app).await?;
+    test_app
+        .upload_avatar(
+            Some(credentials.clone()),
+            gen_image(300, 300, ImageFormat::Png),
+            "image/png",
+        )
+        .await?;
+
+    // Act
+    let (first, second) = futures_util::future::join(
+        test_app.upload_avatar(
+            Some(credentials.clone()),
+            gen_image(200, 200, ImageFormat::Png),
+            "image/png",
+        ),
+        test_app.upload_avatar(
+            Some(credentials.clone()),
+            gen_image(250, 250, ImageFormat::Png),
+            "image/png",
+        ),
+    )
+    .await;
+
+    // Assert
+    assert_eq!(200, first?.status().as_u16());
+    assert_eq!(200, second?.status().as_u16());
+
+    let resp = test_app.my_user(Some(credentials)).await?;
+    let body = resp.json::<serde_json::Value>().await?;
+    let url = body["avatar_url"].as_str().unwrap();
+    let current = url.rsplit_once('/').unwrap().0.rsplit_once('/').unwrap().1;
+
+    let keys = s3.keys();
+    assert_eq!(keys.len(), 3, "{keys:?}");
+    assert!(keys.iter().all(|key| key.contains(current)));
+
+    Ok(())
+}
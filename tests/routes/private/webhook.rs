@@ -0,0 +1,258 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use hmac::{Hmac, Mac};
+use serde_json::json;
+use sha2::Sha256;
+use std::time::Duration;
+use utilities::{
+    dummy::gen_dummy_user,
+    spawn::{spawn_app, spawn_app_with},
+    test_app::TestApp,
+    webhook_receiver::{spawn_webhook_receiver, ReceivedRequest},
+};
+
+use crate::routes::private::{RESERVED_USER_ID, RESERVED_USER_PASS};
+
+const SECRET: &str = "a-secret-of-the-receiver";
+
+/// Make the reserved user an admin, and return their credentials.
+async fn admin(test_app: &mut TestApp) -> anyhow::Result<Basic> {
+    sqlx::query("UPDATE user_ SET role = 'admin' WHERE user_id = $1")
+        .bind(RESERVED_USER_ID)
+        .execute(test_app.db().inner())
+        .await?;
+
+    Ok(Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS)))
+}
+
+async fn spawn_dispatching_app() -> anyhow::Result<TestApp> {
+    spawn_app_with(|c| {
+        c.webhook.poll_interval_ms = 50;
+        c.webhook.retry_backoff_seconds = 0;
+        c.webhook.max_attempts = 2;
+    })
+    .await
+}
+
+fn is_signed(request: &ReceivedRequest) -> bool {
+    let timestamp = request.header("Webhook-Timestamp").unwrap();
+    let signature = request.header("Webhook-Signature").unwrap();
+    let hex = signature.strip_prefix("sha256=").unwrap();
+    let expected = (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+        .collect::<Vec<u8>>();
+
+    let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(&request.body);
+    mac.verify_slice(&expected).is_ok()
+}
+
+/// List deliveries with `status` until there are `count` of them.
+async fn wait_for_deliveries(
+    test_app: &TestApp,
+    status: &str,
+    count: usize,
+    credentials: &Basic,
+) -> anyhow::Result<Vec<serde_json::Value>> {
+    for _ in 0..100 {
+        let body = test_app
+            .list_deliveries(&format!("?status={status}"), Some(credentials.clone()))
+            .await?
+            .json::<serde_json::Value>()
+            .await?;
+        let deliveries = body["deliveries"].as_array().unwrap().clone();
+        if deliveries.len() >= count {
+            return Ok(deliveries);
+        }
+        actix_web::rt::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("Expected {count} {status} deliveries");
+}
+
+#[actix_web::test]
+async fn account_events_are_delivered_with_a_signature() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_dispatching_app().await?;
+    let admin = admin(&mut test_app).await?;
+    let receiver = spawn_webhook_receiver().await?;
+    let subscription = json!({
+        "url": receiver.url(),
+        "secret": SECRET,
+        "event_types": ["user.created", "user.deleted"],
+    });
+    let resp = test_app.create_webhook(&subscription, Some(admin)).await?;
+    assert_eq!(201, resp.status().as_u16());
+    let user_data = gen_dummy_user();
+
+    // Act
+    test_app.signup(&user_data).await?;
+    let requests = receiver.wait_for(1).await;
+
+    // Assert
+    let request = &requests[0];
+    assert_eq!(request.header("Webhook-Event"), Some("user.created"));
+    assert!(request.header("Webhook-Id").is_some());
+    assert!(is_signed(request), "The signature does not match");
+    let body = request.json();
+    assert_eq!(body["type"], "user.created");
+    assert_eq!(body["data"]["user_id"], user_data["user_id"]);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn only_subscribed_events_are_delivered() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_dispatching_app().await?;
+    let admin = admin(&mut test_app).await?;
+    let receiver = spawn_webhook_receiver().await?;
+    let subscription = json!({
+        "url": receiver.url(),
+        "secret": SECRET,
+        "event_types": ["user.deleted"],
+    });
+    test_app
+        .create_webhook(&subscription, Some(admin.clone()))
+        .await?;
+    let user_data = gen_dummy_user();
+    let user_id = user_data["user_id"].as_str().unwrap();
+    let password = user_data["password"].as_str().unwrap();
+
+    // Act
+    test_app.signup(&user_data).await?;
+    test_app
+        .close_account(Some(Basic::new(
+            user_id.to_owned(),
+            Some(password.to_owned()),
+        )))
+        .await?;
+    let requests = receiver.wait_for(1).await;
+
+    // Assert
+    assert_eq!(requests.len(), 1);
+    assert_eq!(requests[0].header("Webhook-Event"), Some("user.deleted"));
+    let deliveries = wait_for_deliveries(&test_app, "delivered", 1, &admin).await?;
+    assert_eq!(deliveries[0]["event_type"], "user.deleted");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn failed_deliveries_are_dead_until_replayed() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_dispatching_app().await?;
+    let admin = admin(&mut test_app).await?;
+    let receiver = spawn_webhook_receiver().await?;
+    receiver.respond_with(500);
+    let subscription = json!({
+        "url": receiver.url(),
+        "secret": SECRET,
+        "event_types": ["user.created"],
+    });
+    test_app
+        .create_webhook(&subscription, Some(admin.clone()))
+        .await?;
+    test_app.create_and_signup_user().await?;
+    let dead = wait_for_deliveries(&test_app, "dead", 1, &admin).await?;
+    let requests = receiver.wait_for(2).await;
+    receiver.respond_with(200);
+
+    // Act
+    let resp = test_app
+        .replay_delivery(dead[0]["id"].as_str().unwrap(), Some(admin.clone()))
+        .await?;
+
+    // Assert
+    assert_eq!(dead[0]["attempts"], 2);
+    assert_eq!(
+        requests[0].header("Webhook-Id"),
+        requests[1].header("Webhook-Id")
+    );
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["delivery"]["status"], "pending");
+    let delivered = wait_for_deliveries(&test_app, "delivered", 1, &admin).await?;
+    assert_eq!(delivered[0]["id"], dead[0]["id"]);
+
+    let resp = test_app
+        .replay_delivery(dead[0]["id"].as_str().unwrap(), Some(admin))
+        .await?;
+    assert_eq!(409, resp.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn subscriptions_can_be_listed_and_deleted() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let admin = admin(&mut test_app).await?;
+    let subscription = json!({
+        "url": "https://example.com/hook",
+        "secret": SECRET,
+        "event_types": ["user.updated"],
+    });
+    let body = test_app
+        .create_webhook(&subscription, Some(admin.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let id = body["subscription"]["id"].as_str().unwrap();
+
+    // Act
+    let listed = test_app
+        .list_webhooks(Some(admin.clone()))
+        .await?
+        .json::<serde_json::Value>()
+        .await?;
+    let deleted = test_app.delete_webhook(id, Some(admin.clone())).await?;
+    let deleted_again = test_app.delete_webhook(id, Some(admin)).await?;
+
+    // Assert
+    assert_eq!(listed["subscriptions"][0]["id"], id);
+    assert!(listed["subscriptions"][0]["secret"].is_null());
+    assert_eq!(200, deleted.status().as_u16());
+    assert_eq!(404, deleted_again.status().as_u16());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn unknown_event_types_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let admin = admin(&mut test_app).await?;
+    let subscription = json!({
+        "url": "https://example.com/hook",
+        "secret": SECRET,
+        "event_types": ["user.renamed"],
+    });
+
+    // Act
+    let resp = test_app.create_webhook(&subscription, Some(admin)).await?;
+
+    // Assert
+    assert_eq!(400, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["errors"][0]["field"], "event_types");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn only_admins_can_manage_webhooks() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .list_webhooks(Some(Basic::new(RESERVED_USER_ID, Some(RESERVED_USER_PASS))))
+        .await?;
+
+    // Assert
+    assert_eq!(403, resp.status().as_u16());
+
+    Ok(())
+}
@@ -0,0 +1,280 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use futures_util::future::join;
+use serde_json::json;
+use utilities::dummy::gen_dummy_user;
+use utilities::spawn::{spawn_app, spawn_app_with};
+use utilities::test_app::TestApp;
+use uuid::Uuid;
+
+async fn count_users(test_app: &mut TestApp, user_id: &serde_json::Value) -> anyhow::Result<i64> {
+    let count = sqlx::query_scalar("SELECT COUNT(*) FROM user_ WHERE user_id = $1")
+        .bind(user_id.as_str().unwrap())
+        .fetch_one(test_app.db().inner())
+        .await?;
+
+    Ok(count)
+}
+
+#[actix_web::test]
+async fn retried_signups_replay_the_first_response() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let key = Uuid::new_v4().to_string();
+    let first = test_app.signup_with_key(&user_data, &key).await?;
+    let first_status = first.status();
+    let first_body = first.bytes().await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(201, first_status.as_u16());
+    assert_eq!(201, resp.status().as_u16());
+    assert_eq!(resp.headers()["Idempotent-Replayed"], "true");
+    assert_eq!(resp.headers()["Content-Type"], "application/json");
+    assert_eq!(resp.bytes().await?, first_body);
+    assert_eq!(count_users(&mut test_app, &user_data["user_id"]).await?, 1);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn concurrent_retries_sign_up_once() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let key = Uuid::new_v4().to_string();
+
+    // Act
+    let (first, second) = join(
+        test_app.signup_with_key(&user_data, &key),
+        test_app.signup_with_key(&user_data, &key),
+    )
+    .await;
+
+    // Assert
+    let mut statuses = [first?.status().as_u16(), second?.status().as_u16()];
+    statuses.sort();
+    assert!(
+        statuses == [201, 201] || statuses == [201, 409],
+        "Expected one signup and a replay or a conflict, got {statuses:?}"
+    );
+    assert_eq!(count_users(&mut test_app, &user_data["user_id"]).await?, 1);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn a_key_reused_for_another_request_is_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let key = Uuid::new_v4().to_string();
+    test_app.signup_with_key(&gen_dummy_user(), &key).await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&gen_dummy_user(), &key).await?;
+
+    // Assert
+    assert_eq!(422, resp.status().as_u16());
+    assert!(resp.headers().get("Idempotent-Replayed").is_none());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "idempotency_key_reused");
+    assert_eq!(
+        body["title"],
+        "The idempotency key was used for a different request"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn failed_requests_are_replayed_too() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let key = Uuid::new_v4().to_string();
+    let user_data = json!({ "user_id": "taro", "password": "password123" });
+    test_app.signup_with_key(&user_data, &key).await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(400, resp.status().as_u16());
+    assert_eq!(resp.headers()["Idempotent-Replayed"], "true");
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "validation_failed");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn expired_keys_can_be_used_again() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|c| c.idempotency.ttl_seconds = 0).await?;
+    let user_data = gen_dummy_user();
+    let key = Uuid::new_v4().to_string();
+    test_app.signup_with_key(&user_data, &key).await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(409, resp.status().as_u16());
+    assert!(resp.headers().get("Idempotent-Replayed").is_none());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "user_id_taken");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn keys_abandoned_by_their_request_are_taken_over() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let key = Uuid::new_v4().to_string();
+    test_app.signup_with_key(&user_data, &key).await?;
+    // As if the first request had died before its response was stored.
+    sqlx::query(
+        "UPDATE idempotency_key SET status = NULL, headers = NULL, body = NULL, locked_until = created_at WHERE key = $1",
+    )
+    .bind(&key)
+    .execute(test_app.db().inner())
+    .await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(409, resp.status().as_u16());
+    assert!(resp.headers().get("Idempotent-Replayed").is_none());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "user_id_taken");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn keys_held_by_their_request_are_in_progress() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let key = Uuid::new_v4().to_string();
+    test_app.signup_with_key(&user_data, &key).await?;
+    sqlx::query(
+        "UPDATE idempotency_key SET status = NULL, headers = NULL, body = NULL, locked_until = expires_at WHERE key = $1",
+    )
+    .bind(&key)
+    .execute(test_app.db().inner())
+    .await?;
+
+    // Act
+    let resp = test_app.signup_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(409, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "idempotency_key_in_progress");
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn signin_tokens_are_not_stored() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let key = Uuid::new_v4().to_string();
+    let first = test_app.signin_with_key(&user_data, &key).await?;
+
+    // Act
+    let resp = test_app.signin_with_key(&user_data, &key).await?;
+
+    // Assert
+    assert_eq!(200, first.status().as_u16());
+    assert_eq!(first.headers()["Cache-Control"], "no-store");
+    assert_eq!(200, resp.status().as_u16());
+    assert!(resp.headers().get("Idempotent-Replayed").is_none());
+    let stored: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM idempotency_key WHERE key = $1")
+        .bind(&key)
+        .fetch_one(test_app.db().inner())
+        .await?;
+    assert_eq!(stored, 0);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn keys_are_scoped_to_the_credentials() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let key = Uuid::new_v4().to_string();
+    let mut credentials = Vec::new();
+    for _ in 0..2 {
+        let user_data = gen_dummy_user();
+        test_app.signup(&user_data).await?;
+        credentials.push(Basic::new(
+            user_data["user_id"].as_str().unwrap().to_owned(),
+            Some(user_data["password"].as_str().unwrap().to_owned()),
+        ));
+    }
+
+    // Act
+    let first = test_app
+        .close_account_with_key(credentials[0].clone(), &key)
+        .await?;
+    let second = test_app
+        .close_account_with_key(credentials[1].clone(), &key)
+        .await?;
+
+    // Assert
+    assert_eq!(200, first.status().as_u16());
+    assert_eq!(200, second.status().as_u16());
+    assert!(second.headers().get("Idempotent-Replayed").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn keys_are_not_replayed_to_invalid_credentials() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let key = Uuid::new_v4().to_string();
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let user_id = user_data["user_id"].as_str().unwrap().to_owned();
+    let password = user_data["password"].as_str().unwrap().to_owned();
+    test_app
+        .close_account_with_key(Basic::new(user_id.clone(), Some(password)), &key)
+        .await?;
+
+    // Act
+    let resp = test_app
+        .close_account_with_key(Basic::new(user_id, Some("wrong password")), &key)
+        .await?;
+
+    // Assert
+    assert_eq!(401, resp.status().as_u16());
+    assert!(resp.headers().get("Idempotent-Replayed").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn invalid_keys_are_rejected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let key = "k".repeat(256);
+
+    // Act
+    let resp = test_app.signup_with_key(&gen_dummy_user(), &key).await?;
+
+    // Assert
+    assert_eq!(400, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["code"], "invalid_idempotency_key");
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app_with};
+
+#[actix_web::test]
+async fn user_cache_reports_hits_and_misses() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app_with(|settings| settings.cache.user_capacity = 1).await?;
+    let first = gen_dummy_user();
+    let second = gen_dummy_user();
+    test_app.signup(&first).await?;
+    test_app.signup(&second).await?;
+    let credentials = Basic::new(
+        first["user_id"].as_str().unwrap().to_owned(),
+        Some(first["password"].as_str().unwrap().to_owned()),
+    );
+
+    // Act
+    test_app.my_user(Some(credentials.clone())).await?;
+    test_app.my_user(Some(credentials.clone())).await?;
+    test_app
+        .get_user(second["user_id"].as_str().unwrap(), Some(credentials))
+        .await?;
+    let resp = test_app.metrics().await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let body = resp.json::<serde_json::Value>().await?;
+    let stats = &body["user_cache"];
+    assert!(stats["hits"].as_u64().unwrap() > 0);
+    assert!(stats["misses"].as_u64().unwrap() > 0);
+    assert!(stats["evictions"].as_u64().unwrap() > 0);
+    assert_eq!(stats["entries"], 1);
+    assert_eq!(stats["capacity"], 1);
+
+    Ok(())
+}
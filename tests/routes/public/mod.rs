@@ -1,7 +1,12 @@
 use utilities::spawn::spawn_app;
 
 mod health;
+mod idempotency;
+mod metrics;
+mod negotiation;
+mod openapi;
 mod signup;
+mod versioning;
 
 #[actix_web::test]
 async fn accessing_base_url_returns_404() -> anyhow::Result<()> {
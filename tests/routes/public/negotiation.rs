@@ -0,0 +1,90 @@
+use serde_json::json;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app};
+
+#[actix_web::test]
+async fn signup_accepts_and_returns_message_pack() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let body = rmp_serde::to_vec_named(&user_data)?;
+
+    // Act
+    let resp = test_app
+        .signup_encoded(body, "application/msgpack", "application/msgpack")
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "application/msgpack");
+    assert!(resp.headers()["vary"].to_str()?.contains("accept"));
+    let body: serde_json::Value = rmp_serde::from_slice(&resp.bytes().await?)?;
+    assert_eq!(body["user"]["user_id"], user_data["user_id"]);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn signup_accepts_and_returns_cbor() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let user_data = gen_dummy_user();
+    let mut body = Vec::new();
+    ciborium::into_writer(&user_data, &mut body)?;
+
+    // Act
+    let resp = test_app
+        .signup_encoded(body, "application/cbor", "application/cbor")
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "application/cbor");
+    let body: serde_json::Value = ciborium::from_reader(&resp.bytes().await?[..])?;
+    assert_eq!(body["user"]["user_id"], user_data["user_id"]);
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn the_most_preferred_format_is_returned() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let body = rmp_serde::to_vec_named(&gen_dummy_user())?;
+
+    // Act
+    let resp = test_app
+        .signup_encoded(
+            body,
+            "application/msgpack",
+            "application/cbor;q=0.5, application/json",
+        )
+        .await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "application/json");
+    resp.json::<serde_json::Value>().await?;
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn problems_are_returned_in_the_accepted_format() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let body = rmp_serde::to_vec_named(&json!({ "user_id": 42 }))?;
+
+    // Act
+    let resp = test_app
+        .signup_encoded(body, "application/msgpack", "application/cbor")
+        .await?;
+
+    // Assert
+    assert_eq!(400, resp.status().as_u16());
+    assert_eq!(resp.headers()["content-type"], "application/problem+cbor");
+    let problem: serde_json::Value = ciborium::from_reader(&resp.bytes().await?[..])?;
+    assert_eq!(problem["status"], 400);
+    assert_eq!(problem["code"], "invalid_body");
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::path::PathBuf;
+use utilities::spawn::spawn_app;
+
+/// The checked in OpenAPI document. Run the tests with `UPDATE_SNAPSHOTS=1` to
+/// rewrite it after changing the API on purpose.
+fn snapshot_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/snapshots/openapi.json")
+}
+
+#[actix_web::test]
+async fn openapi_document_matches_the_snapshot() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app.openapi().await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    let document = resp.json::<serde_json::Value>().await?;
+    assert!(document["openapi"].as_str().unwrap().starts_with("3.1"));
+
+    let path = snapshot_path();
+    if std::env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        std::fs::write(&path, serde_json::to_string_pretty(&document)? + "\n")?;
+    }
+    let snapshot: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path)?)?;
+    assert_eq!(
+        snapshot, document,
+        "The OpenAPI document changed, rerun the tests with UPDATE_SNAPSHOTS=1 if this is intended"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn swagger_ui_is_served() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app.docs().await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert!(resp.text().await?.contains("swagger"));
+
+    Ok(())
+}
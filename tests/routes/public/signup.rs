@@ -25,14 +25,9 @@ async fn cannot_sign_up_account_without_user_id_and_password() -> anyhow::Result
     );
 
     assert!(body.is_object());
-    let message = match body.get("message").as_ref().unwrap() {
-        serde_json::Value::String(value) => value,
-        _ => panic!("Should have gotten a string"),
-    };
-
-    assert_eq!(message, "Account creation failed");
+    assert_eq!(body["code"], "invalid_body");
 
-    let message = match body.get("cause").as_ref().unwrap() {
+    let message = match body.get("detail").as_ref().unwrap() {
         serde_json::Value::String(value) => value,
         _ => panic!("Should have gotten a string"),
     };
@@ -84,3 +79,259 @@ async fn can_create_an_account() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[actix_web::test]
+async fn cannot_sign_up_with_a_case_variant_of_an_existing_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 409;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "taroyamada", "password": "password123" }))
+        .await?;
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_sign_up_with_a_user_id_that_looks_like_an_existing_one() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 409;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "TaroYarnada", "password": "password123" }))
+        .await?;
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_sign_up_with_a_reserved_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "Administrator", "password": "password123" }))
+        .await?;
+    let status = resp.status();
+    let body = resp
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let cause = body.get("detail").unwrap().as_str().unwrap();
+    assert!(cause.contains("reserved"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn cannot_sign_up_with_disallowed_characters() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "tаro_yamada", "password": "password123" }))
+        .await?;
+    let status = resp.status();
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn short_user_id_error_reports_the_minimum_length() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let expected_code = 400;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "taro", "password": "password123" }))
+        .await?;
+    let status = resp.status();
+    let body = resp
+        .json::<serde_json::Value>()
+        .await
+        .expect("Expected a valid json body");
+
+    // Assert
+    assert_eq!(
+        expected_code,
+        status.as_u16(),
+        "Expected the api to return {} but instead got {}",
+        expected_code,
+        status.as_str()
+    );
+
+    let cause = body.get("detail").unwrap().as_str().unwrap();
+    assert!(cause.ends_with("greater than or equal to 8"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn errors_are_problem_documents_with_invalid_fields() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .signup(&json!({ "user_id": "taro", "password": "password123" }))
+        .await?;
+    let content_type = resp.headers()["content-type"].to_str()?.to_owned();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(content_type, "application/problem+json");
+    assert_eq!(body["type"], "/problems/validation_failed");
+    assert_eq!(body["code"], "validation_failed");
+    assert_eq!(body["title"], "The submission is invalid");
+    assert_eq!(body["status"], 400);
+    assert_eq!(body["instance"], "/signup");
+    assert_eq!(body["errors"][0]["field"], "user_id");
+    assert!(body["errors"][0]["detail"].is_string());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn malformed_bodies_are_problem_documents() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .signin(&json!({ "user_id": "taro_yamada" }))
+        .await?;
+    let status = resp.status();
+    let content_type = resp.headers()["content-type"].to_str()?.to_owned();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(400, status.as_u16());
+    assert_eq!(content_type, "application/problem+json");
+    assert_eq!(body["code"], "invalid_body");
+    assert_eq!(body["instance"], "/signin");
+    assert!(body["detail"].as_str().unwrap().contains("password"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn errors_are_localized_for_the_accepted_language() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let signup = json!({ "user_id": "taro", "password": "password123" });
+
+    for accept_language in ["ja", "ja-JP", "fr;q=0.9, en;q=0.5, ja"] {
+        // Act
+        let resp = test_app.signup_in(&signup, accept_language).await?;
+        let content_language = resp.headers()["content-language"].to_str()?.to_owned();
+        let body = resp.json::<serde_json::Value>().await?;
+
+        // Assert
+        assert_eq!(content_language, "ja", "for {accept_language}");
+        assert_eq!(body["code"], "validation_failed");
+        assert_eq!(body["title"], "入力内容が不正です");
+        assert_eq!(
+            body["errors"][0]["detail"],
+            "8 文字以上である必要があります"
+        );
+        assert_eq!(
+            body["detail"],
+            "user_id の値が不正です: 8 文字以上である必要があります"
+        );
+    }
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn errors_fall_back_to_english() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+    let signup = json!({ "user_id": "taro", "password": "password123" });
+
+    // Act
+    let resp = test_app.signup_in(&signup, "fr, ja;q=0").await?;
+    let content_language = resp.headers()["content-language"].to_str()?.to_owned();
+    let body = resp.json::<serde_json::Value>().await?;
+
+    // Assert
+    assert_eq!(content_language, "en");
+    assert_eq!(body["title"], "The submission is invalid");
+    assert_eq!(
+        body["errors"][0]["detail"],
+        "must be greater than or equal to 8"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn users_cannot_be_stored_without_the_forms_of_their_user_id() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+
+    // Act
+    let inserted = sqlx::query(
+        "INSERT INTO user_ (id, user_id, password, created_at) VALUES ($1, $2, 'x', now())",
+    )
+    .bind(uuid::Uuid::new_v4())
+    .bind("WithoutForms")
+    .execute(test_app.db().inner())
+    .await;
+
+    // Assert
+    let Err(sqlx::Error::Database(e)) = inserted else {
+        panic!("Expected the insert to be rejected");
+    };
+    assert_eq!(e.constraint(), Some("user_handle_forms_present"));
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use actix_web_httpauth::headers::authorization::Basic;
+use utilities::{dummy::gen_dummy_user, spawn::spawn_app};
+
+#[actix_web::test]
+async fn legacy_paths_are_redirected_to_the_first_version() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .unversioned(reqwest::Method::POST, "/signup?lang=en")
+        .await?;
+
+    // Assert
+    assert_eq!(308, resp.status().as_u16());
+    assert_eq!(
+        resp.headers()["location"].to_str()?,
+        "/v1/signup?lang=en",
+        "Expected the redirect to keep the path and query"
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn unknown_paths_are_not_redirected() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app
+        .unversioned(reqwest::Method::GET, "/recipes")
+        .await?;
+
+    // Assert
+    assert_eq!(404, resp.status().as_u16());
+    assert!(resp.headers().get("location").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn first_version_announces_its_deprecation() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app.signup(&gen_dummy_user()).await?;
+
+    // Assert
+    let headers = resp.headers();
+    assert!(headers["deprecation"].to_str()?.starts_with('@'));
+    assert!(headers["sunset"].to_str()?.ends_with("GMT"));
+    assert_eq!(
+        headers["link"].to_str()?,
+        "</v2/signup>; rel=\"successor-version\""
+    );
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn first_version_announces_its_deprecation_on_errors() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let resp = test_app.my_user(None).await?;
+
+    // Assert
+    assert_eq!(401, resp.status().as_u16());
+    assert!(resp.headers().contains_key("deprecation"));
+    assert!(resp.headers().contains_key("sunset"));
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn second_version_responds_with_bare_resources() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    test_app.use_api_version("v2");
+    let user_data = gen_dummy_user();
+
+    // Act
+    let resp = test_app.signup(&user_data).await?;
+
+    // Assert
+    assert_eq!(200, resp.status().as_u16());
+    assert!(!resp.headers().contains_key("deprecation"));
+    assert!(!resp.headers().contains_key("sunset"));
+    let body = resp.json::<serde_json::Value>().await?;
+    assert_eq!(body["user_id"], user_data["user_id"]);
+    assert!(body.get("message").is_none());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn second_version_responds_with_no_content_to_actions() -> anyhow::Result<()> {
+    // Arrange
+    let mut test_app = spawn_app().await?;
+    test_app.use_api_version("v2");
+    let user_data = gen_dummy_user();
+    test_app.signup(&user_data).await?;
+    let credentials = Basic::new(
+        user_data["user_id"].as_str().unwrap().to_owned(),
+        Some(user_data["password"].as_str().unwrap().to_owned()),
+    );
+
+    // Act
+    let resp = test_app.close_account(Some(credentials)).await?;
+
+    // Assert
+    assert_eq!(204, resp.status().as_u16());
+    assert!(resp.bytes().await?.is_empty());
+
+    Ok(())
+}
+
+#[actix_web::test]
+async fn scoped_legacy_paths_are_redirected_to_the_first_version() -> anyhow::Result<()> {
+    // Arrange
+    let test_app = spawn_app().await?;
+
+    // Act
+    let signup = test_app
+        .unversioned(reqwest::Method::POST, "/public/signup")
+        .await?;
+    let my_user = test_app
+        .unversioned(reqwest::Method::GET, "/private/my_user")
+        .await?;
+
+    // Assert
+    assert_eq!(308, signup.status().as_u16());
+    assert_eq!(signup.headers()["location"].to_str()?, "/v1/signup");
+    assert_eq!(308, my_user.status().as_u16());
+    assert_eq!(my_user.headers()["location"].to_str()?, "/v1/users/my_user");
+
+    Ok(())
+}
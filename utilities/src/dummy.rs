@@ -1,14 +1,16 @@
 use fake::faker::internet::en::Password;
-use fake::faker::internet::en::Username;
 use fake::Fake;
 use serde_json::json;
 
 pub fn gen_dummy_user() -> serde_json::Value {
-    let name: String = Username().fake();
     let password: String = Password(8..16).fake();
 
     json!({
-        "user_id": name,
+        "user_id": gen_dummy_user_id(),
         "password": password
     })
 }
+
+pub fn gen_dummy_user_id() -> String {
+    format!("user{}", (10_000_000..99_999_999).fake::<u32>())
+}
@@ -1,4 +1,6 @@
 pub mod dummy;
+pub mod s3_stub;
 pub mod spawn;
 pub mod telemetry;
 pub mod test_app;
+pub mod webhook_receiver;
@@ -0,0 +1,114 @@
+//! A minimal stand-in for an S3 compatible object store, such as MinIO, which
+//! keeps objects in memory. It only supports path style PUT, GET and DELETE of
+//! single objects, and only checks that requests carry a SigV4 authorization header.
+
+use std::collections::HashMap;
+use std::net::TcpListener;
+use std::sync::{Arc, Mutex};
+use track_api_challenge::actix_web::{
+    http::header, rt::spawn, web, App, HttpRequest, HttpResponse, HttpServer,
+};
+use track_api_challenge::anyhow;
+
+type Objects = Arc<Mutex<HashMap<String, (String, Vec<u8>)>>>;
+
+pub struct S3Stub {
+    endpoint: String,
+    objects: Objects,
+}
+
+impl S3Stub {
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The keys of all stored objects, including the bucket name.
+    pub fn keys(&self) -> Vec<String> {
+        self.objects.lock().unwrap().keys().cloned().collect()
+    }
+}
+
+pub async fn spawn_s3_stub() -> anyhow::Result<S3Stub> {
+    let objects = Objects::default();
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let endpoint = format!("http://127.0.0.1:{}", listener.local_addr()?.port());
+
+    let data = web::Data::new(objects.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(data.clone())
+            .route("/{key:.*}", web::put().to(put_object))
+            .route("/{key:.*}", web::get().to(get_object))
+            .route("/{key:.*}", web::delete().to(delete_object))
+    })
+    .listen(listener)?
+    .run();
+
+    spawn(server);
+
+    Ok(S3Stub { endpoint, objects })
+}
+
+fn is_signed(req: &HttpRequest) -> bool {
+    req.headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.starts_with("AWS4-HMAC-SHA256 Credential="))
+        .unwrap_or(false)
+}
+
+async fn put_object(
+    req: HttpRequest,
+    key: web::Path<String>,
+    body: web::Bytes,
+    objects: web::Data<Objects>,
+) -> HttpResponse {
+    if !is_signed(&req) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    let content_type = req
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("application/octet-stream")
+        .to_owned();
+
+    objects
+        .lock()
+        .unwrap()
+        .insert(key.into_inner(), (content_type, body.to_vec()));
+
+    HttpResponse::Ok().finish()
+}
+
+async fn get_object(
+    req: HttpRequest,
+    key: web::Path<String>,
+    objects: web::Data<Objects>,
+) -> HttpResponse {
+    if !is_signed(&req) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    match objects.lock().unwrap().get(key.as_str()) {
+        Some((content_type, data)) => HttpResponse::Ok()
+            .content_type(content_type.as_str())
+            .body(data.clone()),
+        None => HttpResponse::NotFound().finish(),
+    }
+}
+
+async fn delete_object(
+    req: HttpRequest,
+    key: web::Path<String>,
+    objects: web::Data<Objects>,
+) -> HttpResponse {
+    if !is_signed(&req) {
+        return HttpResponse::Forbidden().finish();
+    }
+
+    objects.lock().unwrap().remove(key.as_str());
+
+    HttpResponse::NoContent().finish()
+}
@@ -6,17 +6,23 @@ use serde_json;
 use track_api_challenge::actix_web_httpauth::headers::authorization::Basic;
 use track_api_challenge::anyhow;
 use track_api_challenge::database::Database;
+use track_api_challenge::grpc::proto::user_service_client::UserServiceClient;
+use track_api_challenge::tonic::transport::Channel;
 
 pub struct TestApp {
     app_address: reqwest::Url,
+    grpc_address: Option<String>,
+    api_version: &'static str,
     client: reqwest::Client,
     db: Database,
 }
 
 impl TestApp {
-    pub fn new(app_address: reqwest::Url, db: Database) -> Self {
+    pub fn new(app_address: reqwest::Url, grpc_address: Option<String>, db: Database) -> Self {
         Self {
             app_address,
+            grpc_address,
+            api_version: "v1",
             client: reqwest::Client::new(),
             db,
         }
@@ -25,6 +31,28 @@ impl TestApp {
     pub fn app_address(&self) -> &reqwest::Url {
         &self.app_address
     }
+
+    /// A client of the gRPC server, which must be enabled.
+    pub async fn grpc(&self) -> anyhow::Result<UserServiceClient<Channel>> {
+        let address = self
+            .grpc_address
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("The gRPC server is disabled"))?;
+
+        Ok(UserServiceClient::connect(address).await?)
+    }
+
+    /// Send the requests of the API to `version`, such as `v2`, instead of `v1`.
+    pub fn use_api_version(&mut self, version: &'static str) {
+        self.api_version = version;
+    }
+
+    /// The URL of `path` in the version of the API the requests are sent to.
+    fn api_url(&self, path: &str) -> anyhow::Result<reqwest::Url> {
+        Ok(self
+            .app_address
+            .join(&format!("/{}{path}", self.api_version))?)
+    }
 }
 
 impl TestApp {
@@ -38,10 +66,40 @@ impl TestApp {
         Ok(res)
     }
 
+    pub async fn metrics(&self) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .get(self.app_address.join("/metrics")?)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn openapi(&self) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .get(self.app_address.join("/openapi.json")?)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn docs(&self) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .get(self.app_address.join("/docs/")?)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
     pub async fn signup(&self, data: &serde_json::Value) -> anyhow::Result<reqwest::Response> {
         let res = self
             .client
-            .post(self.app_address.join("/signup")?)
+            .post(self.api_url("/signup")?)
             .json(data)
             .send()
             .await?;
@@ -52,7 +110,7 @@ impl TestApp {
     pub async fn signup_owned(&self, data: serde_json::Value) -> anyhow::Result<reqwest::Response> {
         let res = self
             .client
-            .post(self.app_address.join("/signup")?)
+            .post(self.api_url("/signup")?)
             .json(&data)
             .send()
             .await?;
@@ -60,10 +118,81 @@ impl TestApp {
         Ok(res)
     }
 
+    /// Sign up with an `Idempotency-Key` header.
+    pub async fn signup_with_key(
+        &self,
+        data: &serde_json::Value,
+        key: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .post(self.api_url("/signup")?)
+            .header("Idempotency-Key", key)
+            .json(data)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    /// Sign up, asking for responses in the languages of `accept_language`.
+    pub async fn signup_in(
+        &self,
+        data: &serde_json::Value,
+        accept_language: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .post(self.api_url("/signup")?)
+            .header("Accept-Language", accept_language)
+            .json(data)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    /// Sign up with a body encoded as `content_type`, asking for responses in the
+    /// formats of `accept`.
+    pub async fn signup_encoded(
+        &self,
+        body: Vec<u8>,
+        content_type: &str,
+        accept: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .post(self.api_url("/signup")?)
+            .header("Content-Type", content_type)
+            .header("Accept", accept)
+            .body(body)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
     pub async fn signin(&self, data: &serde_json::Value) -> anyhow::Result<reqwest::Response> {
         let res = self
             .client
-            .post(self.app_address.join("/signin")?)
+            .post(self.api_url("/signin")?)
+            .json(data)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    /// Sign in with an `Idempotency-Key` header.
+    pub async fn signin_with_key(
+        &self,
+        data: &serde_json::Value,
+        key: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let res = self
+            .client
+            .post(self.api_url("/signin")?)
+            .header("Idempotency-Key", key)
             .json(data)
             .send()
             .await?;
@@ -76,9 +205,35 @@ impl TestApp {
         user_id: &str,
         credentials: Option<Basic>,
     ) -> anyhow::Result<reqwest::Response> {
-        let mut req = self
+        let mut req = self.client.get(self.api_url(&format!("/users/{user_id}"))?);
+
+        if let Some(credentials) = credentials {
+            req = Self::add_auth(req, credentials);
+        }
+
+        let res = req.send().await?;
+
+        Ok(res)
+    }
+
+    /// Get a user with a conditional header, such as `If-None-Match`.
+    pub async fn get_user_if(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+        header: &str,
+        value: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
             .client
-            .get(self.app_address.join(&format!("/users/{user_id}"))?);
+            .get(self.api_url(&format!("/users/{user_id}"))?)
+            .header(header, value);
+
+        self.send_with_auth(req, credentials).await
+    }
+
+    pub async fn my_user(&self, credentials: Option<Basic>) -> anyhow::Result<reqwest::Response> {
+        let mut req = self.client.get(self.api_url("/users/my_user")?);
 
         if let Some(credentials) = credentials {
             req = Self::add_auth(req, credentials);
@@ -108,7 +263,7 @@ impl TestApp {
     ) -> anyhow::Result<reqwest::Response> {
         let mut req = self
             .client
-            .patch(self.app_address.join(&format!("/users/{user_id}"))?);
+            .patch(self.api_url(&format!("/users/{user_id}"))?);
 
         if let Some(credentials) = credentials {
             req = Self::add_auth(req, credentials);
@@ -119,11 +274,326 @@ impl TestApp {
         Ok(res)
     }
 
+    /// Update a user with a body of the given content type, such as a JSON Patch.
+    pub async fn patch_user(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+        content_type: &str,
+        body: &serde_json::Value,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .patch(self.api_url(&format!("/users/{user_id}"))?)
+            .header("Content-Type", content_type)
+            .body(body.to_string());
+
+        self.send_with_auth(req, credentials).await
+    }
+
+    /// Update a user, only if it still has the entity tag `if_match`.
+    pub async fn update_user_if_match(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+        user_info: &serde_json::Value,
+        if_match: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .patch(self.api_url(&format!("/users/{user_id}"))?)
+            .header("If-Match", if_match)
+            .json(user_info);
+
+        self.send_with_auth(req, credentials).await
+    }
+
+    pub async fn change_user_id(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+        new_user_id: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut req = self
+            .client
+            .put(self.api_url(&format!("/users/{user_id}/user_id"))?);
+
+        if let Some(credentials) = credentials {
+            req = Self::add_auth(req, credentials);
+        }
+
+        let res = req
+            .json(&serde_json::json!({ "user_id": new_user_id }))
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn update_privacy(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+        privacy: &serde_json::Value,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut req = self
+            .client
+            .put(self.api_url(&format!("/users/{user_id}/privacy"))?);
+
+        if let Some(credentials) = credentials {
+            req = Self::add_auth(req, credentials);
+        }
+
+        let res = req.json(privacy).send().await?;
+
+        Ok(res)
+    }
+
+    pub async fn follow(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/{user_id}/follow"))?;
+        self.send_with_auth(self.client.put(url), credentials).await
+    }
+
+    pub async fn unfollow(
+        &self,
+        user_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/{user_id}/follow"))?;
+        self.send_with_auth(self.client.delete(url), credentials)
+            .await
+    }
+
+    /// List either the `followers` or the `following` of a user. `query` is
+    /// appended to the url as is.
+    pub async fn list_follows(
+        &self,
+        user_id: &str,
+        list: &str,
+        query: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/{user_id}/{list}{query}"))?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    /// Open the stream of events of the signed in user's account.
+    pub async fn account_events(
+        &self,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url("/users/my_user/events")?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    pub async fn follow_requests(
+        &self,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url("/users/my_user/follow_requests")?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    pub async fn approve_follower(
+        &self,
+        follower_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/my_user/followers/{follower_id}"))?;
+        self.send_with_auth(self.client.put(url), credentials).await
+    }
+
+    pub async fn remove_follower(
+        &self,
+        follower_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/my_user/followers/{follower_id}"))?;
+        self.send_with_auth(self.client.delete(url), credentials)
+            .await
+    }
+
+    /// Either `block` or `mute` another user.
+    pub async fn restrict(
+        &self,
+        user_id: &str,
+        kind: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/{user_id}/{kind}"))?;
+        self.send_with_auth(self.client.put(url), credentials).await
+    }
+
+    /// Lift a `block` or `mute` of another user.
+    pub async fn unrestrict(
+        &self,
+        user_id: &str,
+        kind: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/{user_id}/{kind}"))?;
+        self.send_with_auth(self.client.delete(url), credentials)
+            .await
+    }
+
+    /// List the users who have been blocked or muted, with `kind` being either
+    /// `blocks` or `mutes`.
+    pub async fn list_restrictions(
+        &self,
+        kind: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users/my_user/{kind}"))?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    /// Search users. `query` is appended to the url as is.
+    pub async fn search_users(
+        &self,
+        query: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/users{query}"))?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    /// Sign in and return the issued JWT.
+    pub async fn signin_token(&self, data: &serde_json::Value) -> anyhow::Result<String> {
+        let body = self.signin(data).await?.json::<serde_json::Value>().await?;
+
+        Ok(body["token"].as_str().unwrap_or_default().to_owned())
+    }
+
+    pub async fn create_organization(
+        &self,
+        token: &str,
+        name: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.api_url("/organizations")?)
+            .json(&serde_json::json!({ "name": name }));
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn list_organizations(&self, token: &str) -> anyhow::Result<reqwest::Response> {
+        let req = self.client.get(self.api_url("/organizations")?);
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn switch_organization(
+        &self,
+        token: &str,
+        organization_id: Option<&str>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .put(self.api_url("/organizations/active")?)
+            .json(&serde_json::json!({ "organization_id": organization_id }));
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn list_members(&self, token: &str) -> anyhow::Result<reqwest::Response> {
+        self.list_members_with(token, "").await
+    }
+
+    /// List the members of the active organization. `query` is appended to the
+    /// url as is.
+    pub async fn list_members_with(
+        &self,
+        token: &str,
+        query: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .get(self.api_url(&format!("/organizations/active/members{query}"))?);
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn remove_member(
+        &self,
+        token: &str,
+        user_id: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .delete(self.api_url(&format!("/organizations/active/members/{user_id}"))?);
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn create_invitation(
+        &self,
+        token: &str,
+        invitation: &serde_json::Value,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.api_url("/organizations/active/invitations")?)
+            .json(invitation);
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    pub async fn accept_invitation(
+        &self,
+        token: &str,
+        invitation_token: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.api_url(&format!("/invitations/{invitation_token}/accept"))?);
+        Ok(req.bearer_auth(token).send().await?)
+    }
+
+    async fn send_with_auth(
+        &self,
+        mut req: RequestBuilder,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        if let Some(credentials) = credentials {
+            req = Self::add_auth(req, credentials);
+        }
+
+        Ok(req.send().await?)
+    }
+
+    pub async fn upload_avatar(
+        &self,
+        credentials: Option<Basic>,
+        image: Vec<u8>,
+        mime: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let mut req = self.client.put(self.api_url("/users/my_user/avatar")?);
+
+        if let Some(credentials) = credentials {
+            req = Self::add_auth(req, credentials);
+        }
+
+        let part = reqwest::multipart::Part::bytes(image)
+            .file_name("avatar")
+            .mime_str(mime)?;
+        let form = reqwest::multipart::Form::new().part("avatar", part);
+
+        let res = req.multipart(form).send().await?;
+
+        Ok(res)
+    }
+
+    /// Request a url which was returned by the application, such as a blob url.
+    pub async fn get_url(&self, url: &str) -> anyhow::Result<reqwest::Response> {
+        let res = self.client.get(self.app_address.join(url)?).send().await?;
+
+        Ok(res)
+    }
+
     pub async fn close_account(
         &self,
         credentials: Option<Basic>,
     ) -> anyhow::Result<reqwest::Response> {
-        let mut req = self.client.post(self.app_address.join("/close")?);
+        let mut req = self.client.post(self.api_url("/close")?);
 
         if let Some(credentials) = credentials {
             req = Self::add_auth(req, credentials);
@@ -134,6 +604,103 @@ impl TestApp {
         Ok(res)
     }
 
+    /// Close the account with an `Idempotency-Key` header.
+    pub async fn close_account_with_key(
+        &self,
+        credentials: Basic,
+        key: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.api_url("/close")?)
+            .header("Idempotency-Key", key);
+
+        self.send_with_auth(req, Some(credentials)).await
+    }
+
+    /// Send a request to `path` without a version prefix, and without following
+    /// redirects.
+    pub async fn unversioned(
+        &self,
+        method: reqwest::Method,
+        path: &str,
+    ) -> anyhow::Result<reqwest::Response> {
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .build()?;
+        let res = client
+            .request(method, self.app_address.join(path)?)
+            .send()
+            .await?;
+
+        Ok(res)
+    }
+
+    /// Send a GraphQL query, which is served without a version prefix.
+    pub async fn graphql(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.app_address.join("/graphql")?)
+            .json(&serde_json::json!({ "query": query, "variables": variables }));
+
+        self.send_with_auth(req, credentials).await
+    }
+
+    pub async fn create_webhook(
+        &self,
+        subscription: &serde_json::Value,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let req = self
+            .client
+            .post(self.api_url("/webhooks")?)
+            .json(subscription);
+        self.send_with_auth(req, credentials).await
+    }
+
+    pub async fn list_webhooks(
+        &self,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url("/webhooks")?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    pub async fn delete_webhook(
+        &self,
+        subscription_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/webhooks/{subscription_id}"))?;
+        self.send_with_auth(self.client.delete(url), credentials)
+            .await
+    }
+
+    /// List webhook deliveries. `query` is appended to the url as is.
+    pub async fn list_deliveries(
+        &self,
+        query: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/webhooks/deliveries{query}"))?;
+        self.send_with_auth(self.client.get(url), credentials).await
+    }
+
+    pub async fn replay_delivery(
+        &self,
+        delivery_id: &str,
+        credentials: Option<Basic>,
+    ) -> anyhow::Result<reqwest::Response> {
+        let url = self.api_url(&format!("/webhooks/deliveries/{delivery_id}/replay"))?;
+        self.send_with_auth(self.client.post(url), credentials)
+            .await
+    }
+
     pub async fn base_url(&self) -> anyhow::Result<reqwest::Response> {
         let res = self.client.post(self.app_address.join("/")?).send().await?;
 
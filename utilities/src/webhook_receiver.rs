@@ -0,0 +1,112 @@
+//! A local endpoint for webhook deliveries, which records every request it
+//! receives and answers with a status that tests can change.
+
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicU16, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use track_api_challenge::actix_web::{
+    http::StatusCode, rt::spawn, rt::time::sleep, web, App, HttpRequest, HttpResponse, HttpServer,
+};
+use track_api_challenge::anyhow;
+
+/// A request received by the [`WebhookReceiver`].
+#[derive(Clone, Debug)]
+pub struct ReceivedRequest {
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl ReceivedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> serde_json::Value {
+        serde_json::from_slice(&self.body).expect("Failed to parse the webhook body")
+    }
+}
+
+#[derive(Default)]
+struct State {
+    requests: Mutex<Vec<ReceivedRequest>>,
+    status: AtomicU16,
+}
+
+pub struct WebhookReceiver {
+    url: String,
+    state: Arc<State>,
+}
+
+impl WebhookReceiver {
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Answer the following requests with `status`.
+    pub fn respond_with(&self, status: u16) {
+        self.state.status.store(status, Ordering::SeqCst);
+    }
+
+    pub fn requests(&self) -> Vec<ReceivedRequest> {
+        self.state.requests.lock().unwrap().clone()
+    }
+
+    /// Wait until at least `count` requests have been received, and return them.
+    pub async fn wait_for(&self, count: usize) -> Vec<ReceivedRequest> {
+        for _ in 0..100 {
+            let requests = self.requests();
+            if requests.len() >= count {
+                return requests;
+            }
+            sleep(Duration::from_millis(50)).await;
+        }
+        panic!(
+            "Expected {count} webhook requests, received {}",
+            self.requests().len()
+        );
+    }
+}
+
+pub async fn spawn_webhook_receiver() -> anyhow::Result<WebhookReceiver> {
+    let state = Arc::new(State::default());
+    state.status.store(200, Ordering::SeqCst);
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    let url = format!("http://127.0.0.1:{}/hook", listener.local_addr()?.port());
+
+    let data = web::Data::from(state.clone());
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(data.clone())
+            .default_service(web::to(receive))
+    })
+    .listen(listener)?
+    .run();
+
+    spawn(server);
+
+    Ok(WebhookReceiver { url, state })
+}
+
+async fn receive(req: HttpRequest, body: web::Bytes, state: web::Data<State>) -> HttpResponse {
+    let headers = req
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            let value = value.to_str().unwrap_or_default().to_owned();
+            (name.to_string(), value)
+        })
+        .collect();
+
+    state.requests.lock().unwrap().push(ReceivedRequest {
+        headers,
+        body: body.to_vec(),
+    });
+
+    let status = StatusCode::from_u16(state.status.load(Ordering::SeqCst))
+        .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    HttpResponse::build(status).finish()
+}